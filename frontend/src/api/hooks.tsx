@@ -10,6 +10,7 @@ import useFetch from "fetch-suspense"
 import {
     GenerateTableDataRequest,
     GenerateTableDataResponse,
+    StoredTemplate,
     Template,
 } from "./types"
 
@@ -47,7 +48,8 @@ export const useOverrides = () => {
 }
 
 export const useTemplates = () => {
-    return useFetch(TEMPLATES) as Template[]
+    const templates = useFetch(TEMPLATES) as StoredTemplate[]
+    return templates.map((it): Template => it.request)
 }
 
 export const useFilterColumns = (column: string, search = ".*") => {
@@ -22,3 +22,10 @@ export interface GenerateTableDataResponse {
 export interface Template extends GenerateTableDataRequest {
     name: string
 }
+
+/** A template with who can see it, as listed by the server */
+export interface StoredTemplate {
+    owner: string
+    shared: boolean
+    request: Template
+}
@@ -14,7 +14,7 @@ import {
     TableProps,
 } from "@mui/material"
 import { styled } from "@mui/material/styles"
-import { fancyZip } from "../utils"
+import { fancyZip, labelSubtotals } from "../utils"
 import { forwardRef, useState } from "react"
 import SummarizeIcon from "@mui/icons-material/Summarize"
 import { SummaryStats } from "./SummaryStats"
@@ -132,8 +132,9 @@ const DataTable = forwardRef<HTMLTableSectionElement, DataTableProps>(
         if (error || !data) {
             return <>{error}</>
         }
-        const headers = data.columns.map((it) => it.name)
-        const zipped = fancyZip(data.columns.map((col) => col.values))
+        const columns = labelSubtotals(data.columns, props.input.groupby)
+        const headers = columns.map((it) => it.name)
+        const zipped = fancyZip(columns.map((col) => col.values))
         const [dialogOpen, setDialogOpen] = useState(false)
 
         const summarizeTable = () => {
@@ -169,7 +170,7 @@ const DataTable = forwardRef<HTMLTableSectionElement, DataTableProps>(
                 </Box>
 
                 <SummaryStats
-                    table={{ columns: data.columns }}
+                    table={{ columns }}
                     openState={[dialogOpen, setDialogOpen]}
                 />
             </Paper>
@@ -1,7 +1,7 @@
 import { AdditionalRows, Filter } from "./aside/types"
 import { Filters } from "./utils/NestedKVStoreReducer"
 import { Rows } from "./aside/AddRow"
-import { Template } from "./api/types"
+import { GenerateTableDataResponse, Template } from "./api/types"
 
 export const hasValue = <T>(it: T | null | undefined): boolean =>
     it !== undefined && it !== null
@@ -9,6 +9,41 @@ export const hasValue = <T>(it: T | null | undefined): boolean =>
 export const fancyZip = <T>(rows: T[][]): T[][] =>
     rows[0].map((_, c) => rows.map((row) => row[c]))
 
+/** Marks subtotal rows of the result. Not a dimension, hence not displayed */
+export const GROUPING_ID = "grouping_id"
+
+/**
+ * Labels the rolled up dimensions of subtotal rows "Total" and drops the grouping_id column.
+ * Bit `n-1-i` of grouping_id is set if `groupby[i]` is rolled up, other nulls are nulls in the data
+ */
+export const labelSubtotals = (
+    columns: GenerateTableDataResponse["columns"],
+    groupby: string[],
+): GenerateTableDataResponse["columns"] => {
+    const ids = columns.find((it) => it.name === GROUPING_ID)?.values
+    if (ids === undefined) {
+        return columns
+    }
+    const n = groupby.length
+    return columns
+        .filter((it) => it.name !== GROUPING_ID)
+        .map((column) => {
+            const i = groupby.indexOf(column.name)
+            if (i === -1) {
+                return column
+            }
+            const bit = 2 ** (n - 1 - i)
+            return {
+                ...column,
+                values: column.values.map((value, row) =>
+                    Math.floor(Number(ids[row]) / bit) % 2 === 1
+                        ? "Total"
+                        : value,
+                ),
+            }
+        })
+}
+
 export const mapFilters = (f: Filters): Filter[][] =>
     Object.values(f).map((ands) =>
         Object.values(ands).filter(
@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use ultibi::CalcParameter;
+use ultibi::{CalcParamType, CalcParameter};
 //pub(crate)static FRTB_CALC_PARAMS_MAP: Lazy<HashMap<&'static str,  &'static CalcParameter>> = Lazy::new(||{
 //    FRTB_CALC_PARAMS.iter()
 //        .map(|param| (param.name, param))
@@ -12,36 +12,45 @@ pub(crate) static FRTB_CALC_PARAMS: Lazy<Vec<CalcParameter>> = Lazy::new(|| {
             name: "jurisdiction".to_string(),
             default: Some("BCBS".to_string()),
             type_hint: Some("String: BCBS/CRR2".to_string()),
+            param_type: CalcParamType::Enum {
+                choices: jurisdictions(),
+            },
         },
         CalcParameter {
             name: "reporting_ccy".to_string(),
             default: Some("USD".to_string()),
             type_hint: Some("3 digit String".to_string()),
+            param_type: CalcParamType::String { len: Some(3) },
         },
         CalcParameter {
             name: "drc_offset".to_string(),
             default: Some("true".to_string()),
             type_hint: Some("bool".to_string()),
+            param_type: CalcParamType::Bool,
         },
         CalcParameter {
             name: "apply_fx_curv_div".to_string(),
             default: Some("true".to_string()),
             type_hint: Some("bool".to_string()),
+            param_type: CalcParamType::Bool,
         },
         CalcParameter {
             name: "exotic_rrao_weight".to_string(),
             default: None,
             type_hint: Some("float".to_string()),
+            param_type: CalcParamType::Float,
         },
         CalcParameter {
             name: "other_rrao_weight".to_string(),
             default: None,
             type_hint: Some("float".to_string()),
+            param_type: CalcParamType::Float,
         },
         CalcParameter {
             name: "erm2_ccys".to_string(),
             default: None,
             type_hint: Some("vector of strings".to_string()),
+            param_type: CalcParamType::Strings,
         },
     ];
 
@@ -50,634 +59,937 @@ pub(crate) static FRTB_CALC_PARAMS: Lazy<Vec<CalcParameter>> = Lazy::new(|| {
             "com_delta_diff_cty_rho_per_bucket_base".to_string(),
             None,
             Some("vector 11".to_string()),
+            CalcParamType::Vector { len: Some(11) },
         ),
         (
             "com_delta_rho_diff_loc_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "com_delta_rho_diff_tenor_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "com_delta_rho_overwrite_base".to_string(),
             None,
-            Some("float".to_string()),
+            Some("JSON: rhotype, column, col_equals, value, oneway".to_string()),
+            CalcParamType::Json,
         ),
         (
             "com_vega_rho_bucket_base".to_string(),
             None,
             Some("vector 11".to_string()),
+            CalcParamType::Vector { len: Some(11) },
         ),
         (
-            "com_base_opt_mat_vega_rho_base".to_string(),
+            "com_opt_mat_vega_rho_base".to_string(),
             None,
             Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "csr_nonsec_delta_diff_tenor_rho_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "csr_nonsec_delta_diff_name_rho_per_bucket_base".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 18.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_nonsec_delta_diff_basis_rho_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "csr_nonsec_vega_diff_name_rho_per_bucket_base".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 18.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_nonsec_opt_mat_vega_rho_base".to_string(),
             None,
             Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "csr_ctp_delta_diff_tenor_rho_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "csr_ctp_delta_diff_name_rho_per_bucket_base".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_ctp_diff_basis_rho_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "csr_ctp_vega_diff_name_rho_per_bucket_base".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_ctp_opt_mat_vega_rho_base".to_string(),
             None,
             Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "csr_sec_nonctp_delta_diff_tenor_rho_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "csr_sec_nonctp_delta_diff_name_rho_per_bucket_base".to_string(),
             None,
             Some("vector 25".to_string()),
+            CalcParamType::Vector { len: Some(25) },
         ),
         (
             "csr_sec_nonctp_delta_diff_tranche_rho_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "csr_sec_nonctp_vega_rho_diff_name_per_bucket_base".to_string(),
             None,
             Some("vector 25".to_string()),
+            CalcParamType::Vector { len: Some(25) },
         ),
         (
             "csr_sec_nonctp_opt_mat_vega_rho_base".to_string(),
             None,
             Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "eq_delta_diff_name_rho_per_bucket_base".to_string(),
             None,
             Some("vector 13".to_string()),
+            CalcParamType::Vector { len: Some(13) },
         ),
         (
             "eq_delta_diff_type_rho_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "eq_vega_rho_diff_name_per_bucket_base".to_string(),
             None,
             Some("vector 13".to_string()),
+            CalcParamType::Vector { len: Some(13) },
         ),
         (
             "eq_opt_mat_vega_rho_base".to_string(),
             None,
             Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "girr_delta_rho_same_curve_base".to_string(),
             None,
             Some("matrix 10x10".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(10),
+                cols: Some(10),
+            },
         ),
         (
             "girr_delta_rho_diff_curve_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_delta_rho_infl_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_delta_rho_xccy_base".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
     ];
-    for (name, default, type_hint) in params {
+    for (name, default, type_hint, param_type) in params {
         res.push(CalcParameter {
             name,
             default,
             type_hint,
+            param_type,
         });
     }
     // per scenario params
-    let params: [(String, Option<String>, Option<String>); 93] = [
+    let params: [(String, Option<String>, Option<String>, CalcParamType); 93] = [
         // HIGH
         (
             "fx_delta_gamma_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "fx_opt_mat_vega_rho_high".to_string(),
             None,
-            Some("float".to_string()),
+            Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "fx_vega_gamma_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "fx_curv_gamma_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "com_delta_gamma_high".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
             "com_vega_gamma_high".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
-            "com_curv_gamma_high".to_string(),
+            "commodity_curv_gamma_high".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
             "com_curv_diff_name_rho_per_bucket_high".to_string(),
             None,
-            Some("vector 11x11".to_string()),
+            Some("vector 11".to_string()),
+            CalcParamType::Vector { len: Some(11) },
         ),
         (
             "csr_nonsec_delta_gamma_high".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_nonsec_curv_gamma_high".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_nonsec_curv_diff_name_rho_per_bucket_high".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 18.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_nonsec_vega_gamma_high".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_delta_gamma_high".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_vega_gamma_high".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_curv_diff_name_rho_per_bucket_high".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_ctp_curv_gamma_high".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_sec_nonctp_delta_gamma_high".to_string(),
             None,
             Some("matrix 25x25".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(25),
+                cols: Some(25),
+            },
         ),
         (
             "csr_sec_nonctp_vega_gamma_high".to_string(),
             None,
             Some("matrix 25x25".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(25),
+                cols: Some(25),
+            },
         ),
         (
             "csr_sec_nonctp_curv_diff_name_rho_per_bucket_high".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_sec_nonctp_curv_gamma_high".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "eq_delta_gamma_high".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_vega_gamma_high".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_curv_gamma_high".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_curv_diff_name_rho_per_bucket_high".to_string(),
             None,
             Some("vector 13".to_string()),
+            CalcParamType::Vector { len: Some(13) },
         ),
         (
             "girr_delta_gamma_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_delta_gamma_erm2_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_vega_rho_high".to_string(),
             None,
             Some("matrix 35x35".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(35),
+                cols: Some(35),
+            },
         ),
         (
             "girr_vega_gamma_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_vega_gamma_erm2_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_curv_gamma_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_curv_gamma_erm2_high".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         // Medium
         (
             "fx_delta_gamma_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "fx_opt_mat_vega_rho_medium".to_string(),
             None,
-            Some("float".to_string()),
+            Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "fx_vega_gamma_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "fx_curv_gamma_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "com_delta_gamma_medium".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
             "com_vega_gamma_medium".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
-            "com_curv_gamma_medium".to_string(),
+            "commodity_curv_gamma_medium".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
             "com_curv_diff_name_rho_per_bucket_medium".to_string(),
             None,
-            Some("vector 11x11".to_string()),
+            Some("vector 11".to_string()),
+            CalcParamType::Vector { len: Some(11) },
         ),
         (
             "csr_nonsec_delta_gamma_medium".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_nonsec_curv_gamma_medium".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_nonsec_curv_diff_name_rho_per_bucket_medium".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 18.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_nonsec_vega_gamma_medium".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_delta_gamma_medium".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_vega_gamma_medium".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_curv_diff_name_rho_per_bucket_medium".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_ctp_curv_gamma_medium".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_sec_nonctp_delta_gamma_medium".to_string(),
             None,
             Some("matrix 25x25".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(25),
+                cols: Some(25),
+            },
         ),
         (
             "csr_sec_nonctp_vega_gamma_medium".to_string(),
             None,
             Some("matrix 25x25".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(25),
+                cols: Some(25),
+            },
         ),
         (
             "csr_sec_nonctp_curv_diff_name_rho_per_bucket_medium".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_sec_nonctp_curv_gamma_medium".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "eq_delta_gamma_medium".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_vega_gamma_medium".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_curv_gamma_medium".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_curv_diff_name_rho_per_bucket_medium".to_string(),
             None,
             Some("vector 13".to_string()),
+            CalcParamType::Vector { len: Some(13) },
         ),
         (
             "girr_delta_gamma_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_delta_gamma_erm2_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_vega_rho_medium".to_string(),
             None,
             Some("matrix 35x35".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(35),
+                cols: Some(35),
+            },
         ),
         (
             "girr_vega_gamma_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_vega_gamma_erm2_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_curv_gamma_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_curv_gamma_erm2_medium".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         // Low
         (
             "fx_delta_gamma_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "fx_opt_mat_vega_rho_low".to_string(),
             None,
-            Some("float".to_string()),
+            Some("matrix 5x5".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(5),
+                cols: Some(5),
+            },
         ),
         (
             "fx_vega_gamma_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "fx_curv_gamma_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "com_delta_gamma_low".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
             "com_vega_gamma_low".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
-            "com_curv_gamma_low".to_string(),
+            "commodity_curv_gamma_low".to_string(),
             None,
             Some("matrix 11x11".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(11),
+                cols: Some(11),
+            },
         ),
         (
             "com_curv_diff_name_rho_per_bucket_low".to_string(),
             None,
-            Some("vector 11x11".to_string()),
+            Some("vector 11".to_string()),
+            CalcParamType::Vector { len: Some(11) },
         ),
         (
             "csr_nonsec_delta_gamma_low".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_nonsec_curv_gamma_low".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_nonsec_curv_diff_name_rho_per_bucket_low".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 18.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_nonsec_vega_gamma_low".to_string(),
             None,
             Some("matrix-depends on regulation. 18x18 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_delta_gamma_low".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_vega_gamma_low".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_ctp_curv_diff_name_rho_per_bucket_low".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_ctp_curv_gamma_low".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "csr_sec_nonctp_delta_gamma_low".to_string(),
             None,
             Some("matrix 25x25".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(25),
+                cols: Some(25),
+            },
         ),
         (
             "csr_sec_nonctp_vega_gamma_low".to_string(),
             None,
             Some("matrix 25x25".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(25),
+                cols: Some(25),
+            },
         ),
         (
             "csr_sec_nonctp_curv_diff_name_rho_per_bucket_low".to_string(),
             None,
             Some("vector-depends on regulation. For BCBS 16.".to_string()),
+            CalcParamType::Vector { len: None },
         ),
         (
             "csr_sec_nonctp_curv_gamma_low".to_string(),
             None,
             Some("matrix-depends on regulation. 16x16 for BCBS.".to_string()),
+            CalcParamType::Matrix {
+                rows: None,
+                cols: None,
+            },
         ),
         (
             "eq_delta_gamma_low".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_vega_gamma_low".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_curv_gamma_low".to_string(),
             None,
             Some("matrix 13x13".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(13),
+                cols: Some(13),
+            },
         ),
         (
             "eq_curv_diff_name_rho_per_bucket_low".to_string(),
             None,
             Some("vector 13".to_string()),
+            CalcParamType::Vector { len: Some(13) },
         ),
         (
             "girr_delta_gamma_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_delta_gamma_erm2_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_vega_rho_low".to_string(),
             None,
             Some("matrix 35x35".to_string()),
+            CalcParamType::Matrix {
+                rows: Some(35),
+                cols: Some(35),
+            },
         ),
         (
             "girr_vega_gamma_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_vega_gamma_erm2_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_curv_gamma_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
         (
             "girr_curv_gamma_erm2_low".to_string(),
             None,
             Some("float".to_string()),
+            CalcParamType::Float,
         ),
     ];
 
-    for (name, default, type_hint) in params {
+    for (name, default, type_hint, param_type) in params {
         res.push(CalcParameter {
             name,
             default,
             type_hint,
+            param_type,
         });
     }
 
     res
 });
+
+/// See [crate::prelude::Jurisdiction]
+fn jurisdictions() -> Vec<String> {
+    let mut res = vec!["BCBS".to_string()];
+    #[cfg(feature = "CRR2")]
+    res.push("CRR2".to_string());
+    res
+}
@@ -1,3 +1,4 @@
+use crate::metadata::{amount, ratio};
 use crate::prelude::*;
 use polars::chunked_array::ops::SortMultipleOptions;
 use ultibi::{
@@ -233,6 +234,7 @@ fn drc_nonsec_charge_calculator(rtrn: ReturnMetric, offset: bool, weights: Expr)
 }
 
 pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["DRC", "nonSec"], Some("MAR22"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "DRC nonSec GrossJTD".to_string(),
@@ -240,6 +242,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: metadata("Gross jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC nonSec GrossJTD Scaled".to_string(),
@@ -247,6 +250,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: metadata("Gross jump to default scaled by maturity"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC nonSec CapitalCharge".to_string(),
@@ -254,6 +258,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: metadata("Capital charge"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC nonSec NetLongJTD".to_string(),
@@ -261,6 +266,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: metadata("Net long jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC nonSec NetShortJTD".to_string(),
@@ -268,6 +274,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: metadata("Net short jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC nonSec NetLongJTD Weighted".to_string(),
@@ -275,6 +282,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: metadata("Weighted net long jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC nonSec NetAbsShortJTD Weighted".to_string(),
@@ -282,6 +290,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: metadata("Weighted net short jump to default"),
         }),
         // HBR Only makes sence at Bucket level
         Measure::Base(BaseMeasure {
@@ -290,6 +299,7 @@ pub(crate) fn drc_nonsec_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_nonSec"))),
             calc_params: vec![],
+            metadata: ratio(metadata("Hedge benefit ratio")),
         }),
     ]
 }
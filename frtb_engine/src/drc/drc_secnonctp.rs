@@ -1,3 +1,4 @@
+use crate::metadata::{amount, ratio};
 use crate::prelude::*;
 use ultibi::{
     polars::prelude::{apply_multiple, df, ChunkApply, DataType, GetOutput, IntoSeries},
@@ -183,6 +184,7 @@ fn drc_secnonctp_charge_calculator(rtrn: ReturnMetric) -> Expr {
 }
 
 pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["DRC", "Sec nonCTP"], Some("MAR22"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP GrossJTD".to_string(),
@@ -190,6 +192,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_Sec_nonCTP"))),
             calc_params: vec![],
+            metadata: metadata("Gross jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP GrossJTD Scaled".to_string(),
@@ -197,6 +200,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_Sec_nonCTP"))),
             calc_params: vec![],
+            metadata: metadata("Gross jump to default scaled by maturity"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP CapitalCharge".to_string(),
@@ -204,6 +208,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_Sec_nonCTP"))),
             calc_params: vec![],
+            metadata: metadata("Capital charge"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP NetLongJTD".to_string(),
@@ -211,6 +216,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_Sec_nonCTP"))),
             calc_params: vec![],
+            metadata: metadata("Net long jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP NetShortJTD".to_string(),
@@ -218,6 +224,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_Sec_nonCTP"))),
             calc_params: vec![],
+            metadata: metadata("Net short jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP NetLongJTD Weighted".to_string(),
@@ -225,6 +232,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_Sec_nonCTP"))),
             calc_params: vec![],
+            metadata: metadata("Weighted net long jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP NetShortJTD Weighted".to_string(),
@@ -232,6 +240,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_Sec_nonCTP"))),
             calc_params: vec![],
+            metadata: metadata("Weighted net short jump to default"),
         }),
         Measure::Base(BaseMeasure {
             name: "DRC Sec nonCTP HBR".to_string(),
@@ -239,6 +248,7 @@ pub(crate) fn drc_secnonctp_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("RiskClass").eq(lit("DRC_SecNonCTP"))),
             calc_params: vec![],
+            metadata: ratio(metadata("Hedge benefit ratio")),
         }),
     ]
 }
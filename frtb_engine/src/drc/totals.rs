@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use ultibi::polars::lazy::dsl::col;
 use ultibi::polars::prelude::Expr;
 use ultibi::{DependantMeasure, Measure, PolarsResult, CPM};
@@ -8,6 +9,7 @@ pub(crate) fn drc_charge(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn drc_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["DRC"], Some("MAR22"), description);
     vec![DependantMeasure {
         name: "DRC Charge".to_string(),
         calculator: std::sync::Arc::new(drc_charge),
@@ -19,6 +21,7 @@ pub(crate) fn drc_total_measures() -> Vec<Measure> {
             ),
         ],
         calc_params: vec![],
+        metadata: metadata("Capital charge"),
     }
     .into()]
 }
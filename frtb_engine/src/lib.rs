@@ -12,6 +12,7 @@ pub mod calc_params;
 pub mod docs;
 mod helpers;
 pub mod measures;
+mod metadata;
 pub mod prelude;
 mod risk_weights;
 #[cfg(feature = "CRR2")]
@@ -23,12 +24,14 @@ use prelude::calc_params::FRTB_CALC_PARAMS;
 use ultibi::cache::{Cache, CacheableDataSet};
 use ultibi::datasource::DataSource;
 use ultibi::errors::{UltiResult, UltimaErr};
+use ultibi::hierarchy::Hierarchy;
 use ultibi::new::NewSourcedDataSet;
 use ultibi::polars::prelude::{
     col, lit, when, AnyValue, Expr, LazyFrame, Literal, LiteralValue, NamedFrom, PolarsResult,
     Series, NULL,
 };
 use ultibi::reports::report::ReportersMap;
+use ultibi::window::WindowMeasure;
 use ultibi::{overridable_columns, CalcParameter, DataSet, Measure, MeasuresMap, CPM};
 //use polars:: series::Series, lazy::dsl::when};
 use prelude::{drc::common::drc_scalinng, frtb_measure_vec};
@@ -42,6 +45,9 @@ pub struct FRTBDataSet {
     pub measures: MeasuresMap,
     pub config: BTreeMap<String, String>,
     pub cache: Cache,
+    pub hierarchies: Vec<Hierarchy>,
+    pub windows: Vec<WindowMeasure>,
+    pub snapshots: Option<Vec<String>>,
 }
 impl FRTBDataSet {
     /// Helper function which appends bespoke measures to self.measures
@@ -63,6 +69,9 @@ impl NewSourcedDataSet for FRTBDataSet {
             measures: mm,
             config,
             cache: Cache::default(),
+            hierarchies: vec![],
+            windows: vec![],
+            snapshots: None,
         };
         res.with_measures(frtb_measure_vec());
         res
@@ -90,6 +99,33 @@ impl DataSet for FRTBDataSet {
     fn get_measures(&self) -> &MeasuresMap {
         &self.measures
     }
+    fn build_params(&self) -> &CPM {
+        &self.config
+    }
+    fn hierarchies(&self) -> &[Hierarchy] {
+        &self.hierarchies
+    }
+    fn set_hierarchies(&mut self, hierarchies: Vec<Hierarchy>) -> UltiResult<()> {
+        self.hierarchies = hierarchies;
+        Ok(())
+    }
+    fn windows(&self) -> &[WindowMeasure] {
+        &self.windows
+    }
+    fn set_windows(&mut self, windows: Vec<WindowMeasure>) -> UltiResult<()> {
+        self.windows = windows;
+        Ok(())
+    }
+    fn snapshots(&self) -> UltiResult<Vec<String>> {
+        match &self.snapshots {
+            Some(snapshots) => Ok(snapshots.clone()),
+            None => self.scan_snapshots(),
+        }
+    }
+    fn set_snapshots(&mut self, snapshots: Vec<String>) -> UltiResult<()> {
+        self.snapshots = Some(snapshots);
+        Ok(())
+    }
     /// TODO - this should be done once only
     fn calc_params(&self) -> Vec<CalcParameter> {
         let mut res = vec![];
@@ -105,6 +141,11 @@ impl DataSet for FRTBDataSet {
         hash_res.into_iter().collect()
     }
 
+    /// A typo such as `jurisdicton` would otherwise silently yield BCBS numbers
+    fn strict_calc_params(&self) -> bool {
+        true
+    }
+
     /// Adds: BCBS buckets, CRR2 Buckets
     /// Adds: SensWeights, CurvatureRiskWeight, SensWeightsCRR2, SeniorityRank
     fn prepare_frame(&self, lf: LazyFrame) -> UltiResult<LazyFrame> {
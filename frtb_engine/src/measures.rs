@@ -1,6 +1,6 @@
 //! This file defines all the measures, associated with this library
 
-use ultibi::{BaseMeasure, Measure};
+use ultibi::{BaseMeasure, Measure, MeasureMetadata};
 
 use crate::sbm::common::sens_weights;
 
@@ -50,6 +50,7 @@ use crate::rrao::rrao_measures;
 
 use crate::totals::sa_total_measures;
 
+use crate::metadata::amount;
 use crate::prelude::total_sensis_sum;
 use ultibi::CPM;
 
@@ -62,6 +63,11 @@ pub fn frtb_measure_vec() -> Vec<Measure> {
             aggregation: Some("first".into()),
             precomputefilter: None,
             calc_params: vec![],
+            metadata: MeasureMetadata {
+                description: Some("Risk weights of the sensitivities".to_string()),
+                category: vec!["Sensitivities".to_string()],
+                ..Default::default()
+            },
         }),
         Measure::Base(BaseMeasure {
             name: "Total Sens".to_string(),
@@ -69,6 +75,7 @@ pub fn frtb_measure_vec() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: None,
             calc_params: vec![],
+            metadata: amount(&["Sensitivities"], None, "Sum of sensitivities"),
         }),
     ];
 
@@ -0,0 +1,39 @@
+//! Metadata of FRTB measures, set where each measure is defined
+
+use ultibi::MeasureMetadata;
+
+/// FRTB amounts are in the currency of the `reporting_ccy` calc param
+pub(crate) const REPORTING_CCY: &str = "reporting_ccy";
+
+/// An amount in the reporting currency
+pub(crate) fn amount(
+    category: &[&str],
+    reference: Option<&str>,
+    description: &str,
+) -> MeasureMetadata {
+    MeasureMetadata {
+        description: Some(description.to_string()),
+        reference: reference.map(str::to_string),
+        unit: Some(REPORTING_CCY.to_string()),
+        format: Some("#,##0.00".to_string()),
+        category: category.iter().map(|c| c.to_string()).collect(),
+        hidden: false,
+    }
+}
+
+/// A ratio (eg HBR) rather than an amount
+pub(crate) fn ratio(metadata: MeasureMetadata) -> MeasureMetadata {
+    MeasureMetadata {
+        unit: None,
+        format: Some("0.00%".to_string()),
+        ..metadata
+    }
+}
+
+/// Building blocks of other measures
+pub(crate) fn hidden(metadata: MeasureMetadata) -> MeasureMetadata {
+    MeasureMetadata {
+        hidden: true,
+        ..metadata
+    }
+}
@@ -3,6 +3,7 @@
 //! ,
 
 use crate::helpers::first_appearance;
+use crate::metadata::amount;
 use crate::prelude::get_optional_parameter;
 use crate::statics::MEDIUM_CORR_SCENARIO;
 use polars::lazy::dsl::apply_multiple;
@@ -113,6 +114,7 @@ pub(crate) fn rrao_calc(exotic_weight: f64, other_weight: f64) -> Expr {
 
 /// Exporting Measures
 pub(crate) fn rrao_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["RRAO"], Some("MAR23"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "Exotic RRAO Notional".to_string(),
@@ -120,6 +122,7 @@ pub(crate) fn rrao_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("EXOTIC_RRAO").or(col("OTHER_RRAO"))),
             calc_params: vec![],
+            metadata: metadata("Notional"),
         }),
         Measure::Base(BaseMeasure {
             name: "Other RRAO Notional".to_string(),
@@ -127,6 +130,7 @@ pub(crate) fn rrao_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("EXOTIC_RRAO").or(col("OTHER_RRAO"))),
             calc_params: vec![],
+            metadata: metadata("Notional"),
         }),
         Measure::Base(BaseMeasure {
             name: "Exotic RRAO Charge".to_string(),
@@ -134,6 +138,7 @@ pub(crate) fn rrao_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("EXOTIC_RRAO").or(col("OTHER_RRAO"))),
             calc_params: vec![],
+            metadata: metadata("Capital charge"),
         }),
         Measure::Base(BaseMeasure {
             name: "Other RRAO Charge".to_string(),
@@ -141,6 +146,7 @@ pub(crate) fn rrao_measures() -> Vec<Measure> {
             aggregation: None,
             precomputefilter: Some(col("EXOTIC_RRAO").or(col("OTHER_RRAO"))),
             calc_params: vec![],
+            metadata: metadata("Capital charge"),
         }),
         Measure::Base(BaseMeasure {
             name: "RRAO Charge".to_string(),
@@ -148,6 +154,7 @@ pub(crate) fn rrao_measures() -> Vec<Measure> {
             aggregation: Some("scalar".into()),
             precomputefilter: Some(col("EXOTIC_RRAO").or(col("OTHER_RRAO"))),
             calc_params: vec![],
+            metadata: metadata("Capital charge"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::{amount, hidden};
 use crate::{
     prelude::{
         get_optional_parameter, get_optional_parameter_array, ReturnMetric, ScenarioConfig,
@@ -124,6 +125,13 @@ fn com_curv_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn com_curv_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "Commodity", "Curvature"],
+            Some("MAR21.5"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "Commodity CurvatureDelta".to_string(),
@@ -135,6 +143,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity CurvatureDelta Weighted".to_string(),
@@ -146,6 +155,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity PnLup".to_string(),
@@ -157,6 +167,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity PnLdown".to_string(),
@@ -168,6 +179,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity CVRup".to_string(),
@@ -179,6 +191,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the upward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity CVRdown".to_string(),
@@ -190,6 +203,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the downward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature KbPlus Medium".to_string(),
@@ -201,6 +215,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature KbMinus Medium".to_string(),
@@ -212,6 +229,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature Kb Medium".to_string(),
@@ -223,6 +243,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature Sb Medium".to_string(),
@@ -234,6 +255,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under medium correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity CurvatureCharge Medium".to_string(),
@@ -245,6 +269,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature KbPlus Low".to_string(),
@@ -256,6 +281,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature KbMinus Low".to_string(),
@@ -267,6 +295,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature Kb Low".to_string(),
@@ -278,6 +309,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature Sb Low".to_string(),
@@ -289,6 +321,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under low correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity CurvatureCharge Low".to_string(),
@@ -300,6 +335,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature KbPlus High".to_string(),
@@ -311,6 +347,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature KbMinus High".to_string(),
@@ -322,6 +361,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature Kb High".to_string(),
@@ -333,6 +375,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity Curvature Sb High".to_string(),
@@ -344,6 +387,9 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under high correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity CurvatureCharge High".to_string(),
@@ -355,6 +401,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity CurvatureCharge MAX".to_string(),
@@ -366,6 +413,7 @@ pub(crate) fn com_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,6 +1,7 @@
 //! Commodity Delta Risk Charge
 //! TODO Commodity RiskFactor should be of the form ...CCY (same as FX, where CCY is the reporting CCY)
 
+use crate::metadata::amount;
 use crate::prelude::*;
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{
@@ -268,6 +269,8 @@ fn com_delta_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn com_delta_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "Commodity", "Delta"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaSens".to_string(),
@@ -279,6 +282,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaSens Weighted".to_string(),
@@ -290,6 +294,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaSb".to_string(),
@@ -301,6 +306,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaKb Low".to_string(),
@@ -312,6 +318,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaKb Medium".to_string(),
@@ -323,6 +330,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaKb High".to_string(),
@@ -334,6 +342,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaCharge Low".to_string(),
@@ -345,6 +354,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaCharge Medium".to_string(),
@@ -356,6 +366,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaCharge High".to_string(),
@@ -367,6 +378,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity DeltaCharge MAX".to_string(),
@@ -378,6 +390,7 @@ pub(crate) fn com_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
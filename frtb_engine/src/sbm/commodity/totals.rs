@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use ultibi::polars::prelude::{col, Expr, PolarsResult};
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::DependantMeasure;
@@ -29,6 +30,7 @@ fn com_total_max(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn com_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM", "Commodity"], Some("MAR21.6"), description);
     vec![
         Measure::Dependant(DependantMeasure {
             name: "Commodity TotalCharge Low".to_string(),
@@ -45,6 +47,7 @@ pub(crate) fn com_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "Commodity TotalCharge Medium".to_string(),
@@ -64,6 +67,7 @@ pub(crate) fn com_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "Commodity TotalCharge High".to_string(),
@@ -83,6 +87,7 @@ pub(crate) fn com_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "Commodity TotalCharge MAX".to_string(),
@@ -102,6 +107,7 @@ pub(crate) fn com_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use crate::{prelude::*, sbm::equity::vega::equity_vega_charge};
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{BaseMeasure, CPM};
@@ -91,6 +92,8 @@ fn com_vega_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn com_vega_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "Commodity", "Vega"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "Commodity VegaSens".to_string(),
@@ -102,6 +105,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaSens Weighted".to_string(),
@@ -113,6 +117,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaSb".to_string(),
@@ -124,6 +129,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaKb Low".to_string(),
@@ -135,6 +141,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaCharge Low".to_string(),
@@ -146,6 +153,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaKb Medium".to_string(),
@@ -157,6 +165,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaCharge Medium".to_string(),
@@ -168,6 +177,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaKb High".to_string(),
@@ -179,6 +189,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaCharge High".to_string(),
@@ -190,6 +201,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "Commodity VegaCharge MAX".to_string(),
@@ -201,6 +213,7 @@ pub(crate) fn com_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Commodity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
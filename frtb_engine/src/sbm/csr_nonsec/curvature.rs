@@ -1,5 +1,6 @@
 #![allow(clippy::type_complexity)]
 
+use crate::metadata::{amount, hidden};
 use crate::prelude::*;
 use ndarray::Array2;
 use ultibi::{prelude::CPM, BaseMeasure, IntoLazy};
@@ -248,6 +249,13 @@ fn csrnonsec_curv_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR nonSec", "Curvature"],
+            Some("MAR21.5"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CurvatureDelta".to_string(),
@@ -259,6 +267,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CurvatureDeltaWeighted".to_string(),
@@ -270,6 +279,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec PnLup".to_string(),
@@ -281,6 +291,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec PnLdown".to_string(),
@@ -292,6 +303,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CVRup".to_string(),
@@ -303,6 +315,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the upward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CVRdown".to_string(),
@@ -314,6 +327,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the downward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature KbPlus Medium".to_string(),
@@ -325,6 +339,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature KbMinus Medium".to_string(),
@@ -336,6 +353,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature Kb Medium".to_string(),
@@ -347,6 +367,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature Sb Medium".to_string(),
@@ -358,6 +379,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under medium correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CurvatureCharge Medium".to_string(),
@@ -369,6 +393,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature KbPlus Low".to_string(),
@@ -380,6 +405,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature KbMinus Low".to_string(),
@@ -391,6 +419,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature Kb Low".to_string(),
@@ -402,6 +433,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature Sb Low".to_string(),
@@ -413,6 +445,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under low correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CurvatureCharge Low".to_string(),
@@ -424,6 +459,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature KbPlus High".to_string(),
@@ -435,6 +471,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature KbMinus High".to_string(),
@@ -446,6 +485,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature Kb High".to_string(),
@@ -457,6 +499,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec Curvature Sb High".to_string(),
@@ -468,6 +511,9 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under high correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CurvatureCharge High".to_string(),
@@ -479,6 +525,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec CurvatureCharge MAX".to_string(),
@@ -490,6 +537,7 @@ pub(crate) fn csrnonsec_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,5 +1,6 @@
 //! CSR non-Sec Delta Calculations
 use crate::helpers::*;
+use crate::metadata::amount;
 use crate::sbm::common::*;
 use ndarray::Array2;
 use ultibi::polars_plan::dsl::max_horizontal;
@@ -388,6 +389,13 @@ fn csrnonsec_delta_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR nonSec", "Delta"],
+            Some("MAR21.4"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaSens".to_string(),
@@ -399,6 +407,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaSens Weighted".to_string(),
@@ -410,6 +419,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaSb".to_string(),
@@ -421,6 +431,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaKb Low".to_string(),
@@ -432,6 +443,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaKb Medium".to_string(),
@@ -443,6 +455,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaKb High".to_string(),
@@ -454,6 +467,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaCharge Low".to_string(),
@@ -465,6 +479,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaCharge Medium".to_string(),
@@ -476,6 +491,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaCharge High".to_string(),
@@ -487,6 +503,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec DeltaCharge MAX".to_string(),
@@ -498,6 +515,7 @@ pub(crate) fn csrnonsec_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
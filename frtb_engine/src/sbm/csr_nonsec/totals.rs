@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use ultibi::polars::prelude::{col, Expr, PolarsResult};
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::DependantMeasure;
@@ -30,6 +31,7 @@ fn csrnonsec_total_max(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn csrnonsec_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM", "CSR nonSec"], Some("MAR21.6"), description);
     vec![
         Measure::Dependant(DependantMeasure {
             name: "CSR nonSec TotalCharge Low".to_string(),
@@ -49,6 +51,7 @@ pub(crate) fn csrnonsec_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "CSR nonSec TotalCharge Medium".to_string(),
@@ -68,6 +71,7 @@ pub(crate) fn csrnonsec_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "CSR nonSec TotalCharge High".to_string(),
@@ -87,6 +91,7 @@ pub(crate) fn csrnonsec_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "CSR nonSec TotalCharge MAX".to_string(),
@@ -106,6 +111,7 @@ pub(crate) fn csrnonsec_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use crate::prelude::*;
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{
@@ -221,6 +222,8 @@ fn csrnonsec_vega_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "CSR nonSec", "Vega"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaSens".to_string(),
@@ -232,6 +235,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaSens Weighted".to_string(),
@@ -243,6 +247,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaSb".to_string(),
@@ -254,6 +259,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaCharge Low".to_string(),
@@ -265,6 +271,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaKb Low".to_string(),
@@ -276,6 +283,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaCharge Medium".to_string(),
@@ -287,6 +295,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaKb Medium".to_string(),
@@ -298,6 +307,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaCharge High".to_string(),
@@ -309,6 +319,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaKb High".to_string(),
@@ -320,6 +331,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR nonSec VegaCharge MAX".to_string(),
@@ -331,6 +343,7 @@ pub(crate) fn csrnonsec_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_nonSec"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
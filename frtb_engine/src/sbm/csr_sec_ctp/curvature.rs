@@ -1,3 +1,4 @@
+use crate::metadata::{amount, hidden};
 use crate::{prelude::*, sbm::csr_nonsec::curvature::csrnonsec_curvature_charge};
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{prelude::CPM, BaseMeasure};
@@ -140,6 +141,13 @@ fn csrsecctp_curv_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR Sec CTP", "Curvature"],
+            Some("MAR21.5"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CurvatureDelta".to_string(),
@@ -151,6 +159,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CurvatureDelta_Weighted".to_string(),
@@ -162,6 +171,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP PnLup".to_string(),
@@ -173,6 +183,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP PnLdown".to_string(),
@@ -184,6 +195,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CVRup".to_string(),
@@ -195,6 +207,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the upward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CVRdown".to_string(),
@@ -206,6 +219,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the downward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature KbPlus Medium".to_string(),
@@ -217,6 +231,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature KbMinus Medium".to_string(),
@@ -228,6 +245,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature Kb Medium".to_string(),
@@ -239,6 +259,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature Sb Medium".to_string(),
@@ -250,6 +271,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under medium correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CurvatureCharge Medium".to_string(),
@@ -261,6 +285,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature KbPlus Low".to_string(),
@@ -272,6 +297,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature KbMinus Low".to_string(),
@@ -283,6 +311,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature Kb Low".to_string(),
@@ -294,6 +325,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature Sb Low".to_string(),
@@ -305,6 +337,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under low correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CurvatureCharge Low".to_string(),
@@ -316,6 +351,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature KbPlus High".to_string(),
@@ -327,6 +363,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature KbMinus High".to_string(),
@@ -338,6 +377,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature Kb High".to_string(),
@@ -349,6 +391,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP Curvature Sb High".to_string(),
@@ -360,6 +403,9 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under high correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CurvatureCharge High".to_string(),
@@ -371,6 +417,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP CurvatureCharge MAX".to_string(),
@@ -382,6 +429,7 @@ pub(crate) fn csrsecctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
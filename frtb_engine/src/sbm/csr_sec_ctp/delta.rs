@@ -1,6 +1,7 @@
 //! CSR Sec CTP Delta Calculations
 
 use crate::helpers::*;
+use crate::metadata::amount;
 use sbm::csr_nonsec::delta::csr_nonsec_delta_charge;
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{BaseMeasure, CPM};
@@ -231,6 +232,13 @@ fn csrsecctp_delta_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR Sec CTP", "Delta"],
+            Some("MAR21.4"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaSens".to_string(),
@@ -242,6 +250,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaSens Weighted".to_string(),
@@ -253,6 +262,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaSb".to_string(),
@@ -264,6 +274,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaKb Low".to_string(),
@@ -275,6 +286,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaKb Medium".to_string(),
@@ -286,6 +298,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaKb High".to_string(),
@@ -297,6 +310,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaCharge Low".to_string(),
@@ -308,6 +322,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaCharge Medium".to_string(),
@@ -319,6 +334,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaCharge High".to_string(),
@@ -330,6 +346,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP DeltaCharge MAX".to_string(),
@@ -341,6 +358,7 @@ pub(crate) fn csrsecctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use polars::prelude::*;
 use ultibi::DependantMeasure;
 use ultibi::Measure;
@@ -20,6 +21,8 @@ pub(crate) fn csrsecctp_total_high(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn csrsecctp_total_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "CSR Sec CTP"], Some("MAR21.6"), description);
     vec![
         Measure::Dependant(DependantMeasure {
             name: "CSR Sec CTP TotalCharge Low".to_string(),
@@ -39,6 +42,7 @@ pub(crate) fn csrsecctp_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "CSR Sec CTP TotalCharge Medium".to_string(),
@@ -58,6 +62,7 @@ pub(crate) fn csrsecctp_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "CSR Sec CTP TotalCharge High".to_string(),
@@ -77,6 +82,7 @@ pub(crate) fn csrsecctp_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use crate::{prelude::*, sbm::csr_nonsec::vega::csr_nonsec_vega_charge};
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{BaseMeasure, CPM};
@@ -121,6 +122,13 @@ fn csrsecctp_vega_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR Sec CTP", "Vega"],
+            Some("MAR21.4"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaSens".to_string(),
@@ -132,6 +140,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaSens Weighted".to_string(),
@@ -143,6 +152,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaSb".to_string(),
@@ -154,6 +164,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaCharge Low".to_string(),
@@ -165,6 +176,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaKb Low".to_string(),
@@ -176,6 +188,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaCharge Medium".to_string(),
@@ -187,6 +200,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaKb Medium".to_string(),
@@ -198,6 +212,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaCharge High".to_string(),
@@ -209,6 +224,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaKb High".to_string(),
@@ -220,6 +236,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec CTP VegaCharge MAX".to_string(),
@@ -231,6 +248,7 @@ pub(crate) fn csrsecctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_CTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::{amount, hidden};
 use crate::{prelude::*, sbm::csr_nonsec::curvature::csrnonsec_curvature_charge};
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{prelude::CPM, BaseMeasure};
@@ -121,6 +122,13 @@ fn csrsecnonctp_curv_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR Sec nonCTP", "Curvature"],
+            Some("MAR21.5"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CurvatureDelta".to_string(),
@@ -132,6 +140,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CurvatureDelta Weighted".to_string(),
@@ -143,6 +152,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP PnLup".to_string(),
@@ -154,6 +164,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP PnLdown".to_string(),
@@ -165,6 +176,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CVRup".to_string(),
@@ -176,6 +188,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the upward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CVRdown".to_string(),
@@ -187,6 +200,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the downward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature KbPlus Medium".to_string(),
@@ -198,6 +212,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature KbMinus Medium".to_string(),
@@ -209,6 +226,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature Kb Medium".to_string(),
@@ -220,6 +240,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature Sb Medium".to_string(),
@@ -231,6 +252,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under medium correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CurvatureCharge Medium".to_string(),
@@ -242,6 +266,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature KbPlus Low".to_string(),
@@ -253,6 +278,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature KbMinus Low".to_string(),
@@ -264,6 +292,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature Kb Low".to_string(),
@@ -275,6 +306,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature Sb Low".to_string(),
@@ -286,6 +318,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under low correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CurvatureCharge Low".to_string(),
@@ -297,6 +332,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature KbPlus High".to_string(),
@@ -308,6 +344,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature KbMinus High".to_string(),
@@ -319,6 +358,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature Kb High".to_string(),
@@ -330,6 +372,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP Curvature Sb High".to_string(),
@@ -341,6 +384,9 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under high correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CurvatureCharge High".to_string(),
@@ -352,6 +398,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP CurvatureCharge MAX".to_string(),
@@ -363,6 +410,7 @@ pub(crate) fn csrsecnonctp_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
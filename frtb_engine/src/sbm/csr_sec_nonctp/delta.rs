@@ -1,5 +1,6 @@
 //! CSR Sec non-CTP Delta Calculations
 
+use crate::metadata::amount;
 use crate::prelude::*;
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{
@@ -278,6 +279,13 @@ fn csrsecnonctp_delta_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR Sec nonCTP", "Delta"],
+            Some("MAR21.4"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaSens".to_string(),
@@ -289,6 +297,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaSens Weighted".to_string(),
@@ -300,6 +309,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaKb Low".to_string(),
@@ -311,6 +321,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaKb Medium".to_string(),
@@ -322,6 +333,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaKb High".to_string(),
@@ -333,6 +345,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaSb".to_string(),
@@ -344,6 +357,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaCharge Low".to_string(),
@@ -355,6 +369,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaCharge Medium".to_string(),
@@ -366,6 +381,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaCharge High".to_string(),
@@ -377,6 +393,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP DeltaCharge MAX".to_string(),
@@ -388,6 +405,7 @@ pub(crate) fn csrsecnonctp_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
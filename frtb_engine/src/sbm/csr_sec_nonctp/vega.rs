@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use crate::{prelude::*, sbm::equity::vega::equity_vega_charge};
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{BaseMeasure, CPM};
@@ -97,6 +98,13 @@ fn csrsecnonctp_vega_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
+    let metadata = |description: &str| {
+        amount(
+            &["SBM", "CSR Sec nonCTP", "Vega"],
+            Some("MAR21.4"),
+            description,
+        )
+    };
     vec![
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaSens".to_string(),
@@ -108,6 +116,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaSens Weighted".to_string(),
@@ -119,6 +128,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaSb".to_string(),
@@ -130,6 +140,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaCharge Low".to_string(),
@@ -141,6 +152,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaKb Low".to_string(),
@@ -152,6 +164,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaCharge Medium".to_string(),
@@ -163,6 +176,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaKb Medium".to_string(),
@@ -174,6 +188,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaCharge High".to_string(),
@@ -185,6 +200,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaKb High".to_string(),
@@ -196,6 +212,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "CSR Sec nonCTP VegaCharge MAX".to_string(),
@@ -207,6 +224,7 @@ pub(crate) fn csrsecnonctp_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("CSR_Sec_nonCTP"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,5 +1,6 @@
 #![allow(clippy::type_complexity)]
 
+use crate::metadata::{amount, hidden};
 use crate::prelude::*;
 use ndarray::Array2;
 use ultibi::polars::prelude::{apply_multiple, df, DataType, GetOutput};
@@ -202,6 +203,8 @@ fn eq_curv_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn eq_curv_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "EQ", "Curvature"], Some("MAR21.5"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "EQ CurvatureDelta".to_string(),
@@ -213,6 +216,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ CurvatureDelta_Weighted".to_string(),
@@ -224,6 +228,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ PnLup".to_string(),
@@ -235,6 +240,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ PnLdown".to_string(),
@@ -246,6 +252,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ CVRup".to_string(),
@@ -257,6 +264,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the upward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ CVRdown".to_string(),
@@ -268,6 +276,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the downward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature KbPlus Medium".to_string(),
@@ -279,6 +288,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature KbMinus Medium".to_string(),
@@ -290,6 +302,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under medium correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature Kb Medium".to_string(),
@@ -301,6 +316,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature Sb Medium".to_string(),
@@ -312,6 +328,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under medium correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ CurvatureCharge Medium".to_string(),
@@ -323,6 +342,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature KbPlus Low".to_string(),
@@ -334,6 +354,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature KbMinus Low".to_string(),
@@ -345,6 +368,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under low correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature Kb Low".to_string(),
@@ -356,6 +382,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature Sb Low".to_string(),
@@ -367,6 +394,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under low correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ CurvatureCharge Low".to_string(),
@@ -378,6 +408,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature KbPlus High".to_string(),
@@ -389,6 +420,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the upward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature KbMinus High".to_string(),
@@ -400,6 +434,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata(
+                "Kb with the downward curvature shock under high correlation scenario",
+            )),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature Kb High".to_string(),
@@ -411,6 +448,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ Curvature Sb High".to_string(),
@@ -422,6 +460,9 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata(
+                "Sum of weighted sensitivities of a bucket under high correlation scenario",
+            ),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ CurvatureCharge High".to_string(),
@@ -433,6 +474,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ CurvatureCharge MAX".to_string(),
@@ -444,6 +486,7 @@ pub(crate) fn eq_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
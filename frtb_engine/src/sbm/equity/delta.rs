@@ -9,6 +9,7 @@
 //! Hence, it's sufficient to build two matrixes:
 //! 1 based on rft and 2 based on rf
 
+use crate::metadata::amount;
 use crate::prelude::*;
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{
@@ -195,6 +196,8 @@ fn eq_delta_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn eq_delta_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "EQ", "Delta"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "EQ DeltaSens".to_string(),
@@ -206,6 +209,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaSens Weighted".to_string(),
@@ -217,6 +221,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaSb".to_string(),
@@ -228,6 +233,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaKb Low".to_string(),
@@ -239,6 +245,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaKb Medium".to_string(),
@@ -250,6 +257,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaKb High".to_string(),
@@ -261,6 +269,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaCharge Low".to_string(),
@@ -272,6 +281,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaCharge Medium".to_string(),
@@ -283,6 +293,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaCharge High".to_string(),
@@ -294,6 +305,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ DeltaCharge MAX".to_string(),
@@ -305,6 +317,7 @@ pub(crate) fn eq_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use polars::prelude::*;
 use ultibi::DependantMeasure;
 use ultibi::Measure;
@@ -16,6 +17,7 @@ pub(crate) fn eq_total_high(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn eq_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM", "EQ"], Some("MAR21.6"), description);
     vec![
         Measure::Dependant(DependantMeasure {
             name: "EQ TotalCharge Low".to_string(),
@@ -26,6 +28,7 @@ pub(crate) fn eq_total_measures() -> Vec<Measure> {
                 ("EQ CurvatureCharge Low".to_string(), "scalar".to_string()),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "EQ TotalCharge Medium".to_string(),
@@ -39,6 +42,7 @@ pub(crate) fn eq_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "EQ TotalCharge High".to_string(),
@@ -49,6 +53,7 @@ pub(crate) fn eq_total_measures() -> Vec<Measure> {
                 ("EQ CurvatureCharge High".to_string(), "scalar".to_string()),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use crate::prelude::*;
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{
@@ -182,6 +183,7 @@ fn eq_vega_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn eq_vega_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM", "EQ", "Vega"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "EQ VegaSens".to_string(),
@@ -193,6 +195,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaSens Weighted".to_string(),
@@ -204,6 +207,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaSb".to_string(),
@@ -215,6 +219,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaKb Low".to_string(),
@@ -226,6 +231,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaCharge Low".to_string(),
@@ -237,6 +243,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaKb Medium".to_string(),
@@ -248,6 +255,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaCharge Medium".to_string(),
@@ -259,6 +267,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaKb High".to_string(),
@@ -270,6 +279,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaCharge High".to_string(),
@@ -281,6 +291,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "EQ VegaCharge MAX".to_string(),
@@ -292,6 +303,7 @@ pub(crate) fn eq_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("Equity"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::{amount, hidden};
 use crate::prelude::*;
 use ndarray::{Array1, Array2};
 use ultibi::polars_plan::dsl::max_horizontal;
@@ -236,6 +237,8 @@ fn fx_curv_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn fx_curv_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "FX", "Curvature"], Some("MAR21.5"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "FX CurvatureDelta".to_string(),
@@ -247,6 +250,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX CurvatureDelta Weighted".to_string(),
@@ -258,6 +262,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX PnLup".to_string(),
@@ -269,6 +274,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "FX PnLdown".to_string(),
@@ -280,6 +286,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "FX CVRup".to_string(),
@@ -291,6 +298,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the upward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "FX CVRdown".to_string(),
@@ -302,6 +310,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the downward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "FX Curvature KbPlus".to_string(),
@@ -313,6 +322,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Kb with the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "FX Curvature KbMinus".to_string(),
@@ -324,6 +334,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Kb with the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "FX Curvature Kb".to_string(),
@@ -335,6 +346,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX Curvature Sb".to_string(),
@@ -346,6 +358,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX CurvatureCharge Low".to_string(),
@@ -357,6 +370,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX CurvatureCharge Medium".to_string(),
@@ -368,6 +382,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX CurvatureCharge High".to_string(),
@@ -379,6 +394,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX CurvatureCharge MAX".to_string(),
@@ -390,6 +406,7 @@ pub(crate) fn fx_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,6 +1,7 @@
 //! For FX RiskFactor is the original source of risk, could be offshore
 //! BucketBCBS/CRR2 to be
 
+use crate::metadata::amount;
 use ndarray::{Array, Array1};
 
 use crate::{
@@ -210,6 +211,8 @@ fn fx_delta_max(op: &CPM) -> PolarsResult<Expr> {
 }
 /// Exporting Measures
 pub(crate) fn fx_delta_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "FX", "Delta"], Some("MAR21.4"), description);
     //let reporting_ccy  = FRTB_CALC_PARAMS_MAP.get("reporting_ccy").unwrap(); // it must be present
     //let jurisdiction  = FRTB_CALC_PARAMS_MAP.get("jurisdiction").unwrap(); // it must be present
     //let fx_delta_gamma_low  = FRTB_CALC_PARAMS_MAP.get("fx_delta_gamma_low").unwrap(); // it must be present
@@ -228,6 +231,8 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
 
             calc_params: vec![],
+
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX DeltaSens Weighted".to_string(),
@@ -240,6 +245,7 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
             //calc_params: &[*reporting_ccy, *jurisdiction]
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX DeltaSb".to_string(),
@@ -252,6 +258,7 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
             //calc_params: &[*reporting_ccy, *jurisdiction, *fx_delta_gamma_low, *fx_delta_gamma_medium, *fx_delta_gamma_high]
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX DeltaKb".to_string(),
@@ -264,6 +271,7 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
             //calc_params: &[*reporting_ccy, *jurisdiction, *fx_delta_gamma_low, *fx_delta_gamma_medium, *fx_delta_gamma_high]
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX DeltaCharge Low".to_string(),
@@ -276,6 +284,7 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
             //calc_params: &[*reporting_ccy, *jurisdiction, *fx_delta_gamma_low, *fx_delta_gamma_medium, *fx_delta_gamma_high]
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX DeltaCharge Medium".to_string(),
@@ -288,6 +297,7 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
             //calc_params: &[*reporting_ccy, *jurisdiction, *fx_delta_gamma_low, *fx_delta_gamma_medium, *fx_delta_gamma_high]
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX DeltaCharge High".to_string(),
@@ -300,6 +310,7 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
             //calc_params: &[*reporting_ccy, *jurisdiction, *fx_delta_gamma_low, *fx_delta_gamma_medium, *fx_delta_gamma_high]
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX DeltaCharge MAX".to_string(),
@@ -312,6 +323,7 @@ pub(crate) fn fx_delta_measures() -> Vec<Measure> {
             ),
             //calc_params: &[*reporting_ccy, *jurisdiction, *fx_delta_gamma_low, *fx_delta_gamma_medium, *fx_delta_gamma_high]
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
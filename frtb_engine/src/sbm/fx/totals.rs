@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use polars::prelude::*;
 use ultibi::DependantMeasure;
 use ultibi::Measure;
@@ -16,6 +17,7 @@ pub(crate) fn fx_total_high(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn fx_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM", "FX"], Some("MAR21.6"), description);
     vec![
         Measure::Dependant(DependantMeasure {
             name: "FX TotalCharge Low".to_string(),
@@ -26,6 +28,7 @@ pub(crate) fn fx_total_measures() -> Vec<Measure> {
                 ("FX CurvatureCharge Low".to_string(), "scalar".to_string()),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "FX TotalCharge Medium".to_string(),
@@ -39,6 +42,7 @@ pub(crate) fn fx_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "FX TotalCharge High".to_string(),
@@ -49,6 +53,7 @@ pub(crate) fn fx_total_measures() -> Vec<Measure> {
                 ("FX CurvatureCharge High".to_string(), "scalar".to_string()),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
     ]
 }
@@ -1,4 +1,5 @@
 use crate::helpers::{get_optional_parameter, get_optional_parameter_array, ReturnMetric};
+use crate::metadata::amount;
 use crate::prelude::*;
 use crate::sbm::common::{across_bucket_agg, rc_rcat_sens, total_vega_curv_sens, SBMChargeType};
 use ndarray::{Array1, Array2, Axis};
@@ -176,6 +177,7 @@ fn fx_vega_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn fx_vega_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM", "FX", "Vega"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "FX VegaSens".to_string(),
@@ -187,6 +189,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaSens Weighted".to_string(),
@@ -198,6 +201,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaSb".to_string(),
@@ -209,6 +213,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaKb Low".to_string(),
@@ -220,6 +225,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaKb Medium".to_string(),
@@ -231,6 +237,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaKb High".to_string(),
@@ -242,6 +249,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaCharge Low".to_string(),
@@ -253,6 +261,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaCharge Medium".to_string(),
@@ -264,6 +273,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaCharge High".to_string(),
@@ -275,6 +285,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "FX VegaCharge MAX".to_string(),
@@ -286,6 +297,7 @@ pub(crate) fn fx_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("FX"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::metadata::{amount, hidden};
 use crate::{
     prelude::*,
     sbm::common::{across_bucket_agg, rc_rcat_sens, SBMChargeType},
@@ -232,6 +233,8 @@ fn girr_curv_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn girr_curv_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "GIRR", "Curvature"], Some("MAR21.5"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "GIRR CurvatureDelta".to_string(),
@@ -243,6 +246,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR PnLup".to_string(),
@@ -254,6 +258,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR PnLdown".to_string(),
@@ -265,6 +270,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("PnL of the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR CurvatureDelta Weighted".to_string(),
@@ -276,6 +282,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted delta used to compute curvature"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR CVRup".to_string(),
@@ -287,6 +294,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the upward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR CVRdown".to_string(),
@@ -298,6 +306,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Curvature risk of the downward shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR Curvature KbPlus".to_string(),
@@ -309,6 +318,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Kb with the upward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR Curvature KbMinus".to_string(),
@@ -320,6 +330,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: hidden(metadata("Kb with the downward curvature shock")),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR Curvature Kb".to_string(),
@@ -331,6 +342,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR Curvature Sb".to_string(),
@@ -342,6 +354,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR CurvatureCharge Low".to_string(),
@@ -353,6 +366,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR CurvatureCharge Medium".to_string(),
@@ -364,6 +378,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR CurvatureCharge High".to_string(),
@@ -375,6 +390,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR CurvatureCharge MAX".to_string(),
@@ -386,6 +402,7 @@ pub(crate) fn girr_curv_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
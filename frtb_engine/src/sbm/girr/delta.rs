@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use crate::prelude::*;
 use ultibi::polars_plan::dsl::max_horizontal;
 use ultibi::{
@@ -452,6 +453,8 @@ fn girr_delta_max(op: &CPM) -> PolarsResult<Expr> {
 }
 /// Exporting Measures
 pub(crate) fn girr_delta_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "GIRR", "Delta"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaSens".to_string(),
@@ -463,6 +466,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaSens Weighted".to_string(),
@@ -474,6 +478,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaSb".to_string(),
@@ -485,6 +490,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaCharge Low".to_string(),
@@ -496,6 +502,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaKb Low".to_string(),
@@ -507,6 +514,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaCharge Medium".to_string(),
@@ -518,6 +526,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaKb Medium".to_string(),
@@ -529,6 +538,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaCharge High".to_string(),
@@ -540,6 +550,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaKb High".to_string(),
@@ -551,6 +562,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR DeltaCharge MAX".to_string(),
@@ -562,6 +574,7 @@ pub(crate) fn girr_delta_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
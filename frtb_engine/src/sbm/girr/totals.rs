@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use polars::prelude::*;
 use ultibi::DependantMeasure;
 use ultibi::Measure;
@@ -20,6 +21,7 @@ pub(crate) fn girr_total_high(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn girr_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM", "GIRR"], Some("MAR21.6"), description);
     vec![
         Measure::Dependant(DependantMeasure {
             name: "GIRR TotalCharge Low".to_string(),
@@ -30,6 +32,7 @@ pub(crate) fn girr_total_measures() -> Vec<Measure> {
                 ("GIRR CurvatureCharge Low".to_string(), "scalar".to_string()),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "GIRR TotalCharge Medium".to_string(),
@@ -43,6 +46,7 @@ pub(crate) fn girr_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "GIRR TotalCharge High".to_string(),
@@ -56,6 +60,7 @@ pub(crate) fn girr_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
     ]
 }
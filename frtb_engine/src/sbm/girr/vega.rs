@@ -1,4 +1,5 @@
 use crate::helpers::{get_optional_parameter, get_optional_parameter_array, ReturnMetric};
+use crate::metadata::amount;
 use crate::prelude::*;
 use crate::sbm::common::{
     across_bucket_agg, option_maturity_rho, rc_rcat_sens, rc_tenor_weighted_sens,
@@ -329,6 +330,8 @@ fn girr_vega_max(op: &CPM) -> PolarsResult<Expr> {
 
 /// Exporting Measures
 pub(crate) fn girr_vega_measures() -> Vec<Measure> {
+    let metadata =
+        |description: &str| amount(&["SBM", "GIRR", "Vega"], Some("MAR21.4"), description);
     vec![
         Measure::Base(BaseMeasure {
             name: "GIRR VegaSens".to_string(),
@@ -340,6 +343,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaSens Weighted".to_string(),
@@ -351,6 +355,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Weighted sensitivities"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaSb".to_string(),
@@ -362,6 +367,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Sum of weighted sensitivities of a bucket"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaCharge Low".to_string(),
@@ -373,6 +379,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaKb Low".to_string(),
@@ -384,6 +391,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under low correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaCharge Medium".to_string(),
@@ -395,6 +403,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaKb Medium".to_string(),
@@ -406,6 +415,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under medium correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaCharge High".to_string(),
@@ -417,6 +427,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaKb High".to_string(),
@@ -428,6 +439,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital requirement of a bucket under high correlation scenario"),
         }),
         Measure::Base(BaseMeasure {
             name: "GIRR VegaCharge MAX".to_string(),
@@ -439,6 +451,7 @@ pub(crate) fn girr_vega_measures() -> Vec<Measure> {
                     .and(col("RiskClass").eq(lit("GIRR"))),
             ),
             calc_params: vec![],
+            metadata: metadata("Capital charge under the worst correlation scenario"),
         }),
     ]
 }
@@ -1,5 +1,6 @@
 //! Totals across different Risk Classes
 
+use crate::metadata::amount;
 use ultibi::polars::lazy::dsl::{col, max_horizontal, Expr};
 use ultibi::{DependantMeasure, Measure, PolarsResult, CPM};
 
@@ -42,6 +43,7 @@ pub(crate) fn sbm_charge_dep(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn sbm_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&["SBM"], Some("MAR21.6"), description);
     vec![
         // Testing dependency
         Measure::Dependant(DependantMeasure {
@@ -69,6 +71,7 @@ pub(crate) fn sbm_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under medium correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "SBM Charge Low".to_string(),
@@ -95,6 +98,7 @@ pub(crate) fn sbm_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under low correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "SBM Charge High".to_string(),
@@ -121,6 +125,7 @@ pub(crate) fn sbm_total_measures() -> Vec<Measure> {
                 ),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge under high correlation scenario"),
         }),
         Measure::Dependant(DependantMeasure {
             name: "SBM Charge".to_string(),
@@ -131,6 +136,7 @@ pub(crate) fn sbm_total_measures() -> Vec<Measure> {
                 ("SBM Charge High".to_string(), "scalar".to_string()),
             ],
             calc_params: vec![],
+            metadata: metadata("Capital charge"),
         }),
     ]
 }
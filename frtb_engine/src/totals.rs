@@ -1,3 +1,4 @@
+use crate::metadata::amount;
 use ultibi::polars::lazy::dsl::col;
 use ultibi::polars::prelude::Expr;
 use ultibi::{DependantMeasure, Measure, PolarsResult, CPM};
@@ -8,6 +9,7 @@ fn sa_charge(_: &CPM) -> PolarsResult<Expr> {
 }
 
 pub(crate) fn sa_total_measures() -> Vec<Measure> {
+    let metadata = |description: &str| amount(&[], Some("MAR20"), description);
     vec![Measure::Dependant(DependantMeasure {
         name: "SA Charge".to_string(),
         calculator: std::sync::Arc::new(sa_charge),
@@ -17,5 +19,6 @@ pub(crate) fn sa_total_measures() -> Vec<Measure> {
             ("RRAO Charge".to_string(), "scalar".to_string()),
         ],
         calc_params: vec![],
+        metadata: metadata("Capital charge of the standardised approach"),
     })]
 }
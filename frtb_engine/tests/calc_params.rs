@@ -0,0 +1,44 @@
+use ultibi::{errors::UltimaErr, ComputeRequest, DataSet};
+mod common;
+use common::LAZY_DASET;
+
+fn compute(calc_params: &str) -> Result<(), UltimaErr> {
+    let request = format!(
+        r#"{{"measures": [["DRC nonSec CapitalCharge", "scalar"]],
+        "groupby": ["Desk"],
+        "calc_params": {calc_params}}}"#
+    );
+    let req = serde_json::from_str::<ComputeRequest>(&request).expect("Could not parse request");
+    LAZY_DASET.as_ref().compute(req).map(|_| ())
+}
+
+#[test]
+fn unknown_calc_param() {
+    let err = compute(r#"{"jurisdicton": "CRR2"}"#).expect_err("Typo should be rejected");
+
+    assert_eq!(err.code(), "unknown_calc_param");
+    assert_eq!(err.field(), Some("jurisdicton"));
+}
+
+#[test]
+fn malformed_calc_params() {
+    for (params, param) in [
+        (r#"{"drc_offset": "yes"}"#, "drc_offset"),
+        (r#"{"jurisdiction": "EBA"}"#, "jurisdiction"),
+        (r#"{"reporting_ccy": "USDOLLAR"}"#, "reporting_ccy"),
+        (
+            r#"{"com_vega_rho_bucket_base": "[0.5, 0.5]"}"#,
+            "com_vega_rho_bucket_base",
+        ),
+    ] {
+        let err = compute(params).expect_err(params);
+        assert_eq!(err.code(), "invalid_calc_param");
+        assert_eq!(err.field(), Some(param));
+    }
+}
+
+#[test]
+fn valid_calc_params() {
+    compute(r#"{"jurisdiction": "BCBS", "drc_offset": "false", "exotic_rrao_weight": "0.01"}"#)
+        .expect("Valid params should be accepted");
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::LAZY_DASET;
+use ultibi::{DataSet, DrillThroughRequest, ExecutionContext};
+
+#[test]
+fn eq_delta_charge_of_bucket() {
+    let req = serde_json::from_str::<DrillThroughRequest>(
+        r#"
+    {"request": {"measures": [["EQ DeltaCharge Medium", "scalar"]],
+        "groupby": ["BucketBCBS"],
+        "filters": [[{"op": "Eq", "field": "RiskClass", "value": "Equity"}]]},
+    "cell": {"BucketBCBS": "1"},
+    "measure": "EQ DeltaCharge Medium"
+    }"#,
+    )
+    .expect("Could not parse request");
+
+    let page = LAZY_DASET
+        .as_ref()
+        .drill_through(req, &ExecutionContext::default())
+        .expect("Drill through failed");
+
+    // Vega of the same bucket is not behind the Delta charge
+    assert_eq!(page.total, 4);
+    let risk_category = page.rows.column("RiskCategory").unwrap().str().unwrap();
+    assert!(risk_category.into_iter().all(|rc| rc == Some("Delta")));
+
+    for c in ["SensWeights", "BucketBCBS", "ScaleFactor"] {
+        assert!(page.rows.column(c).is_ok(), "{c} is missing");
+    }
+}
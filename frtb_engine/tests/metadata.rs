@@ -0,0 +1,47 @@
+use ultibi::DataSet;
+mod common;
+use common::LAZY_DASET;
+
+#[test]
+fn measures_are_categorised() {
+    let measures = LAZY_DASET.get_measures();
+
+    let kb = measures["GIRR DeltaKb Low"].metadata();
+    assert_eq!(kb.category, ["SBM", "GIRR", "Delta"]);
+    assert_eq!(kb.reference.as_deref(), Some("MAR21.4"));
+    assert!(!kb.hidden);
+
+    let pnl = measures["FX PnLup"].metadata();
+    assert_eq!(pnl.category, ["SBM", "FX", "Curvature"]);
+    assert!(pnl.hidden);
+
+    let drc = measures["DRC Sec nonCTP CapitalCharge"].metadata();
+    assert_eq!(drc.category, ["DRC", "Sec nonCTP"]);
+    assert_eq!(drc.unit.as_deref(), Some("reporting_ccy"));
+
+    let hbr = measures["DRC nonSec HBR"].metadata();
+    assert_eq!(hbr.unit, None);
+    assert_eq!(hbr.format.as_deref(), Some("0.00%"));
+}
+
+#[test]
+fn every_measure_is_described() {
+    for measure in frtb_engine::measures::frtb_measure_vec() {
+        assert!(
+            measure.metadata().description.is_some(),
+            "{} has no description",
+            measure.name()
+        );
+    }
+}
+
+#[test]
+fn metadata_is_serialised() {
+    let ds: &dyn DataSet = LAZY_DASET.as_ref();
+    let info = serde_json::to_value(ds).expect("DataSet is serialisable");
+
+    assert_eq!(
+        info["measures_metadata"]["SBM Charge"]["category"],
+        serde_json::json!(["SBM"])
+    );
+}
@@ -26,3 +26,86 @@ fn sa_charge() {
 
     assert!(dbg!(res.column("SA Charge")).is_ok());
 }
+
+#[test]
+fn sa_charge_grand_total_is_recomputed() {
+    use ultibi::polars::prelude::{col, lit, IntoLazy};
+    use ultibi::{ComputeRequest, GROUPING_ID};
+
+    let grand_total = |groupby: &str| -> f64 {
+        let request = format!(
+            r#"
+        {{"measures": [
+            ["SA Charge", "scalar"]
+                ],
+        "groupby": ["{groupby}"],
+        "grouping_sets": {{"type": "grand_total"}},
+        "calc_params": {{"jurisdiction": "BCBS"}}
+        }}"#
+        );
+        let data_req =
+            serde_json::from_str::<ComputeRequest>(&request).expect("Could not parse request");
+        let res = common::LAZY_DASET.as_ref().compute(data_req).unwrap();
+        res.lazy()
+            .filter(col(GROUPING_ID).eq(lit(1u32)))
+            .collect()
+            .unwrap()
+            .column("SA Charge")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .get(0)
+            .unwrap()
+    };
+
+    // Charge of the whole portfolio doesn't depend on how it is broken down
+    let by_desk = grand_total("Desk");
+    let by_cob = grand_total("COB");
+    assert!((by_desk - by_cob).abs() < 1e-6);
+}
+
+#[test]
+fn sa_charge_share_of_parent_is_recomputed() {
+    use ultibi::polars::prelude::{col, IntoLazy, JoinArgs, JoinType};
+    use ultibi::ComputeRequest;
+
+    let compute = |request: &str| {
+        let data_req =
+            serde_json::from_str::<ComputeRequest>(request).expect("Could not parse request");
+        common::LAZY_DASET.as_ref().compute(data_req).unwrap()
+    };
+    let by_desk = compute(
+        r#"
+    {"measures": [["SA Charge", "scalar"], ["SA Charge Share of LegalEntity", "scalar"]],
+    "groupby": ["LegalEntity", "Desk"],
+    "calc_params": {"jurisdiction": "BCBS"}
+    }"#,
+    );
+    let by_legal_entity = compute(
+        r#"
+    {"measures": [["SA Charge", "scalar"]],
+    "groupby": ["LegalEntity"],
+    "calc_params": {"jurisdiction": "BCBS"}
+    }"#,
+    )
+    .lazy()
+    .select([col("LegalEntity"), col("SA Charge").alias("Parent")]);
+
+    // Charges don't add up, so the parent is the charge of the LegalEntity
+    let mismatches = by_desk
+        .lazy()
+        .join(
+            by_legal_entity,
+            [col("LegalEntity")],
+            [col("LegalEntity")],
+            JoinArgs::new(JoinType::Left),
+        )
+        .filter(
+            (col("SA Charge Share of LegalEntity") * col("Parent") - col("SA Charge"))
+                .abs()
+                .gt(1e-6),
+        )
+        .collect()
+        .unwrap();
+    assert_eq!(mismatches.height(), 0);
+}
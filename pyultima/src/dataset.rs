@@ -5,7 +5,7 @@ use crate::filter::FilterWrapper;
 use crate::measure::MeasureWrapper;
 use crate::requests;
 use frtb_engine::FRTBDataSet;
-use pyo3::exceptions::PyFileNotFoundError;
+use pyo3::exceptions::{PyFileNotFoundError, PyUserWarning};
 use pyo3::{prelude::*, types::PyType, PyTypeInfo};
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -13,11 +13,13 @@ use std::sync::Arc;
 use std::sync::RwLock;
 use ultibi::datasource::DataSource;
 use ultibi::filters::FilterE;
+use ultibi::hierarchy::resolve_descendants;
 use ultibi::new::NewSourcedDataSet;
 use ultibi::polars::prelude::Series;
 use ultibi::VisualDataSet;
 use ultibi::{
-    self, derive_basic_measures_vec, numeric_columns, DataFrame, DataSet, DataSetBase, MeasuresMap,
+    self, derive_basic_measures_vec, numeric_columns, DataFrame, DataSet, DataSetBase,
+    ExecutionContext, MeasuresMap, MAX_GROUPS,
 };
 
 #[pyclass]
@@ -58,7 +60,8 @@ fn from_source<T: NewSourcedDataSet + 'static>(
     let mut mm: MeasuresMap = MeasuresMap::from_iter(mv);
     mm.extend(bespoke_measures);
 
-    let ds: T = T::new(source, mm, Default::default(), build_params);
+    let mut ds: T = T::new(source, mm, Default::default(), build_params);
+    ds.refresh_snapshots().map_err(PyUltimaErr::Ultima)?;
 
     Ok(DataSetWrapper {
         dataset: Arc::new(RwLock::new(ds)),
@@ -231,16 +234,23 @@ impl DataSetWrapper {
         py: Python,
         request: requests::ComputeRequestWrapper,
     ) -> PyResult<Vec<PyObject>> {
-        py.allow_threads(|| {
+        let ctx = ExecutionContext::new();
+        let res = py.allow_threads(|| {
             self.dataset
                 .read()
                 .expect("Poisonned RwLock")
-                .compute(request.ar)
+                .compute_with_context(request.ar, &ctx)
                 .map_err(PyUltimaErr::Ultima)?
                 .iter()
                 .map(rust_series_to_py_series)
                 .collect()
-        })
+        });
+        if res.is_ok() && ctx.is_truncated() {
+            let msg =
+                format!("Result is truncated to the first {MAX_GROUPS} rows of each grouping set");
+            PyErr::warn(py, py.get_type::<PyUserWarning>(), &msg, 0)?;
+        }
+        res
     }
 
     pub fn measures(&self) -> BTreeMap<String, Option<String>> {
@@ -252,6 +262,16 @@ impl DataSetWrapper {
             .map(|(x, m)| (x.to_string(), m.aggregation().clone()))
             .collect::<BTreeMap<String, Option<String>>>()
     }
+    /// JSON of {measureName: MeasureMetadata}
+    pub fn measures_metadata(&self) -> PyResult<String> {
+        let ds = self.dataset.read().expect("Poisonned RwLock");
+        let metadata = ds
+            .get_measures()
+            .iter()
+            .map(|(x, m)| (x, m.metadata()))
+            .collect::<BTreeMap<_, _>>();
+        Ok(serde_json::to_string(&metadata).map_err(PyUltimaErr::SerdeJson)?)
+    }
     pub fn frame(&self, fltrs: Option<Vec<Vec<FilterWrapper>>>) -> PyResult<Vec<PyObject>> {
         let fltrs = if let Some(f) = fltrs {
             f.into_iter()
@@ -266,10 +286,10 @@ impl DataSetWrapper {
             Default::default()
         };
 
-        self.dataset
-            .read()
-            .expect("Poisonned RwLock")
-            .get_lazyframe(&fltrs)
+        let ds = self.dataset.read().expect("Poisonned RwLock");
+        let mut fltrs = fltrs;
+        resolve_descendants(&mut fltrs, ds.hierarchies()).map_err(PyUltimaErr::Ultima)?;
+        ds.get_lazyframe(&fltrs)
             .map_err(PyUltimaErr::Ultima)?
             .collect()
             .map_err(PyUltimaErr::Polars)?
@@ -278,6 +298,63 @@ impl DataSetWrapper {
             .collect()
     }
 
+    /// JSON of [ultibi::DrillThroughRequest]. Returns the requested page of the rows
+    pub fn drill_through(&self, py: Python, request: &str) -> PyResult<Vec<PyObject>> {
+        let request = serde_json::from_str::<ultibi::DrillThroughRequest>(request)
+            .map_err(PyUltimaErr::SerdeJson)?;
+        py.allow_threads(|| {
+            self.dataset
+                .read()
+                .expect("Poisonned RwLock")
+                .drill_through(request, &Default::default())
+                .map_err(PyUltimaErr::Ultima)?
+                .rows
+                .iter()
+                .map(rust_series_to_py_series)
+                .collect()
+        })
+    }
+
+    /// Rows with the columns of the data before it's prepared. Returns the number of rows appended
+    pub fn append_frame(&self, py: Python, seriess: Vec<Py<PyAny>>) -> PyResult<usize> {
+        let df = DataFrame::new(
+            seriess
+                .into_iter()
+                .map(|x| py_series_to_rust_series(x.as_ref(py)))
+                .collect::<PyResult<Vec<Series>>>()?,
+        )
+        .map_err(PyUltimaErr::Polars)?;
+        py.allow_threads(|| {
+            Ok(self
+                .dataset
+                .write()
+                .expect("Poisonned RwLock")
+                .append_frame(df)
+                .map_err(PyUltimaErr::Ultima)?)
+        })
+    }
+
+    /// Returns the number of rows deleted
+    pub fn delete_where(&self, py: Python, fltrs: Vec<Vec<FilterWrapper>>) -> PyResult<usize> {
+        let fltrs = fltrs
+            .into_iter()
+            .map(|inner| {
+                inner
+                    .into_iter()
+                    .map(|fltr_wrap| fltr_wrap.inner)
+                    .collect::<Vec<FilterE>>()
+            })
+            .collect::<Vec<Vec<FilterE>>>();
+        py.allow_threads(|| {
+            Ok(self
+                .dataset
+                .write()
+                .expect("Poisonned RwLock")
+                .delete_where(&fltrs)
+                .map_err(PyUltimaErr::Ultima)?)
+        })
+    }
+
     pub fn fields(&self) -> PyResult<Vec<String>> {
         let schema = self
             .dataset
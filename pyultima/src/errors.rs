@@ -56,12 +56,33 @@ impl std::convert::From<PyUltimaErr> for PyErr {
             SerdeJson(err) => SerdeJsonError::new_err(format!(
                 "Couldn't (de)serialise input. Check format. {err}"
             )),
-            Ultima(err) => UltimaError::new_err(err.to_string()),
+            Ultima(err) => ultima_exception(err),
             Other(_str) => OtherError::new_err(_str.to_string()),
         }
     }
 }
 
+/// Specific exceptions subclass [UltimaError], so `except UltimaError` still catches them
+fn ultima_exception(err: &UltimaErr) -> PyErr {
+    use UltimaErr::*;
+    let msg = err.to_string();
+    match err {
+        Polars(PolarsError::ColumnNotFound(_)) => UnknownColumnError::new_err(msg),
+        UnknownMeasure { .. } => UnknownMeasureError::new_err(msg),
+        UnknownAggregation { .. } | UnsupportedAggregation { .. } => {
+            UnknownAggregationError::new_err(msg)
+        }
+        UnknownColumn { .. } => UnknownColumnError::new_err(msg),
+        UnknownCalcParam { .. } | InvalidCalcParam { .. } => InvalidCalcParamError::new_err(msg),
+        InvalidOverride { .. } => InvalidOverrideError::new_err(msg),
+        InvalidParameter { .. } | InvalidRequest(_) => InvalidRequestError::new_err(msg),
+        DataSource(_) => DataSourceError::new_err(msg),
+        Timeout(_) => ComputeTimeoutError::new_err(msg),
+        Cancelled(_) => CancelledError::new_err(msg),
+        Polars(_) | SerdeJson(_) | Other(_) => UltimaError::new_err(msg),
+    }
+}
+
 impl Debug for PyUltimaErr {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         use PyUltimaErr::*;
@@ -88,3 +109,12 @@ create_exception!(exceptions, SchemaFieldNotFound, PyException);
 create_exception!(exceptions, StructFieldNotFound, PyException);
 create_exception!(exceptions, UltimaError, PyException);
 create_exception!(exceptions, UltiPolarsError, PyException);
+create_exception!(exceptions, UnknownMeasureError, UltimaError);
+create_exception!(exceptions, UnknownAggregationError, UltimaError);
+create_exception!(exceptions, UnknownColumnError, UltimaError);
+create_exception!(exceptions, InvalidCalcParamError, UltimaError);
+create_exception!(exceptions, InvalidOverrideError, UltimaError);
+create_exception!(exceptions, InvalidRequestError, UltimaError);
+create_exception!(exceptions, DataSourceError, UltimaError);
+create_exception!(exceptions, ComputeTimeoutError, UltimaError);
+create_exception!(exceptions, CancelledError, UltimaError);
@@ -2,8 +2,10 @@
 extern crate ultibi as ultibi_rs;
 
 use errors::{
-    ArrowErrorException, ComputeError, DuplicateError, InvalidOperationError, NoDataError,
-    NotFoundError, OtherError, SchemaError, SerdeJsonError, ShapeError, UltimaError,
+    ArrowErrorException, CancelledError, ComputeError, ComputeTimeoutError, DataSourceError,
+    DuplicateError, InvalidCalcParamError, InvalidOperationError, InvalidOverrideError,
+    InvalidRequestError, NoDataError, NotFoundError, OtherError, SchemaError, SerdeJsonError,
+    ShapeError, UltimaError, UnknownAggregationError, UnknownColumnError, UnknownMeasureError,
 };
 use pyo3::{pyfunction, pymodule, types::PyModule, wrap_pyfunction, PyResult, Python};
 
@@ -42,6 +44,33 @@ fn ultibi_engine(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<db::DbInfo>()?;
 
     m.add("UltimaError", _py.get_type::<UltimaError>()).unwrap();
+    m.add("UnknownMeasureError", _py.get_type::<UnknownMeasureError>())
+        .unwrap();
+    m.add(
+        "UnknownAggregationError",
+        _py.get_type::<UnknownAggregationError>(),
+    )
+    .unwrap();
+    m.add("UnknownColumnError", _py.get_type::<UnknownColumnError>())
+        .unwrap();
+    m.add(
+        "InvalidCalcParamError",
+        _py.get_type::<InvalidCalcParamError>(),
+    )
+    .unwrap();
+    m.add(
+        "InvalidOverrideError",
+        _py.get_type::<InvalidOverrideError>(),
+    )
+    .unwrap();
+    m.add("InvalidRequestError", _py.get_type::<InvalidRequestError>())
+        .unwrap();
+    m.add("DataSourceError", _py.get_type::<DataSourceError>())
+        .unwrap();
+    m.add("ComputeTimeoutError", _py.get_type::<ComputeTimeoutError>())
+        .unwrap();
+    m.add("CancelledError", _py.get_type::<CancelledError>())
+        .unwrap();
 
     m.add("NotFoundError", _py.get_type::<NotFoundError>())
         .unwrap();
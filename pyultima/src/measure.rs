@@ -1,12 +1,14 @@
 use crate::calculator::CalculatorWrapper;
+use crate::errors::PyUltimaErr;
 use crate::filter::FilterWrapper;
 use pyo3::types::PyType;
-use pyo3::{pyclass, pymethods};
-use ultibi::filters::fltr_chain;
+use pyo3::{pyclass, pymethods, PyResult};
 use ultibi::filters::FilterE;
+use ultibi::filters::{check_resolved, fltr_chain};
 use ultibi::DependantMeasure;
 use ultibi::Measure;
-use ultibi::{BaseMeasure, CalcParameter};
+use ultibi::MeasureMetadata;
+use ultibi::{BaseMeasure, CalcParamType, CalcParameter};
 
 #[pyclass]
 #[derive(Clone)]
@@ -16,14 +18,27 @@ pub struct CalcParamWrapper {
 
 #[pymethods]
 impl CalcParamWrapper {
+    /// param_type is a JSON of [CalcParamType], eg {"type": "vector", "len": 3}
     #[new]
-    fn new(name: String, default: Option<String>, type_hint: Option<String>) -> Self {
+    fn new(
+        name: String,
+        default: Option<String>,
+        type_hint: Option<String>,
+        param_type: Option<String>,
+    ) -> PyResult<Self> {
+        let param_type = match param_type {
+            Some(json) => {
+                serde_json::from_str::<CalcParamType>(&json).map_err(PyUltimaErr::SerdeJson)?
+            }
+            None => CalcParamType::default(),
+        };
         let _inner = CalcParameter {
             name,
             default,
             type_hint,
+            param_type,
         };
-        Self { _inner }
+        Ok(Self { _inner })
     }
 }
 #[pyclass]
@@ -42,7 +57,8 @@ impl MeasureWrapper {
         precompute_filter: Option<Vec<Vec<FilterWrapper>>>,
         aggregation_restriction: Option<String>,
         calc_params: Option<Vec<CalcParamWrapper>>,
-    ) -> Self {
+        metadata: Option<String>,
+    ) -> PyResult<Self> {
         let precompute_filters = precompute_filter
             .unwrap_or_default()
             .into_iter()
@@ -53,6 +69,8 @@ impl MeasureWrapper {
             })
             .collect::<Vec<Vec<FilterE>>>();
 
+        // No hierarchies to resolve Descendants with
+        check_resolved(&precompute_filters).map_err(PyUltimaErr::Ultima)?;
         let precomputefilter = fltr_chain(&precompute_filters);
 
         let calc_params = calc_params
@@ -61,16 +79,19 @@ impl MeasureWrapper {
             .map(|cpw| cpw._inner)
             .collect::<Vec<CalcParameter>>();
 
+        let metadata = parse_metadata(metadata)?;
+
         let inner: Measure = BaseMeasure {
             name,
             calculator: calc.inner,
             precomputefilter,
             aggregation: aggregation_restriction,
             calc_params,
+            metadata,
         }
         .into();
 
-        Self { _inner: inner }
+        Ok(Self { _inner: inner })
     }
 
     #[classmethod]
@@ -80,21 +101,35 @@ impl MeasureWrapper {
         calc: CalculatorWrapper,
         depends_upon: Vec<(String, String)>,
         calc_params: Option<Vec<CalcParamWrapper>>,
-    ) -> Self {
+        metadata: Option<String>,
+    ) -> PyResult<Self> {
         let calc_params = calc_params
             .unwrap_or_default()
             .into_iter()
             .map(|cpw| cpw._inner)
             .collect::<Vec<CalcParameter>>();
 
+        let metadata = parse_metadata(metadata)?;
+
         let inner: Measure = DependantMeasure {
             name,
             calculator: calc.inner,
             depends_upon,
             calc_params,
+            metadata,
         }
         .into();
 
-        Self { _inner: inner }
+        Ok(Self { _inner: inner })
     }
 }
+
+/// metadata is a JSON of [MeasureMetadata], eg {"description": "...", "category": ["Risk"]}
+fn parse_metadata(metadata: Option<String>) -> PyResult<MeasureMetadata> {
+    Ok(match metadata {
+        Some(json) => {
+            serde_json::from_str::<MeasureMetadata>(&json).map_err(PyUltimaErr::SerdeJson)?
+        }
+        None => MeasureMetadata::default(),
+    })
+}
@@ -37,6 +37,17 @@ class TestCreation(unittest.TestCase):
         ds = ul.FRTBDataSet.from_frame(df)
         self.assertRaises(ul.internals.UltimaError, ds.validate)
 
+    def test_append_and_delete(self) -> None:
+        df = pl.DataFrame({"a": [1.0, 2.0], "c": ["a", "b"]})
+        ds = ul.DataSet.from_frame(df)
+
+        appended = ds.append_frame(pl.DataFrame({"a": [3.0], "c": ["a"]}))
+        deleted = ds.delete_where([[ul.EqFilter(field="c", value="b")]])
+
+        assert (appended, deleted) == (1, 1)
+        expected = pl.DataFrame({"a": [1.0, 3.0], "c": ["a", "a"]})
+        assert ds.frame().equals(expected)
+
     # TODO this will be turned into prepare_for_each_request
     def test_ds_already_prepared(self) -> None:
         data = {"a": [1, 2, 3], "b": [4, 5, 6], "c": ["a", "a", "b"]}
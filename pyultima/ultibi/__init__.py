@@ -9,15 +9,22 @@ from .internals import (
     AggRequest,
     BaseMeasure,
     CalcParam,
+    CancelledError,
     ComputeRequest,
+    ComputeTimeoutError,
     CustomCalculator,
     DataSet,
     DataSource,
+    DataSourceError,
     DbInfo,
     DependantMeasure,
+    DescendantsFilter,
     EqFilter,
     FRTBDataSet,
     InFilter,
+    InvalidCalcParamError,
+    InvalidOverrideError,
+    InvalidRequestError,
     NeqFilter,
     NoDataError,
     NotInFilter,
@@ -25,6 +32,9 @@ from .internals import (
     RustCalculator,
     StandardCalculator,
     UltimaError,
+    UnknownAggregationError,
+    UnknownColumnError,
+    UnknownMeasureError,
     aggregation_ops,
 )
 
@@ -48,6 +58,7 @@ __all__ = [
     "NeqFilter",
     "InFilter",
     "NotInFilter",
+    "DescendantsFilter",
     "Measure",
     "BaseMeasure",
     "DependantMeasure",
@@ -56,6 +67,15 @@ __all__ = [
     "StandardCalculator",
     "CalcParam",
     "UltimaError",
+    "UnknownMeasureError",
+    "UnknownAggregationError",
+    "UnknownColumnError",
+    "InvalidCalcParamError",
+    "InvalidOverrideError",
+    "InvalidRequestError",
+    "DataSourceError",
+    "ComputeTimeoutError",
+    "CancelledError",
     "DbInfo",
     "RustCalculator",
 ]
@@ -6,11 +6,31 @@ they all import from each other via this __init__ file using
 `import ultibi.internals as uli`. The imports below are being shared across this module.
 """
 
-from ..rust_module.ultibi_engine import NoDataError, OtherError, UltimaError
+from ..rust_module.ultibi_engine import (
+    CancelledError,
+    ComputeTimeoutError,
+    DataSourceError,
+    InvalidCalcParamError,
+    InvalidOverrideError,
+    InvalidRequestError,
+    NoDataError,
+    OtherError,
+    UltimaError,
+    UnknownAggregationError,
+    UnknownColumnError,
+    UnknownMeasureError,
+)
 from .dataset import DS, DataSet, FRTBDataSet
 from .datasource import DataSource
 from .db import DbInfo
-from .filters import EqFilter, Filter, InFilter, NeqFilter, NotInFilter
+from .filters import (
+    DescendantsFilter,
+    EqFilter,
+    Filter,
+    InFilter,
+    NeqFilter,
+    NotInFilter,
+)
 from .measure import (
     BaseMeasure,
     CalcParam,
@@ -34,10 +54,20 @@ __all__ = [
     "NoDataError",
     "OtherError",
     "UltimaError",
+    "UnknownMeasureError",
+    "UnknownAggregationError",
+    "UnknownColumnError",
+    "InvalidCalcParamError",
+    "InvalidOverrideError",
+    "InvalidRequestError",
+    "DataSourceError",
+    "ComputeTimeoutError",
+    "CancelledError",
     "EqFilter",
     "NeqFilter",
     "InFilter",
     "NotInFilter",
+    "DescendantsFilter",
     "Filter",
     "Measure",
     "BaseMeasure",
@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import json
 from typing import Any, Type, TypeVar, no_type_check
 
 import polars as pl
@@ -89,6 +90,14 @@ class DataSet:
              operations
         """
 
+        self.measures_metadata: "dict[str, dict[str, Any]]" = json.loads(
+            self.inner.measures_metadata()
+        )
+        """{measureName: {"description", "reference", "unit", "format", "category",
+            "hidden"}}. category is the path of the measure in the UI tree, eg
+            ["SBM", "GIRR", "Delta"]
+        """
+
         self.calc_params: "list[tuple[str, str|None, str|None]]" = (
             self.inner.calc_params()
         )
@@ -194,6 +203,63 @@ class DataSet:
         vec_srs = self.inner.frame(fltrs)
         return pl.DataFrame(vec_srs)
 
+    def append_frame(self, df: pl.DataFrame) -> int:
+        """Appends rows to an In Memory DataSet (eg intraday trades).
+        Rows are validated and prepared first, hence must have the columns
+        of the data before it's prepared. Attributes and hierarchies are
+        not joined again, so rows must already carry their columns.
+        Cache is cleaned
+
+        Args:
+            df (pl.DataFrame): Rows to append
+
+        Returns:
+            int: Number of rows appended
+        """
+        return self.inner.append_frame(df)
+
+    def delete_where(self, fltrs: "list[list[AnyFilter]]") -> int:
+        """Deletes rows of an In Memory DataSet (eg amended trades).
+        Cache is cleaned
+
+        Args:
+            fltrs (list[list[AnyFilter]]): Rows to delete. Required
+
+        Returns:
+            int: Number of rows deleted
+        """
+        fltrs = [[a_fltr.inner for a_fltr in or_fltrs] for or_fltrs in fltrs]
+        return self.inner.delete_where(fltrs)
+
+    def drill_through(
+        self,
+        req: "dict[Any, Any]",
+        cell: "dict[str, str | None] | None" = None,
+        measure: "str | None" = None,
+        offset: int = 0,
+        limit: int = 100,
+    ) -> pl.DataFrame:
+        """Prepared rows behind a cell of the result of `req`, with the filters,
+        overrides and additional rows of `req` and the precomputefilter of `measure`
+
+        Args:
+            req (dict[Any, Any]): Aggregation request the cell is from
+            cell (dict[str, str | None] | None): Values of the groupby of the cell.
+                Dimensions which are not here are not filtered on
+            measure (str | None): Measure of the cell. Rows of any measure
+                of `req` if None
+            offset (int): First row of the page
+            limit (int): Number of rows of the page, up to 10000
+
+        Returns:
+            pl.DataFrame: The page of the rows
+        """
+        data = dict(
+            request=req, cell=cell or {}, measure=measure, offset=offset, limit=limit
+        )
+        vec_srs = self.inner.drill_through(json.dumps(data))
+        return pl.DataFrame(vec_srs)
+
     def compute(self, req: "dict[Any, Any]|uli.ComputeRequest") -> pl.DataFrame:
         """Make sure that requested groupby and filters exist in self.columns,
         Make sure that requested measures exist in self.measures
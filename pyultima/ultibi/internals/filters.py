@@ -145,3 +145,30 @@ class NotInFilter(Filter):
         """
         data = dict(op=self.op, field=field, value=value)
         super().__init__(data)
+
+
+class DescendantsFilter(Filter):
+    """Node of a hierarchy of the DataSet and all of its descendants
+
+    Examples
+    --------
+    Constructing a DescendantsFilter:
+
+    >>> import ultibi as ul
+    >>> f = ul.DescendantsFilter(
+    ...     hierarchy="Organisation", node="UK", level="LegalEntity"
+    ... )
+    """
+
+    op = "Descendants"
+
+    def __init__(self, hierarchy: str, node: str, level: str | None = None) -> None:
+        """
+
+        Args:
+            hierarchy (str): Name of the hierarchy
+            node (str): Node
+            level (str | None): Level of the node. Required for level based hierarchies
+        """
+        data = dict(op=self.op, hierarchy=hierarchy, node=node, level=level)
+        super().__init__(data)
@@ -18,10 +18,29 @@ from ..rust_module.ultibi_engine import (
 class CalcParam:
     inner = CalcParamWrapper
 
+    """
+    Args:
+        name (str): name of the param, as passed in the request's calc_params
+        default (str|None): shown in the UI
+        type_hint (str|None): human readable description of the value
+        param_type (dict|None): validated before computing, eg
+            {"type": "float"}, {"type": "enum", "choices": ["BCBS", "CRR2"]},
+            {"type": "vector", "len": 3}. Not validated if None
+    """
+
     def __init__(
-        self, name: str, default: "str|None" = None, type_hint: "str|None" = None
+        self,
+        name: str,
+        default: "str|None" = None,
+        type_hint: "str|None" = None,
+        param_type: "dict[str, Any]|None" = None,
     ):
-        self.inner = CalcParamWrapper(name, default, type_hint)
+        self.inner = CalcParamWrapper(
+            name,
+            default,
+            type_hint,
+            json.dumps(param_type) if param_type is not None else None,
+        )
 
 
 TFilter = TypeVar("TFilter", bound=Filter)
@@ -167,6 +186,9 @@ class BaseMeasure(Measure):
             eg. if your measure should only be aggregated as "scalar" or "sum"
         calc_params (list[CalcParam] | None, optional):
             Allows user to set calc_params (which are passed to calculators) via UI
+        metadata (dict | None, optional): Shown in the UI, eg
+            {"description": "...", "reference": "MAR21.4", "unit": "USD",
+            "format": "#,##0.00", "category": ["SBM", "GIRR"], "hidden": False}
 
 
     Examples
@@ -223,6 +245,7 @@ class BaseMeasure(Measure):
         precompute_filter: "list[list[TFilter]]|None" = None,
         aggregation_restriction: "str|None" = None,
         calc_params: "list[CalcParam]|None" = None,
+        metadata: "dict[str, Any]|None" = None,
     ) -> None:
         if precompute_filter:
             precompute_filter_inner = [[y.inner for y in x] for x in precompute_filter]
@@ -239,6 +262,7 @@ class BaseMeasure(Measure):
             precompute_filter_inner,
             aggregation_restriction,
             calc_params_inner,
+            json.dumps(metadata) if metadata is not None else None,
         )
         super().__init__(measure_wrapper)
 
@@ -300,6 +324,7 @@ class DependantMeasure(Measure):
         calc: TCalculator,
         depends_upon: list[tuple[str, str]],
         calc_params: "list[CalcParam]|None" = None,
+        metadata: "dict[str, Any]|None" = None,
     ) -> None:
         if calc_params:
             calc_params_inner = [calc_param.inner for calc_param in calc_params]
@@ -311,5 +336,6 @@ class DependantMeasure(Measure):
             calc.inner,
             depends_upon,
             calc_params_inner,
+            json.dumps(metadata) if metadata is not None else None,
         )
         super().__init__(measure_wrapper)
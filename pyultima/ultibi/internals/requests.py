@@ -38,6 +38,13 @@ class AggRequest:
     ... )
     >>> ar = ul.AggRequest(request_as_duct)
 
+    Notes
+    -----
+    With ``totals=True`` (or ``grouping_sets``) subtotal rows come with a
+    ``grouping_id`` column, like SQL GROUPING_ID: bit ``n-1-i`` is set if
+    ``groupby[i]`` is rolled up in the row. Rolled up dimensions are null, not
+    "Total", and nulls of the data are kept as nulls.
+
     """
 
     def __init__(
@@ -82,6 +89,13 @@ class ComputeRequest:
     ... )
     >>> ar = ul.ComputeRequest(request_as_dict)
 
+    Notes
+    -----
+    With ``totals=True`` (or ``grouping_sets``) subtotal rows come with a
+    ``grouping_id`` column, like SQL GROUPING_ID: bit ``n-1-i`` is set if
+    ``groupby[i]`` is rolled up in the row. Rolled up dimensions are null, not
+    "Total", and nulls of the data are kept as nulls.
+
     """
 
     cr: ComputeRequestWrapper
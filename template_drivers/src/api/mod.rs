@@ -12,6 +12,12 @@ use std::{
 use ultibi::{
     api::routers,
     //aggregations::BASE_CALCS, polars::prelude::PolarsError,
+    audit::AuditLog,
+    auth::Security,
+    jobs::JobRegistry,
+    metrics::{Metrics, RequestMetrics},
+    reload::Reloader,
+    templates::TemplateStore,
     AggregationRequest,
     DataSet,
 };
@@ -24,7 +30,8 @@ include!(concat!(env!("OUT_DIR"), "/generated.rs"));
 pub fn run_server(
     listener: TcpListener,
     ds: Arc<RwLock<dyn DataSet>>,
-    _templates: Vec<AggregationRequest>,
+    templates: Vec<AggregationRequest>,
+    reloader: Option<Arc<Reloader>>,
 ) -> std::io::Result<Server> {
     // Read .env
     dotenv::dotenv().ok();
@@ -32,7 +39,23 @@ pub fn run_server(
     pretty_env_logger::init();
 
     let ds = Data::from(ds);
-    let _templates = Data::new(_templates);
+    let templates = Data::new(
+        TemplateStore::from_env(templates)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?,
+    );
+    let jobs = Data::new(JobRegistry::from_env());
+    let audit = Data::new(AuditLog::from_env());
+    let metrics = Data::new(Metrics::default());
+    // Without a Reloader, the data can't be reloaded via the admin endpoint
+    let reloader = reloader.map(|r| {
+        r.set_audit(audit.clone().into_inner());
+        Data::from(r)
+    });
+    // Authentication and entitlements are set up via env variables, see [ultibi::auth]
+    let security = Data::new(
+        Security::from_env()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?,
+    );
 
     // This is Useful to run locally in dev mode, without regenerating dist, just serve whatever is in the dir
     // Note: it goes together with .service(actix_files::Files::new("/", &static_files_dir).index_file("index.html")) later
@@ -40,21 +63,28 @@ pub fn run_server(
     //     std::env::var("STATIC_FILES_DIR").unwrap_or_else(|_| "frontend/dist".to_string());
 
     let server = HttpServer::new(move || {
-        //let auth = HttpAuthentication::basic(validator);
-
         // We don't use actix_web_static_files here since because we don't want to rebuild the whole
         // project when working on the frontend
         let generated = generate();
 
-        App::new()
+        let app = App::new()
             .wrap(Logger::default())
-            //.wrap(auth)
+            .wrap(RequestMetrics)
             .configure(routers::configure())
             // must be the last one
             //.service(actix_files::Files::new("/", &static_files_dir).index_file("index.html"))
             .service(ResourceFiles::new("/", generated))
             .app_data(ds.clone())
-            .app_data(_templates.clone())
+            .app_data(templates.clone())
+            .app_data(jobs.clone())
+            .app_data(security.clone())
+            .app_data(audit.clone())
+            .app_data(metrics.clone());
+
+        match &reloader {
+            Some(r) => app.app_data(r.clone()),
+            None => app,
+        }
     })
     .listen(listener)?
     .run();
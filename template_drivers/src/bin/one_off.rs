@@ -8,7 +8,7 @@ use template_drivers::helpers::{acquire, cli::CliOnce};
 
 use log::{error, info};
 use std::time::Instant;
-use std::{fs, sync::Arc};
+use std::{collections::BTreeMap, fs, sync::Arc};
 
 #[cfg(target_os = "linux")]
 use jemallocator::Jemalloc;
@@ -40,6 +40,7 @@ fn main() -> anyhow::Result<()> {
     let cli = CliOnce::parse();
     let setup_path = cli.config;
     let requests_path = cli.requests;
+    let params: BTreeMap<String, String> = cli.params.into_iter().collect();
 
     // Build Data
     let data = acquire::data::<DataSetType>(setup_path.as_str());
@@ -55,10 +56,24 @@ fn main() -> anyhow::Result<()> {
 
     // From here we do not panic
     for request in requests {
+        // Parameters which a request doesn't declare are ignored
+        let values = params
+            .iter()
+            .filter(|(k, _)| request.parameters.iter().any(|p| &p.name == *k))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let request = match request.with_parameters(&values) {
+            Ok(r) => r,
+            Err(e) => {
+                error!("Invalid parameters: {:#?}", e);
+                continue;
+            }
+        };
         //let rqst_str = serde_json::to_string(&request);
         info!("{:?}", request);
         let now = Instant::now();
-        match ultibi::exec_agg(&*Arc::clone(&arc_data), request, prepare) {
+        let ctx = ultibi::ExecutionContext::new();
+        match ultibi::exec_agg_with_context(&*Arc::clone(&arc_data), request, prepare, &ctx) {
             Err(e) => {
                 error!("Application error: {:#?}", e);
                 continue;
@@ -68,6 +83,9 @@ fn main() -> anyhow::Result<()> {
                 let elapsed = now.elapsed();
                 println!("result: {df}");
                 println!("Time to Compute: {:.6?}", elapsed);
+                for (stage, d) in ctx.timings() {
+                    println!("    {stage}: {d:.6?}");
+                }
             }
         }
     }
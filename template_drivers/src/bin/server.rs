@@ -1,4 +1,5 @@
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::RwLock;
 use std::{env, fs};
 use std::{net::TcpListener, sync::Arc};
@@ -6,6 +7,7 @@ use std::{net::TcpListener, sync::Arc};
 use clap::Parser;
 use template_drivers::api::run_server;
 use template_drivers::helpers::{acquire, cli::CliServer};
+use ultibi::reload::{Reloader, DEFAULT_WATCH_INTERVAL};
 use ultibi::AggregationRequest;
 //use log::info;
 
@@ -51,6 +53,24 @@ async fn main() -> std::io::Result<()> {
         .expect("can't parse ADDRES variable");
 
     let listener = TcpListener::bind(addr).expect("Failed to bind random port");
-    let data = acquire::data::<DataSetType>(setup_path.as_str());
-    run_server(listener, Arc::new(RwLock::new(data)), _requests)?.await
+    // Loading and preparing might take a while. Meanwhile the server is live, but not ready
+    let data = Arc::new(RwLock::new(acquire::empty::<DataSetType>()));
+
+    // Initial and any new data is built from the same config
+    let config = setup_path.clone();
+    let reloader = Arc::new(Reloader::deferred(data.clone(), move || {
+        acquire::try_data::<DataSetType>(config.as_str())
+    }));
+    reloader
+        .spawn_reload()
+        .expect("Nothing else is loading yet");
+    if cli.watch {
+        let files = acquire::input_files(setup_path.as_str())
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        reloader.watch(files, DEFAULT_WATCH_INTERVAL);
+    }
+
+    run_server(listener, data, _requests, Some(reloader))?.await
 }
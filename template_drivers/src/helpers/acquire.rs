@@ -1,7 +1,12 @@
 use std::time::Instant;
 
 use log::info;
-use ultibi::{new::NewSourcedDataSet, read_toml2, DataSet, DataSourceConfig, MeasuresMap};
+use ultibi::{
+    datasource::DataSource,
+    errors::{UltiResult, UltimaErr},
+    new::NewSourcedDataSet,
+    read_toml2, DataFrame, DataSet, DataSourceConfig, MeasuresMap,
+};
 
 /// Reads initial DataSet from Source
 ///
@@ -10,11 +15,17 @@ use ultibi::{new::NewSourcedDataSet, read_toml2, DataSet, DataSourceConfig, Meas
 /// Then .prepare()
 ///
 /// If streaming is False - also collects
+pub fn data<DS: NewSourcedDataSet>(config_path: &str) -> DS {
+    try_data(config_path).expect("Can not proceed without valid Data Set Up") //Unrecovarable error
+}
+
+/// Same as [data], but returns an error instead of panicking (eg to reload data
+/// while the server is running). Note: building from an invalid config may still panic
 #[allow(clippy::uninlined_format_args)]
-pub fn data<DS: NewSourcedDataSet>(config_path: &str) -> impl DataSet {
+pub fn try_data<DS: NewSourcedDataSet>(config_path: &str) -> UltiResult<DS> {
     // Read Config
     let conf = read_toml2::<DataSourceConfig>(config_path)
-        .expect("Can not proceed without valid Data Set Up"); //Unrecovarable error
+        .map_err(|e| UltimaErr::Other(format!("Invalid Data Set Up {config_path}: {e}")))?;
     info!("Data SetUp: {:?}", conf);
 
     let (lf, measure_vec, build_params) = conf.build();
@@ -45,12 +56,29 @@ pub fn data<DS: NewSourcedDataSet>(config_path: &str) -> impl DataSet {
     // Pre build some columns, which you wish to store in memory alongside the original data
     // Note if streaming then .prepare() should happen post filtering
     if prepare_collect {
-        data.prepare().expect("Failed to Prepare Frame");
+        data.prepare()?;
         let now = Instant::now();
-        data.collect().expect("Failed to Prepare Frame");
+        data.collect()?;
         let elapsed = now.elapsed();
         println!("Time to Prepare DF: {:.6?}", elapsed);
     }
 
-    data
+    Ok(data)
+}
+
+/// A DataSet without data, to serve until the real one is loaded
+pub fn empty<DS: NewSourcedDataSet>() -> DS {
+    DS::new(
+        DataSource::InMemory(DataFrame::default()),
+        MeasuresMap::default(),
+        Default::default(),
+        Default::default(),
+    )
+}
+
+/// Files the DataSet of `config_path` is read from. Empty if the config is invalid
+pub fn input_files(config_path: &str) -> Vec<String> {
+    read_toml2::<DataSourceConfig>(config_path)
+        .map(|conf| conf.input_files())
+        .unwrap_or_default()
 }
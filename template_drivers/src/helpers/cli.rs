@@ -16,6 +16,15 @@ pub struct CliOnce {
     /// In future this to be a mandatory field
     #[arg(short, long, value_name = "PATH_TO_JSON_FILE", default_value_t = REQUESTS.into())]
     pub requests: String,
+    /// Values of the parameters of the requests, eg `-p cob=22/07/2022 -p desk=FXOptions`
+    #[arg(short, long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    pub params: Vec<(String, String)>,
+}
+
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+        .ok_or_else(|| format!("Expected NAME=VALUE, got {s}"))
 }
 
 /// Cli for the server run
@@ -32,4 +41,7 @@ pub struct CliServer {
 
     #[arg(short, long, value_name = "SOCKET_ADDRESS")]
     pub address: Option<String>,
+    /// Reload the data whenever the input files of the config change
+    #[arg(short, long)]
+    pub watch: bool,
 }
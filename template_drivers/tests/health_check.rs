@@ -32,6 +32,22 @@ async fn health_check_works() {
     assert_eq!(Some(0), response.content_length());
 }
 
+#[tokio::test]
+async fn probes_work() {
+    let addr = spawn_app();
+    let client = reqwest::Client::new();
+    for probe in ["live", "ready"] {
+        let response = client
+            .get(&format!("{}/api/{probe}", &addr))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        // Data is loaded before the server starts, hence ready
+        assert!(response.status().is_success(), "{probe}");
+        assert_eq!(Some(0), response.content_length());
+    }
+}
+
 // Launch our application in the background(via tokio::spawn)
 #[allow(clippy::let_underscore_future)]
 fn spawn_app() -> String {
@@ -39,8 +55,8 @@ fn spawn_app() -> String {
     // We retrieve the port assigned to us by the OS
     let port = listener.local_addr().unwrap().port();
     let ds = Arc::new(RwLock::new(DataSetBase::default()));
-    let server =
-        template_drivers::api::run_server(listener, ds, vec![]).expect("Failed to bind address");
+    let server = template_drivers::api::run_server(listener, ds, vec![], None)
+        .expect("Failed to bind address");
     let _ = tokio::spawn(server);
     // We return the application address to the caller!
     format!("http://127.0.0.1:{port}")
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use derivative::Derivative;
 use once_cell::sync::Lazy;
 use polars::{
-    lazy::dsl::lit,
+    lazy::dsl::{col, lit},
     prelude::{Expr, QuantileInterpolOptions},
 };
 
@@ -23,6 +23,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "sum".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.sum()),
+                rollup_fn: Some(Box::new(|e: Expr| e.sum())),
             },
         ),
         (
@@ -30,6 +31,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "min".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.min()),
+                rollup_fn: Some(Box::new(|e: Expr| e.min())),
             },
         ),
         (
@@ -37,6 +39,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "max".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.max()),
+                rollup_fn: Some(Box::new(|e: Expr| e.max())),
             },
         ),
         (
@@ -44,6 +47,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "mean".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.mean()),
+                rollup_fn: None,
             },
         ),
         (
@@ -51,6 +55,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "var".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.var(1)),
+                rollup_fn: None,
             },
         ),
         (
@@ -60,6 +65,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
                 aggregated_expr_fn: Box::new(|e: Expr| {
                     e.quantile(lit(0.95), QuantileInterpolOptions::Lower)
                 }),
+                rollup_fn: None,
             },
         ),
         (
@@ -67,6 +73,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "first".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.first()),
+                rollup_fn: Some(Box::new(|e: Expr| e.first())),
             },
         ),
         (
@@ -74,6 +81,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "count".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.count()),
+                rollup_fn: Some(Box::new(|e: Expr| e.sum())),
             },
         ),
         (
@@ -81,6 +89,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "n_unique".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e.n_unique()),
+                rollup_fn: None,
             },
         ),
         (
@@ -88,6 +97,7 @@ pub static BASE_CALCS: Lazy<HashMap<&'static str, Aggregation>> = Lazy::new(|| {
             Aggregation {
                 name_suffix: "scalar".to_string(),
                 aggregated_expr_fn: Box::new(|e: Expr| e),
+                rollup_fn: None,
             },
         ),
     ])
@@ -101,6 +111,10 @@ pub struct Aggregation {
     pub name_suffix: String,
     #[derivative(Debug = "ignore")]
     pub aggregated_expr_fn: AggregationExecutor,
+    /// Aggregates results of this aggregation over finer groups, eg sum of sums.
+    /// None if the result has to be recomputed from the data for every group (eg mean)
+    #[derivative(Debug = "ignore")]
+    pub rollup_fn: Option<AggregationExecutor>,
 }
 
 impl Aggregation {
@@ -112,6 +126,13 @@ impl Aggregation {
             name_buffer.to_owned()
         }
     }
+    /// Rolls up the column of this aggregation, see [Aggregation::rollup_fn]
+    pub fn rollup(&self, name_buffer: &str) -> Option<Expr> {
+        let alias = self.new_name(name_buffer);
+        self.rollup_fn
+            .as_ref()
+            .map(|f| f(col(&alias)).alias(&alias))
+    }
     pub fn aggregate(&self, calc: Expr, name_buffer: &str) -> Expr {
         let alias = self.new_name(name_buffer);
         let aggregated_expr = (self.aggregated_expr_fn)(calc);
@@ -0,0 +1,280 @@
+//! Measures declared in a request as arithmetic over the other measures of the request,
+//! eg `[FX DeltaCharge] / [SA Charge]`. They become [DependantMeasure]s for the time of the request
+
+use std::sync::Arc;
+
+use polars::prelude::{col, lit, Expr};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aggregations::BASE_CALCS,
+    errors::{UltiResult, UltimaErr},
+    AggregationRequest, DependantMeasure, MeasuresMap, GROUPING_ID,
+};
+
+/// eg `{"name": "FX share", "expression": "100 * [FX DeltaCharge] / total([FX DeltaCharge])"}`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CalculatedMeasure {
+    /// Name of the column in the output
+    pub name: String,
+    /// `+ - * /`, parentheses, numbers and references in square brackets to:
+    /// * a column of a requested measure, eg `[Balance_sum]` or `[SA Charge]`
+    /// * a requested measure, eg `[Balance]`, if it's requested with one aggregation only
+    /// * a calculated measure declared before this one
+    ///
+    /// `total(x)` is the sum of `x` over the rows of the result (the finest grouping set only)
+    pub expression: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Reference(String),
+    Function(String),
+    Op(char),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut res = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '+' | '-' | '*' | '/' | '(' | ')' => res.push(Token::Op(c)),
+            '[' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, ']')) => break,
+                        Some((_, c)) => name.push(c),
+                        None => return Err(format!("unclosed [ at {i}")),
+                    }
+                }
+                res.push(Token::Reference(name.trim().to_string()))
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                    number.push(c)
+                }
+                let n = number
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number {number} at {i}"))?;
+                res.push(Token::Number(n))
+            }
+            c if c.is_alphabetic() => {
+                let mut name = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    name.push(c)
+                }
+                res.push(Token::Function(name))
+            }
+            c => return Err(format!("unexpected {c} at {i}")),
+        }
+    }
+    Ok(res)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Reference(String),
+    Neg(Box<Node>),
+    Binary(Box<Node>, char, Box<Node>),
+    Total(Box<Node>),
+}
+
+/// expr := term (('+' | '-') term)*
+/// term := unary (('*' | '/') unary)*
+/// unary := '-' unary | number | reference | function '(' expr ')' | '(' expr ')'
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse(s: &str) -> Result<Node, String> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+        };
+        let node = parser.expr()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(node),
+            Some(t) => Err(format!("unexpected {t:?}")),
+        }
+    }
+
+    fn next_op_in(&mut self, ops: &[char]) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                self.pos += 1;
+                Some(*op)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, op: char) -> Result<(), String> {
+        self.next_op_in(&[op])
+            .map(|_| ())
+            .ok_or_else(|| format!("expected {op}"))
+    }
+
+    fn expr(&mut self) -> Result<Node, String> {
+        let mut res = self.term()?;
+        while let Some(op) = self.next_op_in(&['+', '-']) {
+            res = Node::Binary(Box::new(res), op, Box::new(self.term()?))
+        }
+        Ok(res)
+    }
+
+    fn term(&mut self) -> Result<Node, String> {
+        let mut res = self.unary()?;
+        while let Some(op) = self.next_op_in(&['*', '/']) {
+            res = Node::Binary(Box::new(res), op, Box::new(self.unary()?))
+        }
+        Ok(res)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Op('-')) => Ok(Node::Neg(Box::new(self.unary()?))),
+            Some(Token::Op('(')) => {
+                let res = self.expr()?;
+                self.expect(')')?;
+                Ok(res)
+            }
+            Some(Token::Number(n)) => Ok(Node::Number(n)),
+            Some(Token::Reference(r)) => Ok(Node::Reference(r)),
+            Some(Token::Function(f)) if f == "total" => {
+                self.expect('(')?;
+                let res = self.expr()?;
+                self.expect(')')?;
+                Ok(Node::Total(Box::new(res)))
+            }
+            Some(Token::Function(f)) => Err(format!("unknown function {f}")),
+            Some(t) => Err(format!("unexpected {t:?}")),
+            None => Err("unexpected end of the expression".to_string()),
+        }
+    }
+}
+
+impl Node {
+    /// `column` resolves a reference into the name of a column
+    fn to_expr(
+        &self,
+        column: &mut impl FnMut(&str) -> UltiResult<String>,
+        grouped: bool,
+    ) -> UltiResult<Expr> {
+        Ok(match self {
+            Node::Number(n) => lit(*n),
+            Node::Reference(r) => col(&column(r)?),
+            Node::Neg(n) => lit(0.) - n.to_expr(column, grouped)?,
+            Node::Binary(l, op, r) => {
+                let (l, r) = (l.to_expr(column, grouped)?, r.to_expr(column, grouped)?);
+                match op {
+                    '+' => l + r,
+                    '-' => l - r,
+                    '*' => l * r,
+                    _ => l / r,
+                }
+            }
+            // Subtotals would be counted more than once
+            Node::Total(n) if grouped => n
+                .to_expr(column, grouped)?
+                .filter(col(GROUPING_ID).eq(lit(0u32)))
+                .sum(),
+            Node::Total(n) => n.to_expr(column, grouped)?.sum(),
+        })
+    }
+}
+
+impl AggregationRequest {
+    /// (Measure, Aggregation) of the requested measure a reference is to
+    fn referenced_measure(&self, reference: &str) -> UltiResult<(String, String)> {
+        let columns = self
+            .measures
+            .iter()
+            .filter(|(m, agg)| {
+                BASE_CALCS
+                    .get(agg.as_str())
+                    .map_or(false, |a| a.new_name(m) == reference)
+            })
+            .collect::<Vec<_>>();
+        let by_name = self
+            .measures
+            .iter()
+            .filter(|(m, _)| m == reference)
+            .collect::<Vec<_>>();
+        match (&columns[..], &by_name[..]) {
+            ([ma, ..], _) | ([], [ma]) => Ok((*ma).clone()),
+            ([], []) => Err(UltimaErr::InvalidRequest(format!(
+                "[{reference}] is not a requested measure"
+            ))),
+            ([], _) => Err(UltimaErr::InvalidRequest(format!(
+                "[{reference}] is requested with several aggregations. Refer to one of its columns instead"
+            ))),
+        }
+    }
+
+    /// [AggregationRequest::calculated] in the order they are declared
+    pub(crate) fn calculated_measures(
+        &self,
+        available: &MeasuresMap,
+        grouped: bool,
+    ) -> UltiResult<Vec<DependantMeasure>> {
+        let mut res: Vec<DependantMeasure> = Vec::with_capacity(self.calculated.len());
+        for cm in &self.calculated {
+            if cm.name.is_empty()
+                || available.contains_key(&cm.name)
+                || res.iter().any(|dm| dm.name == cm.name)
+                || self.referenced_measure(&cm.name).is_ok()
+            {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "Name of the calculated measure \"{}\" must be new and not empty",
+                    cm.name
+                )));
+            }
+            let node = Parser::parse(&cm.expression).map_err(|e| {
+                UltimaErr::InvalidRequest(format!(
+                    "Invalid expression of the calculated measure {}: {e}",
+                    cm.name
+                ))
+            })?;
+
+            let mut depends_upon = vec![];
+            let mut column = |reference: &str| -> UltiResult<String> {
+                let (measure, agg) = if res.iter().any(|dm| dm.name == reference) {
+                    (reference.to_string(), "scalar".to_string())
+                } else {
+                    self.referenced_measure(reference)?
+                };
+                let name = BASE_CALCS
+                    .get(agg.as_str())
+                    .ok_or_else(|| UltimaErr::UnknownAggregation {
+                        aggregation: agg.clone(),
+                        available: BASE_CALCS.keys().map(|a| a.to_string()).collect(),
+                    })?
+                    .new_name(&measure);
+                if !depends_upon.contains(&(measure.clone(), agg.clone())) {
+                    depends_upon.push((measure, agg))
+                }
+                Ok(name)
+            };
+            let expr = node.to_expr(&mut column, grouped)?;
+
+            res.push(DependantMeasure {
+                name: cm.name.clone(),
+                calculator: Arc::new(move |_| Ok(expr.clone())),
+                depends_upon,
+                calc_params: vec![],
+                metadata: Default::default(),
+            });
+        }
+        Ok(res)
+    }
+}
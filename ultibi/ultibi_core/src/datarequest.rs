@@ -3,11 +3,18 @@
 use std::collections::BTreeMap;
 
 use crate::aggregations::AggregationName;
+use crate::calculated::CalculatedMeasure;
+use crate::dimensions::Dimension;
+use crate::errors::{UltiResult, UltimaErr};
 use crate::filters::FilterE;
+use crate::hierarchy::Drill;
 use crate::overrides::Override;
+use crate::parameters::TemplateParameter;
+use crate::window::WindowMeasure;
 use crate::MeasureName;
 use crate::{add_row::AdditionalRows, filters::AndOrFltrChain};
 
+use polars::prelude::DataFrame;
 use serde::{Deserialize, Serialize};
 
 pub type CPM = BTreeMap<String, String>;
@@ -17,7 +24,6 @@ pub type CPM = BTreeMap<String, String>;
 /// i) Aggregation: apply the same procedure to every group and get a single number
 ///
 /// Otherwise, ii) Apply the same procedure to every group and get multiple numbers (ie a Breakdown)
-// TODO #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum ComputeRequest {
@@ -29,6 +35,27 @@ pub enum ComputeRequest {
     Breakdown,
 }
 
+/// Derive doesn't support untagged enums with a unit variant, and [ComputeRequest::Breakdown]
+/// is not accepted yet anyway
+#[cfg(feature = "openapi")]
+impl<'s> utoipa::ToSchema<'s> for ComputeRequest {
+    fn schema() -> (
+        &'s str,
+        utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+    ) {
+        use utoipa::openapi::{schema::OneOfBuilder, Ref};
+
+        (
+            "ComputeRequest",
+            OneOfBuilder::new()
+                .item(Ref::from_schema_name("AggregationRequest"))
+                .item(Ref::from_schema_name("ReportRequest"))
+                .description(Some("Either an AggregationRequest or a ReportRequest"))
+                .into(),
+        )
+    }
+}
+
 impl From<AggregationRequest> for ComputeRequest {
     fn from(item: AggregationRequest) -> Self {
         ComputeRequest::Aggregation(item)
@@ -46,8 +73,8 @@ pub struct AggregationRequest {
     /// Measure: (Name: String, Action: String) where Name will be looked up in
     /// MeasuresMap of the DataSet
     pub measures: Vec<(String, String)>,
-    /// Which column do you want to Group By?
-    pub groupby: Vec<String>,
+    /// Which column do you want to Group By? Columns can be computed, see [Dimension]
+    pub groupby: Vec<Dimension>,
     /// Filter your data (pre compute),
     /// See AndOrFltrChain
     #[serde(default)]
@@ -62,12 +89,211 @@ pub struct AggregationRequest {
     /// drop rows where all results are NULL or 0
     #[serde(default)]
     pub hide_zeros: bool,
-    /// Show totals
+    /// Show subtotals of every prefix of `groupby`, marked by [GROUPING_ID].
+    /// Prefer [AggregationRequest::grouping_sets]
+    #[serde(default)]
+    pub totals: bool,
+    /// Subtotals (or a grand total) as additional rows, marked by [GROUPING_ID]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grouping_sets: Option<GroupingSets>,
+    /// Spread a dimension across the columns. Replaces `groupby`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pivot: Option<Pivot>,
+    /// Group by the children of a node of a hierarchy, restricted to its descendants.
+    /// Appended to `groupby`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drill: Option<Drill>,
+    /// Measures computed from the other measures of the request, eg `[FX DeltaCharge] / [SA Charge]`.
+    /// See [CalculatedMeasure]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calculated: Vec<CalculatedMeasure>,
+    /// Measures evaluated over partitions of the result, eg rank of desks within a region.
+    /// See [WindowMeasure]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<WindowMeasure>,
+    /// Snapshot (eg COB date) of a DataSet which holds several. The last one if None.
+    /// See [crate::snapshot]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+    /// Another snapshot to compare `as_of` with. Each measure is followed by its value
+    /// in this snapshot and the change since
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compare_to: Option<String>,
+    /// Placeholders (eg `{{cob}}`) used in filters, calc_params, overrides, as_of and compare_to.
+    /// See [AggregationRequest::with_parameters]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<TemplateParameter>,
+}
+
+/// Name of the column which tells which grouping set a row belongs to.
+/// Like SQL GROUPING_ID: bit `n-1-i` is set if `groupby[i]` is rolled up (and hence null).
+/// Rows of the full `groupby` are 0 and the grand total is `2^n - 1`
+pub const GROUPING_ID: &str = "grouping_id";
+
+/// Max len of `groupby` with subtotals, one bit of the [GROUPING_ID] (u32) per column
+const MAX_GROUPING_COLUMNS: usize = u32::BITS as usize;
+
+/// Max len of `groupby` for [GroupingSets::Cube], which produces 2^n sets
+const MAX_CUBE_COLUMNS: usize = 10;
+
+/// Sets of `groupby` columns to aggregate by, in addition to (or instead of) the full `groupby`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", content = "sets", rename_all = "snake_case")]
+pub enum GroupingSets {
+    /// `(a, b, c), (a, b), (a), ()`, like SQL ROLLUP
+    Rollup,
+    /// Every combination of the columns, like SQL CUBE
+    Cube,
+    /// `(a, b, c), ()`
+    GrandTotal,
+    /// Each set is a subset of `groupby`, eg `[["a", "b"], ["c"], []]`
+    Custom(Vec<Vec<String>>),
+}
+
+impl GroupingSets {
+    /// Indices of `groupby` columns of each set
+    fn resolve(&self, groupby: &[String]) -> UltiResult<Vec<Vec<usize>>> {
+        let n = groupby.len();
+        let res = match self {
+            GroupingSets::Rollup => (0..=n).rev().map(|i| (0..i).collect()).collect(),
+            GroupingSets::GrandTotal => vec![(0..n).collect(), vec![]],
+            GroupingSets::Cube => {
+                if n > MAX_CUBE_COLUMNS {
+                    return Err(UltimaErr::InvalidRequest(format!(
+                        "Cube is limited to {MAX_CUBE_COLUMNS} groupby columns, got {n}"
+                    )));
+                }
+                // Rolled up columns are the set bits of the grouping id
+                (0..1_usize << n)
+                    .map(|id| (0..n).filter(|i| id & (1 << (n - 1 - i)) == 0).collect())
+                    .collect()
+            }
+            GroupingSets::Custom(sets) => {
+                let mut res: Vec<Vec<usize>> = vec![];
+                for set in sets {
+                    let mut idx = set
+                        .iter()
+                        .map(|c| {
+                            groupby.iter().position(|g| g == c).ok_or_else(|| {
+                                UltimaErr::InvalidRequest(format!(
+                                    "Grouping set column {c} is not in groupby"
+                                ))
+                            })
+                        })
+                        .collect::<UltiResult<Vec<usize>>>()?;
+                    idx.sort_unstable();
+                    idx.dedup();
+                    if !res.contains(&idx) {
+                        res.push(idx)
+                    }
+                }
+                res
+            }
+        };
+        Ok(res)
+    }
+}
+
+/// Crosstab, eg "RiskClass down, Desk across"
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Pivot {
+    /// Row dimensions
+    #[serde(default)]
+    pub rows: Vec<String>,
+    /// Values of this dimension become the columns. A value which clashes with
+    /// the rows, the "Total" or another value (eg "null" and a null) is prefixed with `column`
+    pub column: String,
+    /// Pivoted measures, eg "Balance_sum". All requested measures if empty
+    #[serde(default)]
+    pub measures: Vec<String>,
+    /// Values listed here come first, in this order. The rest follow in order of appearance
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Add a "Total" column, ie measures aggregated across `column`
     #[serde(default)]
     pub totals: bool,
 }
 
+/// Resolved [AggregationRequest::grouping_sets] or [AggregationRequest::totals]
+#[derive(Debug, Clone)]
+pub(crate) struct GroupingPlan {
+    /// Indices of `groupby` columns of each set
+    pub(crate) sets: Vec<Vec<usize>>,
+}
+
 impl AggregationRequest {
+    /// Groups by the dimensions of the [Pivot], if any.
+    /// Its totals are the grouping set of the rows
+    pub(crate) fn with_pivot_groupby(mut self) -> UltiResult<Self> {
+        let Some(pivot) = &self.pivot else {
+            return Ok(self);
+        };
+        if pivot.rows.contains(&pivot.column) {
+            return Err(UltimaErr::InvalidRequest(format!(
+                "Pivot column {} can't be one of the rows",
+                pivot.column
+            )));
+        }
+        if self.totals || self.grouping_sets.is_some() {
+            return Err(UltimaErr::InvalidRequest(
+                "Pivot can't be combined with totals or grouping_sets".to_string(),
+            ));
+        }
+        let mut names = pivot.rows.clone();
+        names.push(pivot.column.clone());
+        if !self.groupby.is_empty()
+            && (self.groupby.len() != names.len()
+                || self
+                    .groupby
+                    .iter()
+                    .any(|g| !names.iter().any(|n| n == g.name())))
+        {
+            return Err(UltimaErr::InvalidRequest(
+                "Pivot rows and column must match groupby, or leave groupby empty".to_string(),
+            ));
+        }
+        // Computed dimensions are declared in the groupby
+        let groupby = names
+            .iter()
+            .map(|n| {
+                self.groupby
+                    .iter()
+                    .find(|g| g.name() == n)
+                    .cloned()
+                    .unwrap_or_else(|| Dimension::from(n.as_str()))
+            })
+            .collect();
+        if pivot.totals {
+            self.grouping_sets = Some(GroupingSets::Custom(vec![names, pivot.rows.clone()]));
+        }
+        self.groupby = groupby;
+        Ok(self)
+    }
+
+    /// None if no subtotals were requested
+    pub(crate) fn grouping_plan(&self) -> UltiResult<Option<GroupingPlan>> {
+        let n = self.groupby.len();
+        if n > MAX_GROUPING_COLUMNS && (self.grouping_sets.is_some() || self.totals) {
+            return Err(UltimaErr::InvalidRequest(format!(
+                "Subtotals are limited to {MAX_GROUPING_COLUMNS} groupby columns, got {n}"
+            )));
+        }
+        match &self.grouping_sets {
+            Some(gs) => Ok(Some(GroupingPlan {
+                sets: gs.resolve(&self.group_by())?,
+            })),
+            None if self.totals && self.groupby.len() > 1 => Ok(Some(GroupingPlan {
+                sets: (1..=self.groupby.len())
+                    .rev()
+                    .map(|i| (0..i).collect())
+                    .collect(),
+            })),
+            None => Ok(None),
+        }
+    }
+
     pub fn filters(&self) -> &AndOrFltrChain {
         &self.filters
     }
@@ -76,7 +302,13 @@ impl AggregationRequest {
         &self.measures
     }
 
-    pub fn group_by(&self) -> &Vec<String> {
+    /// Names of the groupby columns in the output. See [AggregationRequest::dimensions]
+    pub fn group_by(&self) -> Vec<String> {
+        self.groupby.iter().map(|d| d.name().to_string()).collect()
+    }
+
+    /// Groupby columns, some of which may be computed
+    pub fn dimensions(&self) -> &Vec<Dimension> {
         &self.groupby
     }
 
@@ -108,7 +340,7 @@ pub struct CacheableAggregationRequest {
     /// Measure: (Name, Action) where Name will be looked up in
     /// MeasuresMap of the DataSet
     pub measure: (MeasureName, AggregationName),
-    pub groupby: Vec<String>,
+    pub groupby: Vec<Dimension>,
     #[serde(default)]
     pub filters: AndOrFltrChain,
     #[serde(default)]
@@ -120,6 +352,8 @@ pub struct CacheableAggregationRequest {
     /// TODO potentially to move out
     #[serde(default)]
     pub totals: bool,
+    #[serde(default)]
+    pub grouping_sets: Option<GroupingSets>,
 }
 
 impl From<&AggregationRequest> for Vec<CacheableAggregationRequest> {
@@ -135,13 +369,56 @@ impl From<&AggregationRequest> for Vec<CacheableAggregationRequest> {
                 add_row: item.add_row.clone(),
                 calc_params: item.calc_params.clone(),
                 totals: item.totals,
+                grouping_sets: item.grouping_sets.clone(),
             })
             .collect::<Vec<CacheableAggregationRequest>>()
     }
 }
 
+/// Max [DrillThroughRequest::limit]
+pub const MAX_DRILL_THROUGH_ROWS: usize = 10_000;
+
+fn drill_through_page_size() -> usize {
+    100
+}
+
+/// Rows behind a cell of the result of `request`, eg the trades and risk factors of
+/// "EQ DeltaCharge" for Desk=X, BucketBCBS=5. Rows are filtered, prepared and overridden
+/// exactly as for the computation of the cell
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DrillThroughRequest {
+    /// Request the cell is from. Its measures and groupby are only used to select the rows
+    pub request: AggregationRequest,
+    /// Values (as strings) of the `groupby` of the cell, null for nulls.
+    /// Dimensions which are not here (eg rolled up in a subtotal) are not filtered on
+    #[serde(default)]
+    pub cell: BTreeMap<String, Option<String>>,
+    /// Measure of the cell. Only the rows passing its precomputefilter are returned.
+    /// If None, the rows of any measure of `request`
+    #[serde(default)]
+    pub measure: Option<MeasureName>,
+    /// Columns to return. All (including the ones added by prepare) if empty
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub offset: usize,
+    /// Up to [MAX_DRILL_THROUGH_ROWS]
+    #[serde(default = "drill_through_page_size")]
+    pub limit: usize,
+}
+
+/// A page of the rows of a [DrillThroughRequest]
+#[derive(Debug, Clone)]
+pub struct DrillThroughPage {
+    pub rows: DataFrame,
+    /// Number of rows behind the cell, across all pages
+    pub total: usize,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(tag = "type")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ReportRequest {
     /// Report Name
     pub report_name: String,
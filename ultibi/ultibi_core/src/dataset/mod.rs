@@ -7,14 +7,20 @@ use serde::{ser::SerializeMap, Serialize, Serializer};
 
 use crate::cache::{Cache, CacheableDataSet};
 use crate::errors::{UltiResult, UltimaErr};
-use crate::execute;
-use crate::filters::AndOrFltrChain;
+use crate::execution::{exec_drill_through, execute_many, execute_with_context, ExecutionContext};
+use crate::filters::{fltr_chain, AndOrFltrChain};
+use crate::hierarchy::{resolve_descendants, Hierarchy};
 use crate::reports::report::ReportersMap;
-use crate::{CalcParameter, ComputeRequest, MeasuresMap};
+use crate::snapshot::SNAPSHOT_COLUMN;
+use crate::window::WindowMeasure;
+use crate::{
+    CalcParameter, ComputeRequest, DrillThroughPage, DrillThroughRequest, MeasuresMap, CPM,
+};
 use once_cell::sync::Lazy;
 
 use crate::datasource::DataSource;
 pub static EMPTY_REPORTS_MAP: Lazy<ReportersMap> = Lazy::new(Default::default);
+pub static EMPTY_BUILD_PARAMS: Lazy<CPM> = Lazy::new(Default::default);
 
 /// This is the default struct which implements Dataset
 /// Usually a client/user would overwrite it with their own DataSet
@@ -31,6 +37,12 @@ pub struct DataSetBase {
     pub config: BTreeMap<String, String>,
     /// Cache
     pub cache: Cache,
+    /// See [DataSet::hierarchies]
+    pub hierarchies: Vec<Hierarchy>,
+    /// See [DataSet::windows]
+    pub windows: Vec<WindowMeasure>,
+    /// See [DataSet::set_snapshots]. Scanned on each request if None
+    pub snapshots: Option<Vec<String>>,
 }
 
 /// The main Trait
@@ -65,7 +77,53 @@ pub trait DataSet: Send + Sync {
     /// * `streaming` - See polars streaming. Use when your LazyFrame is a Scan if you don't want to load whole frame
     /// into memory. See: https://www.rhosignal.com/posts/polars-dont-fear-streaming/
     fn compute(&self, r: ComputeRequest) -> UltiResult<DataFrame> {
-        execute(self, r, self.get_datasource().prepare_on_each_request())
+        self.compute_with_context(r, &ExecutionContext::default())
+    }
+
+    /// Same as [DataSet::compute], but the computation can be cancelled or timed out
+    /// via `ctx`. See [ExecutionContext]
+    fn compute_with_context(
+        &self,
+        r: ComputeRequest,
+        ctx: &ExecutionContext,
+    ) -> UltiResult<DataFrame> {
+        execute_with_context(
+            self,
+            r,
+            self.get_datasource().prepare_on_each_request(),
+            ctx,
+        )
+    }
+
+    /// Computes several requests at once (eg all the widgets of a dashboard).
+    /// Requests with the same filters, overrides and additional rows share the work up to the
+    /// GroupBy, which then runs in parallel. Results are in the order of `rs`
+    fn compute_many(
+        &self,
+        rs: Vec<ComputeRequest>,
+        ctx: &ExecutionContext,
+    ) -> Vec<UltiResult<DataFrame>> {
+        execute_many(
+            self,
+            rs,
+            self.get_datasource().prepare_on_each_request(),
+            ctx,
+        )
+    }
+
+    /// Rows behind a cell of the result of a request, a page at a time.
+    /// See [DrillThroughRequest]
+    fn drill_through(
+        &self,
+        r: DrillThroughRequest,
+        ctx: &ExecutionContext,
+    ) -> UltiResult<DrillThroughPage> {
+        exec_drill_through(
+            self,
+            r,
+            self.get_datasource().prepare_on_each_request(),
+            ctx,
+        )
     }
 
     /// Get a column. Potentially this will be removed in favour of get_columns
@@ -75,11 +133,33 @@ pub trait DataSet: Send + Sync {
         self.get_datasource().get_column(col_name)
     }
 
+    /// Unique values of a column among the rows satisfying `filters`
+    /// Used to restrict column search to what a user is entitled to see
+    fn get_column_filtered(&self, col_name: &str, filters: &AndOrFltrChain) -> UltiResult<Series> {
+        if filters.is_empty() {
+            return self.get_column(col_name);
+        }
+        let mut filters = filters.clone();
+        resolve_descendants(&mut filters, self.hierarchies())?;
+        self.get_lazyframe(&filters)?
+            .select([col(col_name).unique_stable()])
+            .collect()?
+            .pop() //above select guaranteed one column
+            .ok_or(UltimaErr::UnknownColumn {
+                column: col_name.to_string(),
+            })
+    }
+
     /// Get all Reporters associated with the DataSet
     fn get_reporters(&self) -> &ReportersMap {
         &EMPTY_REPORTS_MAP
     }
 
+    /// Parameters the DataSet was built with (see [crate::DataSourceConfig])
+    fn build_params(&self) -> &CPM {
+        &EMPTY_BUILD_PARAMS
+    }
+
     /// Modify lf in place - applicable only to InMemory DataSet
     /// Common use case - prepare, and then set_inplace
     fn set_lazyframe_inplace(&mut self, _: LazyFrame) -> UltiResult<()> {
@@ -125,6 +205,156 @@ pub trait DataSet: Send + Sync {
         Ok(lf)
     }
 
+    /// Appends rows (eg intraday trades) to an InMemory DataSet, which has been prepared.
+    /// `df` has the columns of the raw data: it is validated (see [DataSet::validate_frame])
+    /// and prepared (see [DataSet::prepare_frame]) before being merged. Cleans the cache.
+    /// Attributes and hierarchies are not joined again (see `files_join_attributes` and
+    /// `attributes_join_hierarchy` of the config), so `df` must already carry their columns.
+    /// Returns the number of rows appended
+    /// Will return an error if [DataSet::set_lazyframe_inplace] is not implemented
+    fn append_frame(&mut self, df: DataFrame) -> UltiResult<usize> {
+        let DataSource::InMemory(current) = self.get_datasource() else {
+            return Err(UltimaErr::Other(
+                "Can only append to an In Memory DataSet".to_string(),
+            ));
+        };
+        let lf = df.lazy();
+        self.validate_frame(Some(&lf), 0)?;
+        // Same columns and types as the current data, including the joined ones.
+        // A missing column or a value which doesn't cast is rejected
+        let appended = self
+            .prepare_frame(lf)?
+            .select(
+                current
+                    .get_columns()
+                    .iter()
+                    .map(|s| col(s.name()).strict_cast(s.dtype().clone()))
+                    .collect::<Vec<_>>(),
+            )
+            .collect()
+            .map_err(|e| {
+                UltimaErr::InvalidRequest(format!("Rows don't match the columns of the data: {e}"))
+            })?;
+        let mut merged = current.clone();
+        merged.vstack_mut(&appended)?;
+        // Each append would otherwise add a chunk
+        merged.as_single_chunk_par();
+        self.set_lazyframe_inplace(merged.lazy())?;
+        self.refresh_snapshots()?;
+        if let Some(cacheable) = self.as_cacheable() {
+            cacheable.clean_cache()
+        }
+        Ok(appended.height())
+    }
+
+    /// Deletes the rows of an InMemory DataSet which satisfy `filters` (eg amended trades).
+    /// Cleans the cache. Returns the number of rows deleted
+    /// Will return an error if [DataSet::set_lazyframe_inplace] is not implemented
+    fn delete_where(&mut self, filters: &AndOrFltrChain) -> UltiResult<usize> {
+        let DataSource::InMemory(current) = self.get_datasource() else {
+            return Err(UltimaErr::Other(
+                "Can only delete from an In Memory DataSet".to_string(),
+            ));
+        };
+        let mut filters = filters.clone();
+        resolve_descendants(&mut filters, self.hierarchies())?;
+        let Some(predicate) = fltr_chain(&filters) else {
+            return Err(UltimaErr::InvalidRequest(
+                "Filters are required. Rows to delete must be selected explicitly".to_string(),
+            ));
+        };
+        let before = current.height();
+        // Rows where the predicate is null are not selected, hence kept
+        let kept = current
+            .clone()
+            .lazy()
+            .filter(predicate.not().fill_null(lit(true)))
+            .collect()?;
+        let deleted = before - kept.height();
+        self.set_lazyframe_inplace(kept.lazy())?;
+        self.refresh_snapshots()?;
+        if let Some(cacheable) = self.as_cacheable() {
+            cacheable.clean_cache()
+        }
+        Ok(deleted)
+    }
+
+    /// Hierarchies of the fields, used by [crate::filters::FilterE::Descendants] and
+    /// [crate::AggregationRequest::drill]
+    fn hierarchies(&self) -> &[Hierarchy] {
+        &[]
+    }
+
+    /// Usually called once, with the hierarchies of the [crate::DataSourceConfig]
+    fn set_hierarchies(&mut self, _: Vec<Hierarchy>) -> UltiResult<()> {
+        Err(UltimaErr::Other(
+            "set_hierarchies is Not implemented for your Data Set".to_string(),
+        ))
+    }
+
+    /// Window measures which can be requested by name, like the measures of [DataSet::get_measures].
+    /// See [crate::window]
+    fn windows(&self) -> &[WindowMeasure] {
+        &[]
+    }
+
+    /// Usually called once, with the windows of the [crate::DataSourceConfig]
+    fn set_windows(&mut self, _: Vec<WindowMeasure>) -> UltiResult<()> {
+        Err(UltimaErr::Other(
+            "set_windows is Not implemented for your Data Set".to_string(),
+        ))
+    }
+
+    /// Column which tells apart the dated snapshots of the data, if it holds several.
+    /// Named by the `snapshot_column` build param. See [crate::snapshot]
+    fn snapshot_column(&self) -> Option<&str> {
+        self.build_params().get(SNAPSHOT_COLUMN).map(|c| c.as_str())
+    }
+
+    /// Snapshots in the order they were loaded (or appended). The last one is the default
+    /// [crate::AggregationRequest::as_of]. Empty if there is no [DataSet::snapshot_column]
+    /// Default implementation scans the data on each call, see [DataSet::set_snapshots]
+    fn snapshots(&self) -> UltiResult<Vec<String>> {
+        self.scan_snapshots()
+    }
+
+    /// Keeps the list of [DataSet::snapshots], so that the data is not scanned on each request.
+    /// Called at load (see [crate::new::NewSourcedDataSet]) and when rows are appended or deleted
+    fn set_snapshots(&mut self, _: Vec<String>) -> UltiResult<()> {
+        // Nothing to keep, snapshots are scanned
+        Ok(())
+    }
+
+    /// Distinct values of the [DataSet::snapshot_column], in order of appearance
+    fn scan_snapshots(&self) -> UltiResult<Vec<String>> {
+        let Some(column) = self.snapshot_column() else {
+            return Ok(vec![]);
+        };
+        let dates = self
+            .get_lazyframe(&vec![])?
+            .select([col(column).unique_stable().cast(DataType::String)])
+            .collect()?
+            .pop() //above select guaranteed one column
+            .ok_or(UltimaErr::UnknownColumn {
+                column: column.to_string(),
+            })?;
+        Ok(dates
+            .str()?
+            .into_iter()
+            .flatten()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Scans the snapshots again, see [DataSet::set_snapshots]
+    fn refresh_snapshots(&mut self) -> UltiResult<()> {
+        if self.snapshot_column().is_none() {
+            return Ok(());
+        }
+        let snapshots = self.scan_snapshots()?;
+        self.set_snapshots(snapshots)
+    }
+
     /// Calc params are used for the UI and hence are totally optional
     fn calc_params(&self) -> Vec<CalcParameter> {
         let mut res = vec![];
@@ -138,6 +368,12 @@ pub trait DataSet: Send + Sync {
         hash_res.into_iter().collect()
     }
 
+    /// If true, requests with calc params which are not in [DataSet::calc_params] are rejected.
+    /// Values of the declared params are validated regardless, see [crate::validate_calc_params]
+    fn strict_calc_params(&self) -> bool {
+        false
+    }
+
     /// Limits overridable columns which you can override in
     /// See [AggregationRequest::overrides]
     /// Good usecase: add prepared
@@ -184,6 +420,40 @@ impl DataSet for DataSetBase {
         &self.measures
     }
 
+    fn build_params(&self) -> &CPM {
+        &self.config
+    }
+
+    fn hierarchies(&self) -> &[Hierarchy] {
+        &self.hierarchies
+    }
+
+    fn set_hierarchies(&mut self, hierarchies: Vec<Hierarchy>) -> UltiResult<()> {
+        self.hierarchies = hierarchies;
+        Ok(())
+    }
+
+    fn windows(&self) -> &[WindowMeasure] {
+        &self.windows
+    }
+
+    fn set_windows(&mut self, windows: Vec<WindowMeasure>) -> UltiResult<()> {
+        self.windows = windows;
+        Ok(())
+    }
+
+    fn snapshots(&self) -> UltiResult<Vec<String>> {
+        match &self.snapshots {
+            Some(snapshots) => Ok(snapshots.clone()),
+            None => self.scan_snapshots(),
+        }
+    }
+
+    fn set_snapshots(&mut self, snapshots: Vec<String>) -> UltiResult<()> {
+        self.snapshots = Some(snapshots);
+        Ok(())
+    }
+
     //    /// Validate Dataset contains columns
     //    /// files_join_attributes and attributes_join_hierarchy
     //    /// numeric_cols and TODO dimensions(groups and filters)
@@ -207,7 +477,7 @@ pub fn numeric_columns(schema: Arc<Schema>) -> Vec<String> {
         .collect::<Vec<String>>()
 }
 
-/// restrict columns which can be fields to Utf8 and Bool
+/// restrict columns which can be fields (ie groupby) to Utf8, Bool, integers and dates
 pub fn fields_columns(schema: Arc<Schema>) -> Vec<String> {
     schema
         .iter_fields()
@@ -215,6 +485,9 @@ pub fn fields_columns(schema: Arc<Schema>) -> Vec<String> {
             matches!(
                 field.data_type(),
                 DataType::String
+                    | DataType::Boolean
+                    | DataType::Date
+                    | DataType::Datetime(_, _)
                     | DataType::UInt8
                     | DataType::Int8
                     | DataType::UInt16
@@ -250,21 +523,34 @@ impl Serialize for dyn DataSet {
         S: Serializer,
     {
         // For measures we are only interested in their agg method
+        // Windows are requested like scalar measures
+        let scalar = Some("scalar".to_string());
         let measures = self
             .get_measures()
             .iter()
             .map(|(x, m)| (x, m.aggregation()))
+            .chain(self.windows().iter().map(|w| (&w.name, &scalar)))
             .collect::<BTreeMap<&String, &Option<String>>>();
         let ordered_measures: BTreeMap<_, _> = measures.iter().collect();
+        let measures_metadata = self
+            .get_measures()
+            .iter()
+            .map(|(x, m)| (x, m.metadata()))
+            .collect::<BTreeMap<_, _>>();
 
         let utf8_cols = self.get_schema().map(fields_columns).unwrap_or_default();
         let calc_params = self.calc_params();
+        let snapshots = self.snapshots().unwrap_or_default();
 
-        let mut seq = serializer.serialize_map(Some(4))?;
+        let mut seq = serializer.serialize_map(Some(7))?;
 
         seq.serialize_entry("fields", &utf8_cols)?;
         seq.serialize_entry("measures", &ordered_measures)?;
+        seq.serialize_entry("measures_metadata", &measures_metadata)?;
         seq.serialize_entry("calc_params", &calc_params)?;
+        seq.serialize_entry("hierarchies", self.hierarchies())?;
+        seq.serialize_entry("windows", self.windows())?;
+        seq.serialize_entry("snapshots", &snapshots)?;
         seq.end()
     }
 }
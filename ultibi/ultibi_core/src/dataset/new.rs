@@ -1,6 +1,9 @@
 use crate::{
     datasource::DataSource,
-    derive_basic_measures_vec, numeric_columns,
+    derive_basic_measures_vec,
+    errors::UltiResult,
+    hierarchy::Hierarchy,
+    numeric_columns,
     reports::report::{Reporter, ReportersMap},
     DataSet, DataSetBase, DataSourceConfig, Measure, MeasuresMap, CPM,
 };
@@ -42,16 +45,38 @@ pub trait NewSourcedDataSet: DataSet {
 
         let mm: MeasuresMap = MeasuresMap::from_iter(ms);
         let rm: ReportersMap = ReportersMap::from_iter(rm);
-        Self::new(source, mm, rm, params)
+        let mut res = Self::new(source, mm, rm, params);
+        res.refresh_snapshots()
+            .expect("Failed to load the snapshots");
+        res
     }
 
     fn from_config(conf: DataSourceConfig) -> Self
     where
         Self: Sized,
     {
+        let hierarchies = conf
+            .hierarchies()
+            .iter()
+            .cloned()
+            .map(Hierarchy::load)
+            .collect::<UltiResult<Vec<_>>>()
+            .expect("Failed to load hierarchies"); // <- Ok to panic upon server startup
+        let windows = conf.windows().to_vec();
         let (frame, measure_cols, bp) = conf.build();
         let mm: MeasuresMap = MeasuresMap::from_iter(measure_cols);
-        Self::new(frame, mm, Default::default(), bp)
+        let mut res = Self::new(frame, mm, Default::default(), bp);
+        if !hierarchies.is_empty() {
+            res.set_hierarchies(hierarchies)
+                .expect("DataSet with hierarchies must implement set_hierarchies");
+        }
+        if !windows.is_empty() {
+            res.set_windows(windows)
+                .expect("DataSet with windows must implement set_windows");
+        }
+        res.refresh_snapshots()
+            .expect("Failed to load the snapshots"); // <- Ok to panic upon server startup
+        res
     }
 }
 
@@ -5,7 +5,7 @@ use super::DataSource;
 use crate::errors::UltiResult;
 use crate::{
     errors::UltimaErr,
-    filters::{AndOrFltrChain, FilterE},
+    filters::{unresolved_msg, AndOrFltrChain, FilterE},
 };
 use connectorx::prelude::get_arrow;
 use connectorx::{source_router::SourceConn, sql::CXQuery};
@@ -52,16 +52,16 @@ pub fn sql_get_column(db: &DbInfo, col_name: &str) -> UltiResult<Series> {
 
 pub fn sql_query(db: &DbInfo, query: &str) -> UltiResult<DataFrame> {
     let source_conn = SourceConn::try_from(db.conn_uri.as_str())
-        .map_err(|err| UltimaErr::Other(err.to_string()))?;
+        .map_err(|err| UltimaErr::DataSource(err.to_string()))?;
 
     let queries = &[CXQuery::from(query)];
 
-    let destination =
-        get_arrow(&source_conn, None, queries).map_err(|err| UltimaErr::Other(err.to_string()))?;
+    let destination = get_arrow(&source_conn, None, queries)
+        .map_err(|err| UltimaErr::DataSource(err.to_string()))?;
 
     let data = destination
         .arrow()
-        .map_err(|err| UltimaErr::Other(err.to_string()))?;
+        .map_err(|err| UltimaErr::DataSource(err.to_string()))?;
 
     let df = record_batches_to_df(data)?;
 
@@ -79,7 +79,7 @@ pub fn sql_query(db: &DbInfo, query: &str) -> UltiResult<DataFrame> {
         .map_err(UltimaErr::Polars)
 }
 
-pub fn fltr_chain_to_sql_query(table: &str, chain: &AndOrFltrChain) -> String {
+pub fn fltr_chain_to_sql_query(table: &str, chain: &AndOrFltrChain) -> UltiResult<String> {
     let mut base = format!("SELECT * FROM {}", table);
 
     let mut outer = vec![];
@@ -92,7 +92,10 @@ pub fn fltr_chain_to_sql_query(table: &str, chain: &AndOrFltrChain) -> String {
 
         // To track if we need to prefix OR
         // First iteration is not OR
-        let inner_fltrs_sql: Vec<String> = inner_or_filters.iter().map(fltr_to_sql_query).collect();
+        let inner_fltrs_sql = inner_or_filters
+            .iter()
+            .map(fltr_to_sql_query)
+            .collect::<UltiResult<Vec<String>>>()?;
         let inner_fltrs_sql_joined = inner_fltrs_sql.join(" OR ");
 
         outer.push(format!("({})", inner_fltrs_sql_joined));
@@ -105,12 +108,14 @@ pub fn fltr_chain_to_sql_query(table: &str, chain: &AndOrFltrChain) -> String {
         base.push_str(&outer_joined);
     }
 
-    base
+    Ok(base)
 }
 
+/// Errors on a [FilterE::Descendants], which must be resolved with
+/// [crate::hierarchy::resolve_descendants] first
 #[cfg(feature = "db")]
-pub fn fltr_to_sql_query(fltr: &FilterE) -> String {
-    match fltr {
+pub fn fltr_to_sql_query(fltr: &FilterE) -> UltiResult<String> {
+    let sql = match fltr {
         FilterE::Eq { field, value } => match value {
             Some(v) => format!("({field} = '{v}')"),
             None => format!("({field} IS NULL)"),
@@ -121,7 +126,11 @@ pub fn fltr_to_sql_query(fltr: &FilterE) -> String {
         },
         FilterE::In { field, value } => format!("({})", vec_to_or_sql(field, value, false)),
         FilterE::NotIn { field, value } => format!("({})", vec_to_or_sql(field, value, true)),
-    }
+        FilterE::Descendants { hierarchy, .. } => {
+            return Err(UltimaErr::InvalidRequest(unresolved_msg(hierarchy)))
+        }
+    };
+    Ok(sql)
 }
 
 /// SELECT * FROM delta
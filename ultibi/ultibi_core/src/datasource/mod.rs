@@ -13,7 +13,7 @@ use polars::{
 
 use crate::{
     errors::{UltiResult, UltimaErr},
-    filters::{fltr_chain, AndOrFltrChain},
+    filters::{check_resolved, fltr_chain, AndOrFltrChain},
 };
 
 use polars::prelude::{DataFrame, IntoLazy};
@@ -67,6 +67,7 @@ impl Default for DataSource {
 
 impl DataSource {
     pub fn get_lazyframe(&self, filters: &AndOrFltrChain) -> UltiResult<LazyFrame> {
+        check_resolved(filters)?;
         let filter = fltr_chain(filters);
         match self {
             DataSource::InMemory(df) => {
@@ -86,7 +87,7 @@ impl DataSource {
             // TODO do not unwrap
             #[cfg(feature = "db")]
             DataSource::Db(db) => {
-                Ok(sql_query(db, &fltr_chain_to_sql_query(&db.table, filters))?.lazy())
+                Ok(sql_query(db, &fltr_chain_to_sql_query(&db.table, filters)?)?.lazy())
             }
         }
     }
@@ -115,9 +116,9 @@ impl DataSource {
         match self {
             DataSource::InMemory(df) => {
                 let mut df = df.select([col_name])?;
-                let srs = df
-                    .pop()
-                    .ok_or(UltimaErr::Other(format!("Column {col_name} doesn't exist")))?;
+                let srs = df.pop().ok_or(UltimaErr::UnknownColumn {
+                    column: col_name.to_string(),
+                })?;
                 Ok(srs.unique_stable()?)
             }
             DataSource::Scan(lf) => {
@@ -125,7 +126,9 @@ impl DataSource {
                     .select([col(col_name)])
                     .collect()?
                     .pop() //above select guaranteed one column
-                    .ok_or(UltimaErr::Other(format!("Column {col_name} doesn't exist")))
+                    .ok_or(UltimaErr::UnknownColumn {
+                        column: col_name.to_string(),
+                    })
             }
             #[cfg(feature = "db")]
             DataSource::Db(db) => sql_get_column(db, col_name),
@@ -0,0 +1,212 @@
+//! Groupby dimensions which are not columns of the DataSet as they are,
+//! eg month of COB or buckets of GrossJTD
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{UltiResult, UltimaErr};
+
+/// Element of the groupby: a column, or a dimension computed from a column.
+/// Computed dimensions are evaluated right before the GroupBy
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Dimension {
+    Column(String),
+    Computed(ComputedDimension),
+}
+
+impl Dimension {
+    /// Name of the column in the output
+    pub fn name(&self) -> &str {
+        match self {
+            Dimension::Column(c) => c,
+            Dimension::Computed(cd) => &cd.name,
+        }
+    }
+
+    /// None if the dimension is a column as it is
+    pub fn to_expr(&self) -> UltiResult<Option<Expr>> {
+        match self {
+            Dimension::Column(_) => Ok(None),
+            Dimension::Computed(cd) => cd.to_expr().map(Some),
+        }
+    }
+}
+
+impl AsRef<str> for Dimension {
+    fn as_ref(&self) -> &str {
+        self.name()
+    }
+}
+
+impl From<String> for Dimension {
+    fn from(c: String) -> Self {
+        Dimension::Column(c)
+    }
+}
+
+impl From<&str> for Dimension {
+    fn from(c: &str) -> Self {
+        Dimension::Column(c.to_string())
+    }
+}
+
+/// eg `{"name": "MaturityYear", "column": "MaturityDate", "type": "date_trunc", "every": "1y"}`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ComputedDimension {
+    /// Name of the new column. Must not clash with the columns of the DataSet
+    pub name: String,
+    /// Column it's computed from
+    pub column: String,
+    #[serde(flatten)]
+    pub expr: DimensionExpr,
+}
+
+impl ComputedDimension {
+    pub fn to_expr(&self) -> UltiResult<Expr> {
+        let c = col(&self.column);
+        let expr = match &self.expr {
+            DimensionExpr::DateTrunc { every } => {
+                c.dt().truncate(lit(every.as_str()), String::new())
+            }
+            DimensionExpr::Bins { edges, labels } => bins(c, &edges.0, labels)?,
+            DimensionExpr::Substring { offset, length } => c
+                .cast(DataType::String)
+                .str()
+                .slice(lit(*offset), length.map(lit).unwrap_or_else(|| lit(NULL))),
+            DimensionExpr::Extract { pattern, group } => c
+                .cast(DataType::String)
+                .str()
+                .extract(lit(pattern.as_str()), *group),
+            DimensionExpr::Map { mapping, default } => {
+                let c = c.cast(DataType::String);
+                let otherwise = match default {
+                    Some(d) => lit(d.as_str()),
+                    None => c.clone(),
+                };
+                mapping.iter().rev().fold(otherwise, |res, (k, v)| {
+                    when(c.clone().eq(lit(k.as_str())))
+                        .then(lit(v.as_str()))
+                        .otherwise(res)
+                })
+            }
+            DimensionExpr::Groups { groups, default } => {
+                let c = c.cast(DataType::String);
+                let otherwise = match default {
+                    Some(d) => lit(d.as_str()),
+                    None => c.clone(),
+                };
+                groups
+                    .iter()
+                    .rev()
+                    .fold(otherwise, |res, (group, members)| {
+                        let members = Series::new("members", members);
+                        when(c.clone().is_in(members.lit()))
+                            .then(lit(group.as_str()))
+                            .otherwise(res)
+                    })
+            }
+        };
+        Ok(expr.alias(&self.name))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum DimensionExpr {
+    /// Start of the period of a date, eg "1mo" (month of COB) or "1y" (maturity year).
+    /// See polars duration strings
+    DateTrunc { every: String },
+    /// Numbers into left closed bins. Edges `[1e6, 1e7]` give bins
+    /// "<1000000", "1000000-10000000" and ">=10000000", unless `labels` are provided
+    Bins {
+        edges: BinEdges,
+        /// One more than the edges
+        #[serde(default)]
+        labels: Vec<String>,
+    },
+    /// `length` characters (till the end if None) from `offset`
+    Substring { offset: i64, length: Option<u64> },
+    /// Capture `group` (0 is the whole match) of the regex `pattern`. Null if no match
+    Extract {
+        pattern: String,
+        #[serde(default)]
+        group: usize,
+    },
+    /// Values missing from `mapping` become `default`, or are kept as they are
+    Map {
+        mapping: BTreeMap<String, String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+    /// Like [DimensionExpr::Map], but each group lists its values,
+    /// eg the descendants of each child of a node of a [crate::hierarchy::Hierarchy]
+    Groups {
+        groups: BTreeMap<String, Vec<String>>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+/// Increasing edges of [DimensionExpr::Bins]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BinEdges(pub Vec<f64>);
+
+// Needed for the cache key. Edges are never NaN, see [bins]
+impl Eq for BinEdges {}
+
+impl Hash for BinEdges {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for e in &self.0 {
+            e.to_bits().hash(state)
+        }
+    }
+}
+
+fn bins(c: Expr, edges: &[f64], labels: &[String]) -> UltiResult<Expr> {
+    if edges.is_empty()
+        || edges.iter().any(|e| e.is_nan())
+        || edges.windows(2).any(|w| w[0] >= w[1])
+    {
+        return Err(UltimaErr::InvalidRequest(format!(
+            "Bin edges must be increasing, got {edges:?}"
+        )));
+    }
+    let labels = if labels.is_empty() {
+        let mut res = vec![format!("<{}", edges[0])];
+        res.extend(edges.windows(2).map(|w| format!("{}-{}", w[0], w[1])));
+        res.push(format!(">={}", edges[edges.len() - 1]));
+        res
+    } else if labels.len() == edges.len() + 1 {
+        labels.to_vec()
+    } else {
+        return Err(UltimaErr::InvalidRequest(format!(
+            "Expected {} bin labels, got {}",
+            edges.len() + 1,
+            labels.len()
+        )));
+    };
+
+    let c = c.cast(DataType::Float64);
+    let above = lit(labels[edges.len()].as_str());
+    let binned = edges
+        .iter()
+        .zip(&labels)
+        .rev()
+        .fold(above, |res, (edge, label)| {
+            when(c.clone().lt(lit(*edge)))
+                .then(lit(label.as_str()))
+                .otherwise(res)
+        });
+    Ok(when(c.is_null())
+        .then(lit(NULL).cast(DataType::String))
+        .otherwise(binned))
+}
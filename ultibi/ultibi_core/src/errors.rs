@@ -1,5 +1,6 @@
 use polars::prelude::PolarsError;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 use thiserror::Error;
 
 pub type UltiResult<T> = Result<T, UltimaErr>;
@@ -10,10 +11,107 @@ pub enum UltimaErr {
     Polars(#[from] PolarsError),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
+    #[error("Request was cancelled during {0}")]
+    Cancelled(String),
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("No measure {measure} exists for the dataset. Availiable measures are: {available:?}")]
+    UnknownMeasure {
+        measure: String,
+        available: Vec<String>,
+    },
+    #[error("No action {aggregation} supported. Supported actions are: {available:?}")]
+    UnknownAggregation {
+        aggregation: String,
+        available: Vec<String>,
+    },
+    #[error("Measure {measure} supports only {supported} aggregation, but {requested} requested")]
+    UnsupportedAggregation {
+        measure: String,
+        supported: String,
+        requested: String,
+    },
+    #[error("Column {column} doesn't exist")]
+    UnknownColumn { column: String },
+    #[error("No calc param {param} exists for the dataset")]
+    UnknownCalcParam { param: String },
+    #[error("Invalid value of calc param {param}: {reason}")]
+    InvalidCalcParam { param: String, reason: String },
+    #[error("Can't override {field}: {reason}")]
+    InvalidOverride { field: String, reason: String },
+    /// Parameter of a template, see [crate::parameters]
+    #[error("{reason}")]
+    InvalidParameter { name: String, reason: String },
+    /// Request is malformed, eg has no measures
+    #[error("{0}")]
+    InvalidRequest(String),
+    /// Failed to read from the source of the data, eg a database
+    #[error("Data source error: {0}")]
+    DataSource(String),
     #[error("{0}")]
     Other(String),
 }
 
+impl UltimaErr {
+    /// Stable identifier of the kind of the error, eg for clients to match on
+    pub fn code(&self) -> &'static str {
+        use UltimaErr::*;
+        match self {
+            Polars(PolarsError::ColumnNotFound(_)) => "unknown_column",
+            Polars(_) => "compute_error",
+            SerdeJson(_) => "invalid_json",
+            Cancelled(_) => "cancelled",
+            Timeout(_) => "timeout",
+            UnknownMeasure { .. } => "unknown_measure",
+            UnknownAggregation { .. } => "unknown_aggregation",
+            UnsupportedAggregation { .. } => "unsupported_aggregation",
+            UnknownColumn { .. } => "unknown_column",
+            UnknownCalcParam { .. } => "unknown_calc_param",
+            InvalidCalcParam { .. } => "invalid_calc_param",
+            InvalidOverride { .. } => "invalid_override",
+            InvalidParameter { .. } => "invalid_parameter",
+            InvalidRequest(_) => "invalid_request",
+            DataSource(_) => "data_source_error",
+            Other(_) => "other",
+        }
+    }
+
+    /// The offending measure, aggregation, column, calc param, override or parameter, if known
+    pub fn field(&self) -> Option<&str> {
+        use UltimaErr::*;
+        match self {
+            UnknownMeasure { measure, .. } | UnsupportedAggregation { measure, .. } => {
+                Some(measure)
+            }
+            UnknownAggregation { aggregation, .. } => Some(aggregation),
+            UnknownColumn { column } => Some(column),
+            UnknownCalcParam { param } | InvalidCalcParam { param, .. } => Some(param),
+            InvalidOverride { field, .. } => Some(field),
+            InvalidParameter { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// The error is caused by the request (rather than the data or the server)
+    pub fn is_client_error(&self) -> bool {
+        use UltimaErr::*;
+        matches!(
+            self,
+            Polars(PolarsError::ColumnNotFound(_))
+                | SerdeJson(_)
+                | UnknownMeasure { .. }
+                | UnknownAggregation { .. }
+                | UnsupportedAggregation { .. }
+                | UnknownColumn { .. }
+                | UnknownCalcParam { .. }
+                | InvalidCalcParam { .. }
+                | InvalidOverride { .. }
+                | InvalidParameter { .. }
+                | InvalidRequest(_)
+        )
+    }
+}
+
 impl Debug for UltimaErr {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         use UltimaErr::*;
@@ -21,6 +119,7 @@ impl Debug for UltimaErr {
             Polars(err) => write!(f, "{err}"),
             SerdeJson(err) => write!(f, "Couldn't serialize string. Check format. {err}"),
             Other(err) => write!(f, "BindingsError: {err}"),
+            err => write!(f, "{err}"),
         }
     }
 }
@@ -0,0 +1,145 @@
+//! State shared between the caller of a computation and [exec_agg](crate::exec_agg)
+//! while the computation is running
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::errors::{UltiResult, UltimaErr};
+
+/// Cooperative cancellation, timeouts and per stage timings.
+///
+/// Polars can't interrupt a running query, hence the flag is checked
+/// between the stages of the execution (see [ExecutionContext::checkpoint]).
+/// Clones share the same flag (and timings), so a clone can be handed over to the
+/// computing thread while the original is kept to cancel it or to read the timings.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionContext {
+    cancelled: Arc<AtomicBool>,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    timings: Arc<Mutex<StageTimings>>,
+    cache_hits: Arc<AtomicU64>,
+    cache_misses: Arc<AtomicU64>,
+    truncated: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Default)]
+struct StageTimings {
+    started: Option<Instant>,
+    last: Option<Instant>,
+    stages: Vec<(String, Duration)>,
+}
+
+impl ExecutionContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Computation will fail with [UltimaErr::Timeout] at the first
+    /// checkpoint after `timeout` has elapsed (counting from now)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Request cancellation. Computation stops at the next checkpoint
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Stages are timed from here. Called at the start of the execution
+    pub fn start_timer(&self) {
+        let mut timings = self.timings.lock().expect("Poisonned Mutex");
+        let now = Instant::now();
+        timings.started = Some(now);
+        timings.last = Some(now);
+    }
+
+    /// Wall time since [ExecutionContext::start_timer], if started
+    pub fn elapsed(&self) -> Option<Duration> {
+        let timings = self.timings.lock().expect("Poisonned Mutex");
+        timings.started.map(|started| started.elapsed())
+    }
+
+    /// Time spent in each stage, in the order of execution.
+    /// Stages which run several times (eg totals) are summed up.
+    ///
+    /// Note: the lazy stages (filter, prepare, overrides and add_row) only build the query plan,
+    /// hence they time the planning. The plan runs in the stages which collect (eg groupby),
+    /// see [ExecutionContext::elapsed] for the time of the whole computation
+    pub fn timings(&self) -> Vec<(String, Duration)> {
+        self.timings.lock().expect("Poisonned Mutex").stages.clone()
+    }
+
+    pub fn record_cache_lookup(&self, hit: bool) {
+        let counter = if hit {
+            &self.cache_hits
+        } else {
+            &self.cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// (hits, misses)
+    pub fn cache_lookups(&self) -> (u64, u64) {
+        (
+            self.cache_hits.load(Ordering::Relaxed),
+            self.cache_misses.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Called when rows of the result are dropped,
+    /// see [MAX_GROUPS](crate::execution::execute_agg::MAX_GROUPS)
+    pub fn record_truncation(&self) {
+        self.truncated.store(true, Ordering::Relaxed)
+    }
+
+    /// Whether the result lacks rows (of any of its grouping sets)
+    pub fn is_truncated(&self) -> bool {
+        self.truncated.load(Ordering::Relaxed)
+    }
+
+    /// Called at the end of each stage of the execution. Records the time spent in
+    /// the stage (since the previous checkpoint).
+    /// Returns an error if the computation was cancelled or timed out
+    pub fn checkpoint(&self, stage: &str) -> UltiResult<()> {
+        self.record_stage(stage);
+        if self.is_cancelled() {
+            return Err(UltimaErr::Cancelled(stage.to_string()));
+        }
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.timeout) {
+            if Instant::now() > deadline {
+                return Err(UltimaErr::Timeout(timeout));
+            }
+        }
+        Ok(())
+    }
+
+    fn record_stage(&self, stage: &str) {
+        let mut timings = self.timings.lock().expect("Poisonned Mutex");
+        let now = Instant::now();
+        let Some(last) = timings.last.replace(now) else {
+            // Timer was not started
+            return;
+        };
+        let elapsed = now - last;
+        match timings.stages.iter_mut().find(|(s, _)| s == stage) {
+            Some((_, d)) => *d += elapsed,
+            None => timings.stages.push((stage.to_string(), elapsed)),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Underlying rows of a cell of an aggregation
+
+use polars::prelude::{col, len, Expr, IdxSize};
+
+use crate::{
+    datarequest::MAX_DRILL_THROUGH_ROWS,
+    errors::{UltiResult, UltimaErr},
+    execution::{
+        context::ExecutionContext,
+        execute_agg::{_prepared_frame, measure_filter, plan_agg, resolve_request},
+    },
+    filters::fltr_eq,
+    DataSet, DrillThroughPage, DrillThroughRequest,
+};
+
+const TOTAL: &str = "__total__";
+
+/// Filters (including the precomputefilter of the measure), prepares and overrides the data
+/// the same way [super::exec_agg_with_context] does, then keeps the rows of the cell
+pub fn exec_drill_through<DS: DataSet + ?Sized>(
+    data: &DS,
+    req: DrillThroughRequest,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> UltiResult<DrillThroughPage> {
+    ctx.start_timer();
+
+    let DrillThroughRequest {
+        request,
+        cell,
+        measure,
+        columns,
+        offset,
+        limit,
+    } = req;
+    if limit > MAX_DRILL_THROUGH_ROWS {
+        return Err(UltimaErr::InvalidRequest(format!(
+            "Can't return more than {MAX_DRILL_THROUGH_ROWS} rows at once, got limit {limit}"
+        )));
+    }
+
+    // Rows of the as_of snapshot. Drill through the compare_to snapshot by requesting it as_of
+    let mut request = request;
+    request.compare_to = None;
+    let mut request = resolve_request(data, request)?;
+    // Calculated and window measures don't select any rows of their own
+    let windows = std::mem::take(&mut request.windows);
+    for w in &windows {
+        let produced_by_request = windows.iter().any(|e| e.name == w.measure.0)
+            || request.calculated.iter().any(|cm| cm.name == w.measure.0);
+        if !produced_by_request && !request.measures.contains(&w.measure) {
+            request.measures.push(w.measure.clone())
+        }
+    }
+    request.calculated.clear();
+    if let Some(m) = &measure {
+        request.measures.retain(|(name, _)| name == m);
+        if request.measures.is_empty() {
+            return Err(UltimaErr::InvalidRequest(format!(
+                "{m} is not a measure of the request"
+            )));
+        }
+    }
+    let plan = plan_agg(data, &request)?;
+
+    ctx.checkpoint("lookup")?;
+
+    let fltr = measure_filter(
+        plan.base_measures
+            .into_iter()
+            .map(|(_, _, m)| m.precomputefilter),
+    );
+    let mut f1 = _prepared_frame(
+        data,
+        request.filters,
+        request.add_row,
+        &request.overrides,
+        fltr,
+        prepare,
+        ctx,
+    )?;
+
+    for (name, value) in &cell {
+        let Some(dimension) = request.groupby.iter().find(|d| d.name() == name) else {
+            return Err(UltimaErr::InvalidRequest(format!(
+                "{name} is not in the groupby of the request"
+            )));
+        };
+        if let Some(expr) = dimension.to_expr()? {
+            f1 = f1.with_column(expr)
+        }
+        f1 = f1.filter(fltr_eq(name, value))
+    }
+
+    if !columns.is_empty() {
+        f1 = f1.select(columns.iter().map(|c| col(c)).collect::<Vec<Expr>>())
+    }
+
+    // Only the page is loaded
+    let total = f1
+        .clone()
+        .select([len().alias(TOTAL)])
+        .collect()?
+        .column(TOTAL)?
+        .idx()?
+        .get(0)
+        .unwrap_or_default() as usize;
+    let rows = f1.slice(offset as i64, limit as IdxSize).collect()?;
+
+    ctx.checkpoint("drill_through")?;
+
+    Ok(DrillThroughPage { total, rows })
+}
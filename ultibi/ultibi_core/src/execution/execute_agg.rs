@@ -4,7 +4,7 @@ use std::collections::{HashMap, HashSet};
 
 use polars::{
     chunked_array::ops::SortMultipleOptions,
-    prelude::{concat_lf_diagonal, PolarsError},
+    prelude::{concat_lf_diagonal, IdxSize, JoinArgs, JoinType, LazyFrame},
 };
 pub use polars::{
     functions::concat_df_diagonal,
@@ -15,12 +15,19 @@ use crate::{
     add_row::{df_from_maps_and_schema, AdditionalRows},
     agg_measure_lookup, agg_measure_to_expr,
     aggregations::{Aggregation, AggregationName, BASE_CALCS},
-    errors::UltiResult,
+    datarequest::{GroupingPlan, GROUPING_ID},
+    dimensions::Dimension,
+    errors::{UltiResult, UltimaErr},
     execute_agg_with_cache::_exec_agg_with_cache,
+    execution::{context::ExecutionContext, pivot::pivot_frame},
     filters::AndOrFltrChain,
     lookup_dependants_with_depth,
     overrides::Override,
-    AggregationRequest, DataSet, Measure, MeasureName, ProcessedBaseMeasure, ProcessedMeasure,
+    snapshot::exec_comparison,
+    validate_calc_params,
+    window::{apply_windows, has_parents, join_parents, WindowStage},
+    AggregationRequest, DataSet, Measure, MeasureName, MeasuresMap, Pivot, ProcessedBaseMeasure,
+    ProcessedMeasure,
 };
 
 #[cfg(feature = "db")]
@@ -36,28 +43,179 @@ pub fn exec_agg<DS: DataSet + ?Sized>(
     req: AggregationRequest,
     prepare: bool,
 ) -> UltiResult<DataFrame> {
-    // If we cache, we will need req down the line. If that's the case, clone
-    let req_clone = data.as_cacheable().map(|_| req.clone());
+    exec_agg_with_context(data, req, prepare, &ExecutionContext::default())
+}
+
+/// Same as [exec_agg], but checks `ctx` for cancellation/timeout between the stages
+pub fn exec_agg_with_context<DS: DataSet + ?Sized>(
+    data: &DS,
+    req: AggregationRequest,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> UltiResult<DataFrame> {
+    ctx.start_timer();
+
+    let req = resolve_request(data, req)?;
+    exec_resolved(data, req, prepare, ctx)
+}
+
+/// Executes a request which has been through [resolve_request]
+pub(crate) fn exec_resolved<DS: DataSet + ?Sized>(
+    data: &DS,
+    req: AggregationRequest,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> UltiResult<DataFrame> {
+    // Each snapshot is computed on its own
+    if req.compare_to.is_some() {
+        return exec_comparison(data, req, prepare, ctx);
+    }
+
+    // Step 0 and 1: Lookup and return Expr
+    let plan = plan_agg(data, &req)?;
+
+    ctx.checkpoint("lookup")?;
+
+    // Step 2 Compute basics
+    let hide_zeros = req.hide_zeros;
+    let pivot = req.pivot.clone();
+    let parents_req = has_parents(&plan.windows).then(|| req.clone());
+    let res = match data.as_cacheable() {
+        Some(cacheable) => {
+            let res = _exec_agg_with_cache(
+                cacheable,
+                req,
+                plan.grouping.as_ref(),
+                plan.base_measures,
+                prepare,
+                ctx,
+            );
+            ctx.checkpoint("cache")?;
+            res
+        }
+        _ => _exec_agg_base(
+            data,
+            req.filters,
+            req.add_row,
+            &req.overrides,
+            &req.groupby,
+            plan.grouping.as_ref(),
+            plan.base_measures
+                .into_iter()
+                .map(|(_, _, pbm)| pbm)
+                .collect(),
+            prepare,
+            ctx,
+        ),
+    }?;
+    let res = match &parents_req {
+        Some(req) => join_parents(
+            data,
+            req,
+            plan.grouping.as_ref(),
+            &plan.windows,
+            res,
+            prepare,
+            ctx,
+        )?,
+        None => res,
+    };
 
+    // Step 3 and 4
+    _finish_agg(
+        res,
+        plan.dependants,
+        plan.windows,
+        &plan.columns,
+        hide_zeros.then_some(&plan.measure_columns[..]),
+        pivot.as_ref(),
+        ctx,
+    )
+}
+
+/// Templates can be executed as they are if all their parameters have defaults.
+/// Hierarchies and pivot determine the groupby. Windows of the DataSet are requested by name.
+/// `as_of` becomes a filter on the snapshot, unless snapshots are compared
+pub(crate) fn resolve_request<DS: DataSet + ?Sized>(
+    data: &DS,
+    req: AggregationRequest,
+) -> UltiResult<AggregationRequest> {
+    let req = if req.parameters.is_empty() {
+        req
+    } else {
+        req.with_parameters(&Default::default())?
+    };
+    req.with_hierarchies(data.hierarchies())?
+        .with_windows(data.windows())?
+        .with_pivot_groupby()?
+        .with_snapshot(data)
+}
+
+/// What needs to be computed for a request, once its measures have been looked up
+pub(crate) struct AggPlan {
+    /// (Measure Name, Aggregation Name, Expr)
+    pub(crate) base_measures: Vec<(MeasureName, AggregationName, ProcessedBaseMeasure)>,
+    /// Executed in .with_columns() context, by depth
+    pub(crate) dependants: Vec<Vec<Expr>>,
+    /// Executed after the dependants, one after another
+    pub(crate) windows: Vec<WindowStage>,
+    /// Groupby followed by the requested measures
+    pub(crate) columns: Vec<String>,
+    /// Requested measures
+    pub(crate) measure_columns: Vec<String>,
+    /// Subtotals, if requested
+    pub(crate) grouping: Option<GroupingPlan>,
+}
+
+/// Looks up requested measures and expresses them
+pub(crate) fn plan_agg<DS: DataSet + ?Sized>(
+    data: &DS,
+    req: &AggregationRequest,
+) -> UltiResult<AggPlan> {
     // Step 0: Lookup and return Expr
-    let all_requested_measures = req.measures;
-    if all_requested_measures.is_empty() {
-        return Err(PolarsError::InvalidOperation(
-            "Select measures. What do you want to aggregate?".into(),
-        )
-        .into());
+    if req.measures.is_empty() && req.windows.is_empty() {
+        return Err(UltimaErr::InvalidRequest(
+            "Select measures. What do you want to aggregate?".to_string(),
+        ));
     }
 
     let op = &req.calc_params; // Optional params of the request
+    validate_calc_params(&data.calc_params(), op, data.strict_calc_params())?;
 
-    let dataset_measure_map = data.get_measures(); // all availiable measures
+    let grouping = req.grouping_plan()?;
+
+    // Calculated measures of the request are dependants on top of the DataSet's measures
+    let calculated = req.calculated_measures(data.get_measures(), grouping.is_some())?;
+    let mut all_requested_measures = req.measures.clone();
+    let with_calculated: MeasuresMap;
+    let dataset_measure_map = if calculated.is_empty() {
+        data.get_measures() // all availiable measures
+    } else {
+        all_requested_measures.extend(
+            calculated
+                .iter()
+                .map(|dm| (dm.name.clone(), "scalar".to_string())),
+        );
+        let mut mm = data.get_measures().clone();
+        mm.extend(
+            calculated
+                .into_iter()
+                .map(|dm| (dm.name.clone(), dm.into())),
+        );
+        with_calculated = mm;
+        &with_calculated
+    };
+
+    // Windows are over the result, so the measures they are over must be computed too
+    let (window_measures, windows) = req.window_stages(data.get_measures(), grouping.is_some())?;
+    let mut to_compute = all_requested_measures.clone();
+    to_compute.extend(window_measures);
 
     // Step 1.0 Lookup requested measures in the DataSet
-    let looked_up_measures = agg_measure_lookup(&all_requested_measures, dataset_measure_map)?;
+    let looked_up_measures = agg_measure_lookup(&to_compute, dataset_measure_map)?;
 
     // Step 1.1 For dependants we need to keep track of their "depth"
-    let dependants_with_depth =
-        lookup_dependants_with_depth(&all_requested_measures, dataset_measure_map);
+    let dependants_with_depth = lookup_dependants_with_depth(&to_compute, dataset_measure_map);
 
     // Step 1.2 Express dependants now
     let mut processed_dependants = Vec::with_capacity(dependants_with_depth.len());
@@ -94,45 +252,80 @@ pub fn exec_agg<DS: DataSet + ?Sized>(
         )
         .collect::<PolarsResult<Vec<(&MeasureName, &AggregationName, ProcessedMeasure)>>>()?;
 
+    // Invalid computed dimensions fail before any work is done
+    let computed = req
+        .groupby
+        .iter()
+        .filter_map(|d| match d {
+            Dimension::Computed(cd) => Some(cd),
+            Dimension::Column(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if !computed.is_empty() {
+        let schema = data.get_schema()?;
+        for cd in computed {
+            if schema.contains(&cd.name) {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "Computed dimension {} would replace the column of the same name. Rename it",
+                    cd.name
+                )));
+            }
+            cd.to_expr()?;
+        }
+    }
+
     // Keep all REQUESTED Column Names for later use:
-    let mut all_requested_columns_names = req.groupby.clone();
-    all_requested_columns_names.extend(all_requested_measures.iter().map(|(measure_name, agg)| {
-        let agg = BASE_CALCS.get(agg as &str).expect("Failed to look up agg"); //we have checked in agg_measure_lookup
-        agg.new_name(measure_name as &str)
-    }));
-    // Keep cosmetic arguments for later use:
-    let hide_zeros = req.hide_zeros;
+    let mut all_requested_columns_names = req.group_by();
+    if grouping.is_some() {
+        all_requested_columns_names.push(GROUPING_ID.to_string());
+    }
+    let measure_columns: Vec<String> = all_requested_measures
+        .iter()
+        .map(|(measure_name, agg)| {
+            let agg = BASE_CALCS.get(agg as &str).expect("Failed to look up agg"); //we have checked in agg_measure_lookup
+            agg.new_name(measure_name as &str)
+        })
+        .collect();
+    all_requested_columns_names.extend(measure_columns.iter().cloned());
+    all_requested_columns_names.extend(req.windows.iter().map(|w| w.name.clone()));
 
     //  break down measures into dependant and basic
     let mut base_measures = Vec::with_capacity(expressed_measures.len());
 
     for m in expressed_measures {
         if let (measure_name, aggregation_name, ProcessedMeasure::Base(pbm)) = m {
-            base_measures.push((measure_name, aggregation_name, pbm))
+            base_measures.push((measure_name.clone(), aggregation_name.clone(), pbm))
         }
     }
 
-    // Step 2 Compute basics
-    let mut res = match data.as_cacheable() {
-        Some(cacheable) => {
-            _exec_agg_with_cache(cacheable, req_clone.unwrap(), base_measures, prepare)
-        }
-        _ => _exec_agg_base(
-            data,
-            req.filters,
-            req.add_row,
-            &req.overrides,
-            req.groupby,
-            req.totals,
-            base_measures.into_iter().map(|(_, _, pbm)| pbm).collect(),
-            prepare,
-        ),
-    }?;
+    Ok(AggPlan {
+        base_measures,
+        dependants: processed_dependants,
+        windows,
+        columns: all_requested_columns_names,
+        measure_columns,
+        grouping,
+    })
+}
 
+/// Computes dependants on top of the result of base measures,
+/// selects the requested columns, applies cosmetics and pivots
+pub(crate) fn _finish_agg(
+    mut res: DataFrame,
+    dependants: Vec<Vec<Expr>>,
+    windows: Vec<WindowStage>,
+    all_requested_columns_names: &[String],
+    // Rows where all of these are zero or null are hidden
+    hide_zeros: Option<&[String]>,
+    pivot: Option<&Pivot>,
+    ctx: &ExecutionContext,
+) -> UltiResult<DataFrame> {
     // Step 3 compute dependants
-    for i in processed_dependants.into_iter() {
-        res = res.lazy().with_columns(i).collect()?
+    for i in dependants.into_iter() {
+        res = res.lazy().with_columns(i).collect()?;
+        ctx.checkpoint("dependants")?;
     }
+    res = apply_windows(res, windows, ctx)?;
     res = res
         .lazy()
         .select(
@@ -145,8 +338,8 @@ pub fn exec_agg<DS: DataSet + ?Sized>(
 
     // TODO Step 4 - cosmetics
     // Hide Zeros
-    if hide_zeros {
-        let mut it = all_requested_columns_names.into_iter().filter(|col_name| {
+    if let Some(measure_columns) = hide_zeros {
+        let mut it = measure_columns.iter().filter(|col_name| {
             res.column(col_name)
                 .expect("Requested column not found")
                 ._dtype()
@@ -155,48 +348,60 @@ pub fn exec_agg<DS: DataSet + ?Sized>(
 
         if let Some(c) = it.next() {
             // Filter where col is Not Eq 0 AND Not Eq Null
-            let mut predicate = col(&c).neq(lit::<f64>(0.)).and(col(&c).is_not_null());
+            let mut predicate = col(c).neq(lit::<f64>(0.)).and(col(c).is_not_null());
             for c in it {
-                predicate = predicate.or(col(&c).neq(lit::<f64>(0.)).and(col(&c).is_not_null()))
+                predicate = predicate.or(col(c).neq(lit::<f64>(0.)).and(col(c).is_not_null()))
             }
             res = res.lazy().filter(predicate).collect()?;
         }
     };
 
-    Ok(res)
+    match pivot {
+        Some(pivot) => pivot_frame(res, pivot),
+        None => Ok(res),
+    }
 }
 
 /// main function which returns a Result of the calculation
 /// Executes base measures on your DataSet
-pub(crate) fn _exec_agg_base<DS, I, S>(
+pub(crate) fn _exec_agg_base<DS>(
     data: &DS,
     filters: AndOrFltrChain,
     add_rows: AdditionalRows,
     overrides: &[Override],
-    groupby: I,
-    totals: bool,
+    groupby: &[Dimension],
+    grouping: Option<&GroupingPlan>,
     processed_base_measures: Vec<ProcessedBaseMeasure>,
     prepare: bool,
+    ctx: &ExecutionContext,
 ) -> UltiResult<DataFrame>
 where
     DS: DataSet + ?Sized,
-    I: IntoIterator<Item = S>,
-    S: AsRef<str>,
 {
-    // TODO PRECOMPUTE FILTER TO THE MAIN FILTER - not so easy because precompute filter is an expr
-
-    // Step 1.0 and 1.1 - get existing Filtered frame - first building block
-    let mut f1 = data.get_lazyframe(&filters)?;
-
     // Step 2.1
-    // Unpack - (New Column Name, AggExpr, MeasureSpecificFilter)
-    let (_, (aggregateions, fltrs)): (Vec<String>, (Vec<Expr>, Vec<Option<Expr>>)) =
+    // Unpack - (AggExpr, Rollup), MeasureSpecificFilter
+    let ((aggregateions, rollups), fltrs): ((Vec<Expr>, Vec<Option<Expr>>), Vec<Option<Expr>>) =
         processed_base_measures
             .into_iter()
-            .map(|m| (m.name, (m.calculator, m.precomputefilter)))
+            .map(|m| ((m.calculator, m.rollup), m.precomputefilter))
             .unzip();
 
-    // Step 2.2 Build Measure Specific Filter
+    let f1 = _prepared_frame(
+        data,
+        filters,
+        add_rows,
+        overrides,
+        measure_filter(fltrs),
+        prepare,
+        ctx,
+    )?;
+
+    _group_by(f1, groupby, grouping, &aggregateions, &rollups, ctx)
+}
+
+/// Step 2.2 Build Measure Specific Filter
+/// Returns None if at least one of the measures doesn't have a filter
+pub(crate) fn measure_filter(fltrs: impl IntoIterator<Item = Option<Expr>>) -> Option<Expr> {
     // Note: DOESN'T WORK .or(lit::<bool>(true))
     // By default, everything is false (ie everything is filtered out)
     let mut measure_filter_opt = Some(lit::<bool>(false));
@@ -214,14 +419,34 @@ where
             }
         }
     }
+    measure_filter_opt
+}
+
+/// Filtered, prepared (if `prepare`) and overridden frame, with additional rows.
+/// Everything up to the GroupBy
+pub(crate) fn _prepared_frame<DS: DataSet + ?Sized>(
+    data: &DS,
+    filters: AndOrFltrChain,
+    add_rows: AdditionalRows,
+    overrides: &[Override],
+    measure_filter: Option<Expr>,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> UltiResult<LazyFrame> {
+    // TODO PRECOMPUTE FILTER TO THE MAIN FILTER - not so easy because precompute filter is an expr
+
+    // Step 1.0 and 1.1 - get existing Filtered frame - first building block
+    let mut f1 = data.get_lazyframe(&filters)?;
 
     // Step 2.3 Applying (Measure)FILTER
-    if let Some(fltr) = measure_filter_opt {
+    if let Some(fltr) = measure_filter {
         f1 = f1.filter(fltr)
     }
 
     // dbg!(f1.clone().collect());
 
+    // Up to add_row the stages only build the plan, see [ExecutionContext::timings]
+    ctx.checkpoint("filter")?;
     // If streaming then prepare (assign weights) NOW (ie post filtering)
     if prepare {
         f1 = data.prepare_frame(f1)?;
@@ -236,11 +461,15 @@ where
 
     // dbg!(f1.clone().select([col("TradeId"), col("Desk"), col("RiskFactor"),col("BucketBCBS"), col("SensWeights"), col("SensitivitySpot")]).collect());
 
+    ctx.checkpoint("prepare")?;
+
     // Step 2.4 Applying Overwrites
     for ow in overrides {
-        f1 = ow.lf_with_overwrite(f1)?
+        f1 = ow.lf_with_checked_overwrite(f1)?
     }
 
+    ctx.checkpoint("overrides")?;
+
     // Step 2.5 Add Row
     if !add_rows.rows.is_empty() {
         let current_schema = f1.schema()?;
@@ -261,80 +490,143 @@ where
     //dbg!(f1.clone().select([col("*")]).collect());
     //dbg!(&groupby);
 
-    // Step 3.1 Build GROUPBY
-    let groups: Vec<Expr> = groupby.into_iter().map(|x| col(x.as_ref())).collect();
-    // fill nulls with a "null" - needed for better totals views
-    let groups_fill_nulls: Vec<Expr> = groups
-        .clone()
-        .into_iter()
-        .map(|e| e.fill_null(lit(" ")))
-        .collect();
+    ctx.checkpoint("add_row")?;
 
-    //let f1 = f1.collect()?.lazy();
+    Ok(f1)
+}
+
+/// Keeps aggregations in the group context when a grouping set is empty
+const GRAND_TOTAL_KEY: &str = "__grand_total__";
+
+/// Max number of rows of each grouping set of a result (or of the result if there are none)
+pub const MAX_GROUPS: usize = 1_000;
+
+/// First [MAX_GROUPS] rows. Tells `ctx` if there were more
+fn limited(lf: LazyFrame, ctx: &ExecutionContext) -> UltiResult<DataFrame> {
+    let df = lf.limit(MAX_GROUPS as IdxSize + 1).collect()?;
+    if df.height() > MAX_GROUPS {
+        ctx.record_truncation();
+        Ok(df.head(Some(MAX_GROUPS)))
+    } else {
+        Ok(df)
+    }
+}
+
+/// GroupBy and Agg, with subtotals if `grouping` is requested.
+/// Measures which can be rolled up are aggregated from the finest level,
+/// the rest (eg FRTB charges) are recomputed from `f1` for each set
+pub(crate) fn _group_by(
+    mut f1: LazyFrame,
+    groupby: &[Dimension],
+    grouping: Option<&GroupingPlan>,
+    aggregateions: &[Expr],
+    rollups: &[Option<Expr>],
+    ctx: &ExecutionContext,
+) -> UltiResult<DataFrame> {
+    // Step 3.1 Build GROUPBY
+    let computed = groupby
+        .iter()
+        .filter_map(|d| d.to_expr().transpose())
+        .collect::<UltiResult<Vec<Expr>>>()?;
+    if !computed.is_empty() {
+        f1 = f1.with_columns(computed);
+    }
+    let groups: Vec<Expr> = groupby.iter().map(|x| col(x.name())).collect();
 
     // Step 3.2 Apply GroupBy and Agg
+    let Some(grouping) = grouping else {
+        let aggregated_df = limited(
+            f1.with_streaming(true) // Set streaming to True anyway - no performance penalty
+                .group_by_stable(&groups)
+                .agg(aggregateions),
+            ctx,
+        )?;
+        ctx.checkpoint("groupby")?;
+        return Ok(aggregated_df);
+    };
+
+    // Not limited, since subtotals are rolled up from it
+    let finest = f1
+        .clone()
+        .with_streaming(true)
+        .group_by_stable(&groups)
+        .agg(aggregateions)
+        .collect()?;
+
+    ctx.checkpoint("groupby")?;
 
-    // The difference is that we do not need to clone f1
-    // unless we are computing totals
-    let res = if totals & (groups.len() > 1) {
-        let mut aggregated_df = f1
-            .clone() // if totals then we must clone here
-            .with_streaming(true) // Set streaming to True anyway - no performance penalty
-            .group_by_stable(&groups)
-            .agg(&aggregateions)
-            .limit(1_000)
-            .with_columns(&groups_fill_nulls)
-            .collect()?;
-
-        let ordered_cols = aggregated_df.get_column_names_owned();
-        let mut total_frames = vec![];
-        //let mut with_cols = vec![];
-
-        for i in (1..groups.len()).rev() {
-            // Columns which we are going to aggregate this time
-            // (groups minus it's last element)
-            let grp_by = &groups[0..i];
-            let grp_by_fill_null = &groups_fill_nulls[0..i];
-            // Not doing this, since we otherwise loose soring
-            //let last_gr_col_name = &req._group_by()[i];
-            //with_cols.push(lit("Total").alias(last_gr_col_name));
-
-            let _df = f1
-                .clone()
-                .with_streaming(true)
-                .group_by_stable(grp_by)
-                .agg(&aggregateions)
-                .limit(100)
-                .with_columns(grp_by_fill_null)
-                .collect()?;
-            total_frames.push(_df)
+    let mut additive = vec![];
+    let mut non_additive = vec![];
+    for (agg, rollup) in aggregateions.iter().zip(rollups) {
+        match rollup {
+            Some(rollup) => additive.push(rollup.clone()),
+            None => non_additive.push(agg.clone()),
         }
+    }
 
-        total_frames.push(aggregated_df);
-        aggregated_df = concat_df_diagonal(&total_frames)?;
+    let n = groups.len();
+    let subtotals = grouping.sets.iter().filter(|set| set.len() < n).count();
+    // Scan and prepare once if non additive measures are recomputed several times
+    let f1 = if !non_additive.is_empty() && subtotals > 1 {
+        f1.collect()?.lazy()
+    } else {
+        f1
+    };
 
-        let groups_totals: Vec<Expr> = groups
-            .clone()
-            .into_iter()
-            .map(|e| e.fill_null(lit("Total")))
-            .collect();
+    let mut frames = Vec::with_capacity(grouping.sets.len());
+
+    for set in &grouping.sets {
+        // Subtotals are rolled up from all of `finest`, then limited alike, see [limited]
+        let df = if set.len() == n {
+            finest.clone()
+        } else {
+            let mut keys: Vec<Expr> = set.iter().map(|i| groups[*i].clone()).collect();
+            let mut finer = finest.clone().lazy();
+            let mut f = f1.clone();
+            if keys.is_empty() {
+                finer = finer.with_column(lit(0).alias(GRAND_TOTAL_KEY));
+                f = f.with_column(lit(0).alias(GRAND_TOTAL_KEY));
+                keys.push(col(GRAND_TOTAL_KEY));
+            }
+
+            let mut lf = finer.group_by_stable(&keys).agg(&additive);
+            if !non_additive.is_empty() {
+                let recomputed = f
+                    .with_streaming(true)
+                    .group_by_stable(&keys)
+                    .agg(&non_additive);
+                let mut args = JoinArgs::new(JoinType::Left);
+                args.join_nulls = true;
+                lf = lf.join(recomputed, keys.clone(), keys, args);
+            }
+            if set.is_empty() {
+                lf = lf.drop([GRAND_TOTAL_KEY]);
+            }
+            lf.collect()?
+        };
 
+        // Rolled up columns are null, hence the id tells subtotals from nulls in the data
+        frames.push(limited(
+            df.lazy()
+                .with_column(lit(grouping_id(set, n)).alias(GROUPING_ID)),
+            ctx,
+        )?);
+
+        ctx.checkpoint("totals")?;
+    }
+
+    let mut res = concat_df_diagonal(&frames)?.lazy();
+    if !groups.is_empty() {
         let sort_options = SortMultipleOptions::default().with_maintain_order(true);
+        res = res.sort_by_exprs(&groups, sort_options);
+    }
 
-        aggregated_df
-            .lazy()
-            .sort_by_exprs(&groups, sort_options)
-            .select(ordered_cols.iter().map(|c| col(c)).collect::<Vec<Expr>>())
-            .with_columns(groups_totals)
-            .collect()?
-    } else {
-        f1.with_streaming(true) // Set streaming to True anyway - no performance penalty
-            .group_by_stable(&groups)
-            .agg(&aggregateions)
-            .limit(1_000)
-            .with_columns(&groups_fill_nulls)
-            .collect()?
-    };
+    Ok(res.collect()?)
+}
 
-    Ok(res)
+/// See [GROUPING_ID]
+pub(crate) fn grouping_id(set: &[usize], n: usize) -> u32 {
+    (0..n)
+        .filter(|i| !set.contains(i))
+        .fold(0, |id, i| id | 1 << (n - 1 - i))
 }
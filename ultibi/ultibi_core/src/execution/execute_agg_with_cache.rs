@@ -1,9 +1,13 @@
 use polars::prelude::{col, DataFrame, Expr, IntoLazy, JoinArgs, JoinCoalesce, JoinType};
 
+use crate::aggregations::AggregationName;
 use crate::cache::CacheableDataSet;
+use crate::datarequest::{GroupingPlan, GROUPING_ID};
 use crate::errors::UltiResult;
+use crate::execution::context::ExecutionContext;
 use crate::{
-    AggregationRequest, CacheableAggregationRequest, CacheableComputeRequest, ProcessedBaseMeasure,
+    AggregationRequest, CacheableAggregationRequest, CacheableComputeRequest, MeasureName,
+    ProcessedBaseMeasure,
 };
 
 /// Looks up from Cache
@@ -13,11 +17,26 @@ use crate::{
 pub(crate) fn _exec_agg_with_cache<DS: CacheableDataSet + ?Sized>(
     data: &DS,
     req: AggregationRequest,
-    processed_base_measures: Vec<(&String, &String, ProcessedBaseMeasure)>,
+    grouping: Option<&GroupingPlan>,
+    processed_base_measures: Vec<(MeasureName, AggregationName, ProcessedBaseMeasure)>,
     streaming: bool,
+    ctx: &ExecutionContext,
 ) -> UltiResult<DataFrame> {
-    let requested_groupby = req.group_by().clone();
-    let grp_by_expr = req.group_by().iter().map(|x| col(x)).collect::<Vec<Expr>>();
+    // Rows of different grouping sets differ by the grouping id
+    let mut requested_groupby = req.group_by();
+    if grouping.is_some() {
+        requested_groupby.push(GROUPING_ID.to_string());
+    }
+    let grp_by_expr = requested_groupby
+        .iter()
+        .map(|x| col(x))
+        .collect::<Vec<Expr>>();
+    // Nulls are kept in the groupby, and subtotals have nulls in rolled up columns
+    let join_args = || {
+        let mut args = JoinArgs::from(JoinType::Outer).with_coalesce(JoinCoalesce::CoalesceColumns);
+        args.join_nulls = true;
+        args
+    };
 
     // have already been calculated
     let mut cached_results = vec![];
@@ -28,7 +47,7 @@ pub(crate) fn _exec_agg_with_cache<DS: CacheableDataSet + ?Sized>(
     //for cacheable_request in cacheable_requests {
     for (measure_name, agg_name, pbm) in processed_base_measures {
         let cacheable_request = CacheableAggregationRequest {
-            measure: (measure_name.to_string(), agg_name.to_string()),
+            measure: (measure_name, agg_name),
             name: req.name.clone(),
             groupby: req.groupby.clone(),
             filters: req.filters.clone(),
@@ -36,6 +55,7 @@ pub(crate) fn _exec_agg_with_cache<DS: CacheableDataSet + ?Sized>(
             add_row: req.add_row.clone(),
             calc_params: req.calc_params.clone(),
             totals: req.totals,
+            grouping_sets: req.grouping_sets.clone(),
         };
 
         let cacheable_compute_request = CacheableComputeRequest::Aggregation(cacheable_request);
@@ -43,10 +63,14 @@ pub(crate) fn _exec_agg_with_cache<DS: CacheableDataSet + ?Sized>(
         match data.get_cache().get(&cacheable_compute_request) {
             // If found - store result
             Some(rf) => {
+                ctx.record_cache_lookup(true);
                 cached_results.push(rf.value().clone());
             }
             // if not push to those which will have to be calculated
-            _ => yet_to_calculate.push((cacheable_compute_request, pbm)),
+            _ => {
+                ctx.record_cache_lookup(false);
+                yet_to_calculate.push((cacheable_compute_request, pbm))
+            }
         }
     }
 
@@ -63,7 +87,7 @@ pub(crate) fn _exec_agg_with_cache<DS: CacheableDataSet + ?Sized>(
                     df.lazy(),
                     grp_by_expr.clone(),
                     grp_by_expr.clone(),
-                    JoinArgs::from(JoinType::Outer).with_coalesce(JoinCoalesce::CoalesceColumns),
+                    join_args(),
                 )
                 .collect()?)
         }
@@ -94,10 +118,11 @@ pub(crate) fn _exec_agg_with_cache<DS: CacheableDataSet + ?Sized>(
             req.filters,
             req.add_row,
             &req.overrides,
-            req.groupby,
-            req.totals,
+            &req.groupby,
+            grouping,
             processed_base_measures,
             streaming,
+            ctx,
         )?;
 
         // Now save each of new measures to cache
@@ -119,12 +144,7 @@ pub(crate) fn _exec_agg_with_cache<DS: CacheableDataSet + ?Sized>(
     match (_chached_df, _new_res) {
         (Some(cached), Some(new)) => Ok(cached
             .lazy()
-            .join(
-                new.lazy(),
-                grp_by_expr.clone(),
-                grp_by_expr,
-                JoinArgs::from(JoinType::Outer).with_coalesce(JoinCoalesce::CoalesceColumns),
-            )
+            .join(new.lazy(), grp_by_expr.clone(), grp_by_expr, join_args())
             .collect()?),
         (None, Some(df)) | (Some(df), None) => Ok(df),
         _ => unreachable!(),
@@ -0,0 +1,251 @@
+//! Execution of several requests at once (eg all the widgets of a dashboard)
+//!
+//! Requests which share filters, overrides and additional rows (and measure specific filters)
+//! share the filtered, prepared and overridden frame. It is computed once and then each
+//! request's GroupBy runs in parallel on top of it.
+
+use std::collections::{BTreeSet, HashMap};
+
+use polars::{
+    export::rayon::prelude::{IntoParallelIterator, ParallelIterator},
+    prelude::{DataFrame, IntoLazy},
+};
+
+use crate::{
+    add_row::AdditionalRows,
+    errors::{UltiResult, UltimaErr},
+    execution::{
+        context::ExecutionContext,
+        execute_agg::{
+            _finish_agg, _group_by, _prepared_frame, exec_resolved, measure_filter, plan_agg,
+            resolve_request, AggPlan,
+        },
+    },
+    filters::AndOrFltrChain,
+    overrides::Override,
+    window::join_parents,
+    AggregationRequest, DataSet, MeasureName,
+};
+
+/// Everything up to the GroupBy is the same for requests with the same key
+#[derive(PartialEq, Eq, Hash)]
+struct SharedWork {
+    filters: AndOrFltrChain,
+    overrides: Vec<Override>,
+    add_row: AdditionalRows,
+    /// Measures whose precompute filters are applied. None if not filtered
+    filtered_measures: Option<BTreeSet<MeasureName>>,
+}
+
+impl SharedWork {
+    fn new(req: &AggregationRequest, plan: &AggPlan) -> Self {
+        let filtered_measures = plan
+            .base_measures
+            .iter()
+            .map(|(measure, _, pbm)| pbm.precomputefilter.as_ref().map(|_| measure.clone()))
+            .collect::<Option<BTreeSet<_>>>();
+        Self {
+            filters: req.filters.clone(),
+            overrides: req.overrides.clone(),
+            add_row: req.add_row.clone(),
+            filtered_measures,
+        }
+    }
+}
+
+type Member = (usize, AggregationRequest, AggPlan);
+
+/// Results are in the order of `reqs`. A failure of one request doesn't fail the others
+///
+/// Note: requests which share work with others bypass the cache
+pub fn exec_agg_many<DS: DataSet + ?Sized>(
+    data: &DS,
+    reqs: Vec<AggregationRequest>,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> Vec<UltiResult<DataFrame>> {
+    ctx.start_timer();
+    let mut results: Vec<Option<UltiResult<DataFrame>>> = reqs.iter().map(|_| None).collect();
+
+    // Keep the order in which groups appear
+    let mut keys: HashMap<SharedWork, usize> = HashMap::new();
+    let mut groups: Vec<Vec<Member>> = vec![];
+    let mut comparisons = vec![];
+
+    for (i, req) in reqs.into_iter().enumerate() {
+        let planned =
+            resolve_request(data, req).and_then(|req| plan_agg(data, &req).map(|plan| (req, plan)));
+        match planned {
+            // Snapshots are compared on their own
+            Ok((req, _)) if req.compare_to.is_some() => comparisons.push((i, req)),
+            Ok((req, plan)) => {
+                let key = SharedWork::new(&req, &plan);
+                let g = *keys.entry(key).or_insert_with(|| {
+                    groups.push(vec![]);
+                    groups.len() - 1
+                });
+                groups[g].push((i, req, plan));
+            }
+            Err(e) => results[i] = Some(Err(e)),
+        }
+    }
+
+    if let Err(e) = ctx.checkpoint("lookup") {
+        return results
+            .into_iter()
+            .map(|r| r.unwrap_or_else(|| Err(replicate(&e))))
+            .collect();
+    }
+
+    for mut members in groups {
+        if members.len() == 1 {
+            // Nothing to share, take the usual path (which uses the cache)
+            let (i, req, _) = members.pop().expect("Group is not empty");
+            results[i] = Some(exec_resolved(data, req, prepare, ctx));
+            continue;
+        }
+        for (i, res) in exec_shared(data, members, prepare, ctx) {
+            results[i] = Some(res);
+        }
+    }
+    for (i, req) in comparisons {
+        results[i] = Some(exec_resolved(data, req, prepare, ctx));
+    }
+
+    results
+        .into_iter()
+        .map(|r| r.expect("Every request has a result"))
+        .collect()
+}
+
+/// Computes the shared frame once, then fans out GroupBys
+fn exec_shared<DS: DataSet + ?Sized>(
+    data: &DS,
+    members: Vec<Member>,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> Vec<(usize, UltiResult<DataFrame>)> {
+    let (_, first, first_plan) = &members[0];
+    let shared = _prepared_frame(
+        data,
+        first.filters.clone(),
+        first.add_row.clone(),
+        &first.overrides,
+        measure_filter(
+            first_plan
+                .base_measures
+                .iter()
+                .map(|(_, _, pbm)| pbm.precomputefilter.clone()),
+        ),
+        prepare,
+        ctx,
+    )
+    .and_then(|lf| Ok(lf.collect()?.lazy()));
+
+    let shared = match shared {
+        Ok(lf) => lf,
+        Err(e) => {
+            return members
+                .into_iter()
+                .map(|(i, _, _)| (i, Err(replicate(&e))))
+                .collect()
+        }
+    };
+
+    // On the rayon pool, ie at most a thread per core however large the batch
+    members
+        .into_iter()
+        .map(|(i, req, plan)| (i, req, plan, shared.clone()))
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(i, req, plan, f1)| {
+            let (aggregations, rollups): (Vec<_>, Vec<_>) = plan
+                .base_measures
+                .into_iter()
+                .map(|(_, _, pbm)| (pbm.calculator, pbm.rollup))
+                .unzip();
+            let res = _group_by(
+                f1,
+                &req.groupby,
+                plan.grouping.as_ref(),
+                &aggregations,
+                &rollups,
+                ctx,
+            )
+            .and_then(|df| {
+                join_parents(
+                    data,
+                    &req,
+                    plan.grouping.as_ref(),
+                    &plan.windows,
+                    df,
+                    prepare,
+                    ctx,
+                )
+            })
+            .and_then(|df| {
+                _finish_agg(
+                    df,
+                    plan.dependants,
+                    plan.windows,
+                    &plan.columns,
+                    req.hide_zeros.then_some(&plan.measure_columns[..]),
+                    req.pivot.as_ref(),
+                    ctx,
+                )
+            });
+            (i, res)
+        })
+        .collect()
+}
+
+/// The error of the shared work is reported against each request of the group.
+/// Polars and serde errors can't be cloned, so only their message is kept
+fn replicate(e: &UltimaErr) -> UltimaErr {
+    use UltimaErr::*;
+    match e {
+        Cancelled(stage) => Cancelled(stage.clone()),
+        Timeout(t) => Timeout(*t),
+        UnknownMeasure { measure, available } => UnknownMeasure {
+            measure: measure.clone(),
+            available: available.clone(),
+        },
+        UnknownAggregation {
+            aggregation,
+            available,
+        } => UnknownAggregation {
+            aggregation: aggregation.clone(),
+            available: available.clone(),
+        },
+        UnsupportedAggregation {
+            measure,
+            supported,
+            requested,
+        } => UnsupportedAggregation {
+            measure: measure.clone(),
+            supported: supported.clone(),
+            requested: requested.clone(),
+        },
+        UnknownColumn { column } => UnknownColumn {
+            column: column.clone(),
+        },
+        UnknownCalcParam { param } => UnknownCalcParam {
+            param: param.clone(),
+        },
+        InvalidCalcParam { param, reason } => InvalidCalcParam {
+            param: param.clone(),
+            reason: reason.clone(),
+        },
+        InvalidOverride { field, reason } => InvalidOverride {
+            field: field.clone(),
+            reason: reason.clone(),
+        },
+        InvalidParameter { name, reason } => InvalidParameter {
+            name: name.clone(),
+            reason: reason.clone(),
+        },
+        InvalidRequest(msg) => InvalidRequest(msg.clone()),
+        DataSource(msg) => DataSource(msg.clone()),
+        e @ (Polars(_) | SerdeJson(_) | Other(_)) => Other(e.to_string()),
+    }
+}
@@ -1,17 +1,66 @@
 pub use super::helpers::searches::*;
+pub mod context;
+pub mod drill_through;
 pub mod execute_agg;
+pub use crate::execution::context::ExecutionContext;
+pub use crate::execution::drill_through::exec_drill_through;
 pub use crate::execution::execute_agg::*;
-use crate::{errors::UltiResult, ComputeRequest, DataSet};
+use crate::{
+    errors::{UltiResult, UltimaErr},
+    ComputeRequest, DataSet,
+};
 pub mod execute_agg_with_cache;
+pub mod execute_many;
+mod pivot;
+pub use crate::execution::execute_many::exec_agg_many;
 
 /// Distributes work based on request
 pub fn execute<DS: DataSet + ?Sized>(
     data: &DS,
     r: ComputeRequest,
     prepare: bool,
+) -> UltiResult<DataFrame> {
+    execute_with_context(data, r, prepare, &ExecutionContext::default())
+}
+
+/// Same as [execute], but can be cancelled or timed out via `ctx`
+pub fn execute_with_context<DS: DataSet + ?Sized>(
+    data: &DS,
+    r: ComputeRequest,
+    prepare: bool,
+    ctx: &ExecutionContext,
 ) -> UltiResult<DataFrame> {
     match r {
-        ComputeRequest::Aggregation(ar) => exec_agg(data, ar, prepare),
+        ComputeRequest::Aggregation(ar) => exec_agg_with_context(data, ar, prepare, ctx),
         _ => unimplemented!(),
     }
 }
+
+/// Same as [execute_with_context], for several requests at once. See [exec_agg_many]
+///
+/// Only aggregations can be batched. Any other request gets an error in its slot.
+pub fn execute_many<DS: DataSet + ?Sized>(
+    data: &DS,
+    rs: Vec<ComputeRequest>,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> Vec<UltiResult<DataFrame>> {
+    let mut slots = Vec::with_capacity(rs.len());
+    let mut reqs = vec![];
+    for r in rs {
+        match r {
+            ComputeRequest::Aggregation(ar) => {
+                reqs.push(ar);
+                slots.push(None)
+            }
+            _ => slots.push(Some(Err(UltimaErr::InvalidRequest(
+                "only aggregation requests can be batched".to_string(),
+            )))),
+        }
+    }
+    let mut aggs = exec_agg_many(data, reqs, prepare, ctx).into_iter();
+    slots
+        .into_iter()
+        .map(|slot| slot.unwrap_or_else(|| aggs.next().expect("one result per aggregation")))
+        .collect()
+}
@@ -0,0 +1,153 @@
+//! Spreads a dimension across the columns, see [Pivot]
+
+use std::collections::HashSet;
+
+use polars::prelude::{
+    col, lit, DataFrame, DataType, Expr, IntoLazy, JoinArgs, JoinType, LazyFrame,
+    UniqueKeepStrategy,
+};
+
+use crate::{
+    datarequest::GROUPING_ID,
+    errors::{UltiResult, UltimaErr},
+    Pivot,
+};
+
+/// Pivot without rows is a single row
+const NO_ROWS_KEY: &str = "__no_rows__";
+
+/// Name of the "Total" column of the [Pivot]
+const TOTAL: &str = "Total";
+
+/// `df` is grouped by `pivot.rows` and `pivot.column`. If `pivot.totals` then rows of
+/// `pivot.rows` grouping set hold the totals (see [crate::AggregationRequest::with_pivot_groupby])
+pub(crate) fn pivot_frame(df: DataFrame, pivot: &Pivot) -> UltiResult<DataFrame> {
+    let mut dimensions = pivot.rows.clone();
+    dimensions.extend([pivot.column.clone(), GROUPING_ID.to_string()]);
+    let measures = if pivot.measures.is_empty() {
+        df.get_column_names()
+            .into_iter()
+            .filter(|c| !dimensions.iter().any(|d| d == c))
+            .map(|c| c.to_string())
+            .collect()
+    } else {
+        for m in &pivot.measures {
+            if df.column(m).is_err() {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "Pivot measure {m} is not requested"
+                )));
+            }
+        }
+        pivot.measures.clone()
+    };
+
+    let (cells, totals) = if pivot.totals {
+        let grouping_id = df.column(GROUPING_ID)?.cast(&DataType::UInt32)?;
+        let (cells, totals) = (
+            df.filter(&grouping_id.equal(0)?)?,
+            df.filter(&grouping_id.equal(1)?)?,
+        );
+        (cells, Some(totals))
+    } else {
+        (df, None)
+    };
+
+    // Column values in order of appearance
+    let mut values: Vec<Option<String>> = vec![];
+    let column = cells.column(&pivot.column)?.cast(&DataType::String)?;
+    for v in column.str()?.into_iter() {
+        let v = v.map(str::to_string);
+        if !values.contains(&v) {
+            values.push(v)
+        }
+    }
+    let mut ordered: Vec<Option<String>> = pivot
+        .order
+        .iter()
+        .map(|o| Some(o.clone()))
+        .filter(|o| values.contains(o))
+        .collect();
+    ordered.extend(values.into_iter().filter(|v| !ordered.contains(v)));
+
+    let mut keys: Vec<Expr> = pivot.rows.iter().map(|r| col(r)).collect();
+    let with_key = |lf: LazyFrame| {
+        if pivot.rows.is_empty() {
+            lf.with_column(lit(0).alias(NO_ROWS_KEY))
+        } else {
+            lf
+        }
+    };
+    if keys.is_empty() {
+        keys.push(col(NO_ROWS_KEY));
+    }
+    let cells = with_key(cells.lazy());
+    let mut join_args = JoinArgs::new(JoinType::Left);
+    join_args.join_nulls = true;
+
+    let name = |measure: &str, value: &str| {
+        if measures.len() == 1 {
+            value.to_string()
+        } else {
+            format!("{measure}_{}_{value}", pivot.column)
+        }
+    };
+    // Values can clash with the rows, with the totals or (eg "null") with each other
+    let mut taken: HashSet<String> = pivot.rows.iter().cloned().collect();
+    let total_names: Vec<String> = measures.iter().map(|m| name(m, TOTAL)).collect();
+    if pivot.totals {
+        taken.extend(total_names.iter().cloned());
+    }
+    let mut unique_name = |measure: &str, value: &str| {
+        let mut res = name(measure, value);
+        if taken.contains(&res) {
+            res = format!("{}_{res}", pivot.column);
+        }
+        let mut i = 1;
+        let base = res.clone();
+        while taken.contains(&res) {
+            i += 1;
+            res = format!("{base}_{i}");
+        }
+        taken.insert(res.clone());
+        res
+    };
+    let cells_of = |lf: LazyFrame, names: &[String]| {
+        let mut select = keys.clone();
+        select.extend(
+            measures
+                .iter()
+                .zip(names)
+                .map(|(m, name)| col(m).alias(name)),
+        );
+        lf.select(select)
+    };
+
+    let mut res = cells
+        .clone()
+        .select(&keys)
+        .unique_stable(None, UniqueKeepStrategy::First);
+
+    for value in ordered {
+        let (predicate, value) = match value {
+            Some(v) => (
+                col(&pivot.column).cast(DataType::String).eq(lit(v.clone())),
+                v,
+            ),
+            None => (col(&pivot.column).is_null(), "null".to_string()),
+        };
+        let names: Vec<String> = measures.iter().map(|m| unique_name(m, &value)).collect();
+        let part = cells_of(cells.clone().filter(predicate), &names);
+        res = res.join(part, keys.clone(), keys.clone(), join_args.clone());
+    }
+
+    if let Some(totals) = totals {
+        let part = cells_of(with_key(totals.lazy()), &total_names);
+        res = res.join(part, keys.clone(), keys.clone(), join_args.clone());
+    }
+
+    if pivot.rows.is_empty() {
+        res = res.drop([NO_ROWS_KEY]);
+    }
+
+    Ok(res.collect()?)
+}
@@ -1,6 +1,8 @@
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::errors::{UltiResult, UltimaErr};
+
 /// This type represents the convention.
 /// Outer elements are AND, inner elements are OR
 pub type AndOrFltrChain = Vec<Vec<FilterE>>;
@@ -33,6 +35,15 @@ pub enum FilterE {
         field: String,
         value: Vec<Option<String>>,
     },
+    /// The node and all of its descendants in a [crate::hierarchy::Hierarchy].
+    /// `level` of the node is required for level based hierarchies.
+    /// Resolved into one of the above with the hierarchies of the DataSet
+    Descendants {
+        hierarchy: String,
+        node: String,
+        #[serde(default)]
+        level: Option<String>,
+    },
 }
 
 impl FilterE {
@@ -48,10 +59,34 @@ impl FilterE {
                 field: c,
                 value: vs,
             } => fltr_not_in(c, vs),
+            // Must have been resolved, see [crate::hierarchy::resolve_descendants]
+            FilterE::Descendants { hierarchy, .. } => {
+                let msg = unresolved_msg(hierarchy);
+                lit(false).map(
+                    move |_| Err(PolarsError::InvalidOperation(msg.clone().into())),
+                    GetOutput::same_type(),
+                )
+            }
         }
     }
 }
 
+pub(crate) fn unresolved_msg(hierarchy: &str) -> String {
+    format!("Descendants filter of hierarchy {hierarchy} has not been resolved")
+}
+
+/// Errors if `chain` holds a [FilterE::Descendants],
+/// which must be resolved with [crate::hierarchy::resolve_descendants] first
+pub fn check_resolved(chain: &AndOrFltrChain) -> UltiResult<()> {
+    match chain.iter().flatten().find_map(|f| match f {
+        FilterE::Descendants { hierarchy, .. } => Some(hierarchy),
+        _ => None,
+    }) {
+        Some(hierarchy) => Err(UltimaErr::InvalidRequest(unresolved_msg(hierarchy))),
+        None => Ok(()),
+    }
+}
+
 pub(crate) fn fltr_in(c: &str, vs: &Vec<Option<String>>) -> Expr {
     let s = Series::new("filter", vs);
     col(c).cast(DataType::String).is_in(s.lit())
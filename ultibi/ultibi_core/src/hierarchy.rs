@@ -0,0 +1,285 @@
+//! Hierarchies of dimensions, eg BookId -> Desk -> Country -> LegalEntity -> Group
+//!
+//! Requests refer to the nodes of a hierarchy via [FilterE::Descendants] and [Drill].
+//! Those are resolved into plain filters and groupby before the computation
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dimensions::{ComputedDimension, Dimension, DimensionExpr},
+    errors::{UltiResult, UltimaErr},
+    filters::{AndOrFltrChain, FilterE},
+    io::helpers::path_to_lf,
+    AggregationRequest,
+};
+
+/// Declared in [crate::DataSourceConfig], eg
+/// ```toml
+/// [[hierarchies]]
+/// name = "Organisation"
+/// kind = "levels"
+/// levels = ["Group", "LegalEntity", "Country", "Desk", "BookId"]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Hierarchy {
+    /// Drilling a [HierarchyKind::ParentChild] groups by a dimension of this name,
+    /// hence it must not be a column of the data
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: HierarchyKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum HierarchyKind {
+    /// Each level is a column of the data, top level first
+    Levels { levels: Vec<String> },
+    /// Nodes are values of `column`. Can be ragged
+    ParentChild {
+        column: String,
+        /// Child -> Parent. Roots are the parents which are not children
+        #[serde(default)]
+        parents: BTreeMap<String, String>,
+        /// CSV with `child` and `parent` columns, loaded into `parents` when the DataSet is built
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        #[serde(default = "child")]
+        child: String,
+        #[serde(default = "parent")]
+        parent: String,
+    },
+}
+
+fn child() -> String {
+    "Child".to_string()
+}
+
+fn parent() -> String {
+    "Parent".to_string()
+}
+
+/// Group by the children of a node of a hierarchy, and filter on its descendants
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Drill {
+    pub hierarchy: String,
+    /// Top level of the hierarchy if None
+    #[serde(default)]
+    pub node: Option<String>,
+    /// Level of the `node`. Required for level based hierarchies
+    #[serde(default)]
+    pub level: Option<String>,
+}
+
+impl Hierarchy {
+    /// Reads `path` of a parent-child hierarchy
+    pub fn load(mut self) -> UltiResult<Self> {
+        if let HierarchyKind::ParentChild {
+            parents,
+            path,
+            child,
+            parent,
+            ..
+        } = &mut self.kind
+        {
+            let Some(path) = path.take() else {
+                return Ok(self);
+            };
+            let cols = [child.clone(), parent.clone()];
+            let df = path_to_lf(&path, &cols, &[])
+                .select([col(child), col(parent)])
+                .drop_nulls(None)
+                .collect()?;
+            let children = df.column(child)?.str()?;
+            let their_parents = df.column(parent)?.str()?;
+            for (c, p) in children.into_iter().zip(their_parents) {
+                if let (Some(c), Some(p)) = (c, p) {
+                    parents.insert(c.to_string(), p.to_string());
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// The node and all of its descendants. Parent-child hierarchies only
+    fn descendants(parents: &BTreeMap<String, String>, node: &str) -> Vec<String> {
+        let mut res = vec![node.to_string()];
+        let mut seen = BTreeSet::from([node]);
+        let mut i = 0;
+        while i < res.len() {
+            for (c, p) in parents {
+                if p == &res[i] && seen.insert(c.as_str()) {
+                    res.push(c.clone())
+                }
+            }
+            i += 1;
+        }
+        res
+    }
+
+    fn unknown_node(&self, node: &str) -> UltimaErr {
+        UltimaErr::InvalidRequest(format!("Node {node} is not in hierarchy {}", self.name))
+    }
+
+    /// Level based hierarchies filter on the level column, which holds the descendants already
+    fn descendants_filter(&self, node: &str, level: Option<&str>) -> UltiResult<FilterE> {
+        match &self.kind {
+            HierarchyKind::Levels { levels } => {
+                let level = self.level(level)?;
+                Ok(FilterE::Eq {
+                    field: levels[level].clone(),
+                    value: Some(node.to_string()),
+                })
+            }
+            HierarchyKind::ParentChild {
+                column, parents, ..
+            } => {
+                if !parents.contains_key(node) && !parents.values().any(|p| p == node) {
+                    return Err(self.unknown_node(node));
+                }
+                Ok(FilterE::In {
+                    field: column.clone(),
+                    value: Self::descendants(parents, node)
+                        .into_iter()
+                        .map(Some)
+                        .collect(),
+                })
+            }
+        }
+    }
+
+    /// Index of the level
+    fn level(&self, level: Option<&str>) -> UltiResult<usize> {
+        let HierarchyKind::Levels { levels } = &self.kind else {
+            unreachable!("Only level based hierarchies have levels")
+        };
+        let level = level.ok_or_else(|| {
+            UltimaErr::InvalidRequest(format!(
+                "Level of the node is required for hierarchy {}",
+                self.name
+            ))
+        })?;
+        levels.iter().position(|l| l == level).ok_or_else(|| {
+            UltimaErr::InvalidRequest(format!("No level {level} in hierarchy {}", self.name))
+        })
+    }
+
+    /// (Dimension to group by, Filter on the descendants of the node)
+    fn drill(&self, drill: &Drill) -> UltiResult<(Dimension, Option<FilterE>)> {
+        let filter = drill
+            .node
+            .as_ref()
+            .map(|node| self.descendants_filter(node, drill.level.as_deref()))
+            .transpose()?;
+
+        let dimension = match &self.kind {
+            HierarchyKind::Levels { levels } => {
+                let below = match &drill.node {
+                    Some(_) => self.level(drill.level.as_deref())? + 1,
+                    None => 0,
+                };
+                let level = levels.get(below).ok_or_else(|| {
+                    UltimaErr::InvalidRequest(format!(
+                        "Can't drill below the last level of hierarchy {}",
+                        self.name
+                    ))
+                })?;
+                Dimension::Column(level.clone())
+            }
+            HierarchyKind::ParentChild {
+                column, parents, ..
+            } => {
+                let children: Vec<&String> = match &drill.node {
+                    Some(node) => parents
+                        .iter()
+                        .filter(|(_, p)| *p == node)
+                        .map(|(c, _)| c)
+                        .collect(),
+                    None => parents
+                        .values()
+                        .filter(|p| !parents.contains_key(*p))
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .collect(),
+                };
+                if children.is_empty() {
+                    return Err(UltimaErr::InvalidRequest(format!(
+                        "Node {} of hierarchy {} has no children",
+                        drill.node.as_deref().unwrap_or_default(),
+                        self.name
+                    )));
+                }
+                // Rows of the node itself (if any) keep their value
+                Dimension::Computed(ComputedDimension {
+                    name: self.name.clone(),
+                    column: column.clone(),
+                    expr: DimensionExpr::Groups {
+                        groups: children
+                            .into_iter()
+                            .map(|c| (c.clone(), Self::descendants(parents, c)))
+                            .collect(),
+                        default: None,
+                    },
+                })
+            }
+        };
+
+        Ok((dimension, filter))
+    }
+}
+
+fn lookup<'a>(hierarchies: &'a [Hierarchy], name: &str) -> UltiResult<&'a Hierarchy> {
+    hierarchies
+        .iter()
+        .find(|h| h.name == name)
+        .ok_or_else(|| UltimaErr::InvalidRequest(format!("No hierarchy {name}")))
+}
+
+/// Replaces every [FilterE::Descendants] of `filters` with a plain filter.
+/// Must be called wherever filters are accepted, since unresolved ones can't be evaluated
+pub fn resolve_descendants(
+    filters: &mut AndOrFltrChain,
+    hierarchies: &[Hierarchy],
+) -> UltiResult<()> {
+    for f in filters.iter_mut().flatten() {
+        if let FilterE::Descendants {
+            hierarchy,
+            node,
+            level,
+        } = f
+        {
+            *f = lookup(hierarchies, hierarchy)?.descendants_filter(node, level.as_deref())?;
+        }
+    }
+    Ok(())
+}
+
+impl AggregationRequest {
+    /// Resolves [FilterE::Descendants] (of the filters and of the overrides) and
+    /// [AggregationRequest::drill]
+    pub(crate) fn with_hierarchies(mut self, hierarchies: &[Hierarchy]) -> UltiResult<Self> {
+        resolve_descendants(&mut self.filters, hierarchies)?;
+        for o in self.overrides.iter_mut() {
+            resolve_descendants(o.filters_mut(), hierarchies)?;
+        }
+
+        if let Some(drill) = self.drill.take() {
+            let (dimension, filter) = lookup(hierarchies, &drill.hierarchy)?.drill(&drill)?;
+            if self.groupby.iter().any(|g| g.name() == dimension.name()) {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "Can't drill into {}, since it's in the groupby already",
+                    dimension.name()
+                )));
+            }
+            self.groupby.push(dimension);
+            self.filters.extend(filter.map(|f| vec![f]));
+        }
+
+        Ok(self)
+    }
+}
@@ -43,6 +43,8 @@ pub fn config_build_validate_prepare<DS: NewSourcedDataSet>(
         data.prepare().expect("Failed to Prepare Frame");
         data.collect().expect("Failed to Prepare Frame");
     }
+    data.refresh_snapshots()
+        .expect("Failed to load the snapshots");
 
     data
 }
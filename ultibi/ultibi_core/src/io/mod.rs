@@ -11,6 +11,9 @@ use std::path::Path;
 
 use crate::{
     datasource::{DataSource, SourceVariant},
+    hierarchy::Hierarchy,
+    snapshot::{Snapshot, SNAPSHOT_COLUMN},
+    window::WindowMeasure,
     Measure,
 };
 use helpers::{empty_frame, finish, path_to_lf};
@@ -53,6 +56,16 @@ pub enum DataSourceConfig {
         /// parameters to be used for build and prepare
         #[serde(default)]
         build_params: BTreeMap<String, String>,
+        /// See [crate::hierarchy]
+        #[serde(default)]
+        hierarchies: Vec<Hierarchy>,
+        /// See [crate::window]
+        #[serde(default)]
+        windows: Vec<WindowMeasure>,
+        /// Dated files, tagged with their date in the `snapshot_column` build param.
+        /// See [crate::snapshot]
+        #[serde(default)]
+        snapshots: Vec<Snapshot>,
     },
 }
 
@@ -65,6 +78,45 @@ impl DataSourceConfig {
             panic!("Can only get mutable Source Type of a CSV config")
         }
     }
+    #[allow(irrefutable_let_patterns)]
+    pub fn hierarchies(&self) -> &[Hierarchy] {
+        if let DataSourceConfig::CSV { hierarchies, .. } = self {
+            hierarchies
+        } else {
+            &[]
+        }
+    }
+    #[allow(irrefutable_let_patterns)]
+    pub fn windows(&self) -> &[WindowMeasure] {
+        if let DataSourceConfig::CSV { windows, .. } = self {
+            windows
+        } else {
+            &[]
+        }
+    }
+    /// Files the data is read from (eg to watch them for changes)
+    #[allow(irrefutable_let_patterns)]
+    pub fn input_files(&self) -> Vec<String> {
+        if let DataSourceConfig::CSV {
+            file_paths,
+            attr,
+            hms,
+            snapshots,
+            ..
+        } = self
+        {
+            file_paths
+                .iter()
+                .chain(snapshots.iter().flat_map(|s| s.files.iter()))
+                .chain(attr.iter())
+                .chain(hms.iter())
+                .cloned()
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
     /// build's DataSet, prepares depending on DataSourceType
     ///
     /// Returns:
@@ -83,8 +135,11 @@ impl DataSourceConfig {
                 f1_numeric_cols: f64_cols,
                 build_params,
                 source_type,
+                hierarchies: _,
+                windows: _,
+                snapshots,
             } => {
-                for f in files.iter() {
+                for f in files.iter().chain(snapshots.iter().flat_map(|s| &s.files)) {
                     assert!(
                         Path::new(&f).exists(),
                         "{f} : File path doesn't exist. Check your config file"
@@ -95,15 +150,32 @@ impl DataSourceConfig {
                         str_cols.push(s.to_string())
                     }
                 }
+                let snapshot_column = build_params.get(SNAPSHOT_COLUMN).cloned();
+                assert!(
+                    snapshots.is_empty() || snapshot_column.is_some(),
+                    "{SNAPSHOT_COLUMN} build param is required with snapshots. Check your config file"
+                );
+                // Dates must be comparable to the filter values of as_of
+                if let Some(c) = &snapshot_column {
+                    if !str_cols.contains(c) {
+                        str_cols.push(c.to_string())
+                    }
+                }
 
-                let concatinated_frame = concat_lf_diagonal(
-                    &files
-                        .iter()
-                        .map(|f| path_to_lf(f, &str_cols, &f64_cols))
-                        .collect::<Vec<LazyFrame>>(),
-                    Default::default(),
-                )
-                .expect("Failed to concatinate provided frames"); // <- Ok to panic upon server startup
+                let mut frames = files
+                    .iter()
+                    .map(|f| path_to_lf(f, &str_cols, &f64_cols))
+                    .collect::<Vec<LazyFrame>>();
+                for Snapshot { date, files } in snapshots {
+                    let column = snapshot_column.as_deref().unwrap_or_default();
+                    frames.extend(files.iter().map(|f| {
+                        path_to_lf(f, &str_cols, &f64_cols)
+                            .with_column(lit(date.clone()).alias(column))
+                    }));
+                }
+
+                let concatinated_frame = concat_lf_diagonal(&frames, Default::default())
+                    .expect("Failed to concatinate provided frames"); // <- Ok to panic upon server startup
 
                 let mut tmp = str_cols.clone();
                 tmp.extend(a2h.clone());
@@ -5,17 +5,23 @@
 pub mod add_row;
 pub mod aggregations;
 pub mod cache;
+pub mod calculated;
 mod datarequest;
 pub mod dataset;
 pub mod datasource;
+pub mod dimensions;
 pub mod errors;
 pub mod execution;
 pub mod filters;
 pub mod helpers;
+pub mod hierarchy;
 pub mod io;
 mod measure;
 pub mod overrides;
+mod parameters;
 pub mod prelude;
 pub mod reports;
+pub mod snapshot;
+pub mod window;
 
 pub use crate::prelude::*;
@@ -1,12 +1,13 @@
 use derivative::Derivative;
 //use derivative::Derivative;
-use polars::prelude::{col, Expr, PolarsError, PolarsResult};
-use serde::Serialize;
+use polars::prelude::{col, Expr, PolarsResult};
+use serde::{Deserialize, Serialize};
 //use serde::Serialize;
 use std::{collections::BTreeMap, sync::Arc};
 
 use crate::{
     aggregations::{Aggregation, AggregationName},
+    errors::{UltiResult, UltimaErr},
     CPM,
 };
 
@@ -20,14 +21,160 @@ pub type MeasuresMap = BTreeMap<MeasureName, Measure>;
 //type Calculator = Box<dyn Fn(&OCP) -> Expr + Send + Sync>;
 pub type Calculator = Arc<dyn Fn(&CPM) -> PolarsResult<Expr> + Send + Sync>;
 
-/// This struct is purely for DataSet descriptive purposes(for now).
 /// Recall measure may take parameters in form of HashMap<paramName, paramValue>
-/// This struct returns all possible paramNames for the given Dataset (for UI purposes only)
+/// This struct describes a param of the given Dataset. It is used by the UI to render inputs
+/// and by [validate_calc_params] to reject malformed values before computing
 #[derive(Debug, Default, Clone, Serialize, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CalcParameter {
     pub name: String,
     pub default: Option<String>,
+    /// Human readable description of the expected value
     pub type_hint: Option<String>,
+    pub param_type: CalcParamType,
+}
+
+/// Calc param values are strings. Apart from [CalcParamType::String] and [CalcParamType::Enum]
+/// they are parsed as JSON, eg `"0.5"`, `"true"` or `"[1, 2]"`
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CalcParamType {
+    /// Not validated
+    #[default]
+    Any,
+    /// Plain string, eg a currency code of `len` 3
+    String {
+        len: Option<usize>,
+    },
+    Bool,
+    Float,
+    /// One of the `choices`, eg BCBS or CRR2
+    Enum {
+        choices: Vec<String>,
+    },
+    /// Array of floats, eg `[0.1, 0.2]`. Length is not checked if `len` is None
+    Vector {
+        len: Option<usize>,
+    },
+    /// Array of strings, eg `["BGN", "DKK"]`
+    Strings,
+    /// Matrix of floats as serialised by ndarray, eg `{"v": 1, "dim": [2, 2], "data": [1, 0.5, 0.5, 1]}`
+    /// Dimensions which are None are not checked
+    Matrix {
+        rows: Option<usize>,
+        cols: Option<usize>,
+    },
+    /// Any JSON object
+    Json,
+}
+
+impl CalcParamType {
+    /// Returns the reason if `value` is not of this type
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        use serde_json::Value;
+
+        let json = || {
+            serde_json::from_str::<Value>(value).map_err(|_| format!("{value} is not valid JSON"))
+        };
+        let floats = |v: &Value| {
+            v.as_array()
+                .filter(|arr| arr.iter().all(Value::is_number))
+                .map(Vec::len)
+        };
+        let check_len = |what: &str, expected: &Option<usize>, actual: usize| match expected {
+            Some(len) if *len != actual => Err(format!("expected {what} of {len}, got {actual}")),
+            _ => Ok(()),
+        };
+
+        match self {
+            CalcParamType::Any => Ok(()),
+            CalcParamType::String { len } => check_len("length", len, value.chars().count()),
+            CalcParamType::Bool => serde_json::from_str::<bool>(value)
+                .map(|_| ())
+                .map_err(|_| format!("expected true or false, got {value}")),
+            CalcParamType::Float => serde_json::from_str::<f64>(value)
+                .map(|_| ())
+                .map_err(|_| format!("expected a number, got {value}")),
+            CalcParamType::Enum { choices } => match choices.iter().any(|c| c == value) {
+                true => Ok(()),
+                false => Err(format!("expected one of {choices:?}, got {value}")),
+            },
+            CalcParamType::Vector { len } => {
+                let actual = floats(&json()?).ok_or("expected an array of numbers")?;
+                check_len("length", len, actual)
+            }
+            CalcParamType::Strings => match json()?.as_array() {
+                Some(arr) if arr.iter().all(Value::is_string) => Ok(()),
+                _ => Err("expected an array of strings".to_string()),
+            },
+            CalcParamType::Matrix { rows, cols } => {
+                let v = json()?;
+                let dim = v["dim"]
+                    .as_array()
+                    .and_then(|d| match d.as_slice() {
+                        [r, c] => Some((r.as_u64()? as usize, c.as_u64()? as usize)),
+                        _ => None,
+                    })
+                    .ok_or("expected a matrix with \"dim\": [rows, cols]")?;
+                let data = floats(&v["data"]).ok_or("expected \"data\" as an array of numbers")?;
+                if data != dim.0 * dim.1 {
+                    return Err(format!("{data} values don't fill {}x{}", dim.0, dim.1));
+                }
+                check_len("rows", rows, dim.0)?;
+                check_len("cols", cols, dim.1)
+            }
+            CalcParamType::Json => json().map(|_| ()),
+        }
+    }
+}
+
+/// Checks the calc params of a request against the ones `declared` by the DataSet.
+/// Unknown params are rejected only if `strict`, since then they are likely to be typos
+pub fn validate_calc_params(
+    declared: &[CalcParameter],
+    params: &CPM,
+    strict: bool,
+) -> UltiResult<()> {
+    for (name, value) in params {
+        match declared.iter().find(|p| &p.name == name) {
+            Some(p) => {
+                p.param_type
+                    .validate(value)
+                    .map_err(|reason| UltimaErr::InvalidCalcParam {
+                        param: name.clone(),
+                        reason,
+                    })?
+            }
+            None if strict => {
+                return Err(UltimaErr::UnknownCalcParam {
+                    param: name.clone(),
+                })
+            }
+            None => (),
+        }
+    }
+    Ok(())
+}
+
+/// Describes a measure to the user. Has no effect on the computation
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct MeasureMetadata {
+    pub description: Option<String>,
+    /// Regulatory reference, eg MAR21.4
+    pub reference: Option<String>,
+    /// Unit or currency of the result. The name of a calc param (eg `reporting_ccy`)
+    /// stands for the value of that param in the request
+    pub unit: Option<String>,
+    /// Number format for the UI, eg `#,##0.00`
+    pub format: Option<String>,
+    /// Path of the measure in the UI tree, eg `["SBM", "GIRR", "Delta"]`. Top level if empty
+    pub category: Vec<String>,
+    /// Internal measures (eg building blocks of other measures) are hidden in the UI,
+    /// but can still be requested
+    pub hidden: bool,
 }
 
 /// Measure is the essentially a Struct of a calculator and a name
@@ -57,6 +204,9 @@ pub struct BaseMeasure {
     /// Calc params
     /// Will determine the list of Params in the UI
     pub calc_params: Vec<CalcParameter>,
+
+    /// Description, units etc. Used by the UI only
+    pub metadata: MeasureMetadata,
 }
 
 /// Dependant Measure cannot be computed directly. Instead it is broken down into it's parents
@@ -90,6 +240,9 @@ pub struct DependantMeasure {
     /// Calc params
     /// Will determine the list of Params in the UI
     pub calc_params: Vec<CalcParameter>,
+
+    /// Description, units etc. Used by the UI only
+    pub metadata: MeasureMetadata,
 }
 
 /// AggRequest --> execute -->  split DependantMeasure into BaseMeasure's (BaseMeasure leave as they are) --> execute_aggregation --> combine back into original request
@@ -150,6 +303,18 @@ impl Measure {
             | Measure::Dependant(DependantMeasure { calc_params, .. }) => calc_params,
         }
     }
+    pub fn metadata(&self) -> &MeasureMetadata {
+        match self {
+            Measure::Base(BaseMeasure { metadata, .. })
+            | Measure::Dependant(DependantMeasure { metadata, .. }) => metadata,
+        }
+    }
+    pub fn metadata_mut(&mut self) -> &mut MeasureMetadata {
+        match self {
+            Measure::Base(BaseMeasure { metadata, .. })
+            | Measure::Dependant(DependantMeasure { metadata, .. }) => metadata,
+        }
+    }
     pub fn calculator(&self) -> &Calculator {
         match self {
             Measure::Base(BaseMeasure { calculator, .. })
@@ -166,8 +331,42 @@ impl Default for BaseMeasure {
             calc_params: vec![],
             aggregation: None,
             precomputefilter: None,
+            metadata: MeasureMetadata::default(),
+        }
+    }
+}
+
+impl BaseMeasure {
+    pub fn with_metadata(mut self, metadata: MeasureMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+impl DependantMeasure {
+    pub fn new(
+        name: impl Into<MeasureName>,
+        calculator: Calculator,
+        depends_upon: Vec<(String, String)>,
+    ) -> Self {
+        DependantMeasure {
+            name: name.into(),
+            calculator,
+            depends_upon,
+            calc_params: vec![],
+            metadata: MeasureMetadata::default(),
         }
     }
+
+    pub fn with_calc_params(mut self, calc_params: Vec<CalcParameter>) -> Self {
+        self.calc_params = calc_params;
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: MeasureMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
 }
 
 pub fn derive_basic_measures_vec(dataset_numer_cols: Vec<String>) -> Vec<Measure> {
@@ -200,6 +399,8 @@ pub(crate) struct ProcessedBaseMeasure {
     pub name: String,
     pub calculator: Expr,
     pub precomputefilter: Option<Expr>,
+    /// See [Aggregation::rollup]
+    pub rollup: Option<Expr>,
 }
 
 pub(crate) struct ProcessedDependantMeasure {
@@ -213,55 +414,71 @@ pub(crate) struct ProcessedDependantMeasure {
 pub(crate) fn agg_measure_lookup<'b, 'a: 'b>(
     requested_measures: &'b [(MeasureName, AggregationName)],
     all_availiable_measures: &'a MeasuresMap,
-) -> PolarsResult<
+) -> UltiResult<
     Vec<(
         (&'b MeasureName, &'b AggregationName),
         (&'a Measure, &'a Aggregation),
     )>,
 > {
-    let res = requested_measures.iter()
+    let res = requested_measures
+        .iter()
         .map(|(requested_measure, requested_action)| {
-
             // Lookup requested measure from all_availiable_measures by name
-            let Some(looked_up_measure) = all_availiable_measures.get(requested_measure as &str) else {
-                return Err(PolarsError::ComputeError(format!("No measure {requested_measure} exists for the dataset. Availiable measures are: {:?}",
-                    all_availiable_measures.keys()).into()))
+            let Some(looked_up_measure) = all_availiable_measures.get(requested_measure as &str)
+            else {
+                return Err(UltimaErr::UnknownMeasure {
+                    measure: requested_measure.clone(),
+                    available: all_availiable_measures.keys().cloned().collect(),
+                });
             };
 
-            // If measure has predefined aggregation, check that requested aggregation matches it          
+            // If measure has predefined aggregation, check that requested aggregation matches it
             if let Some(default_action) = looked_up_measure.aggregation() {
                 if default_action != requested_action {
-                    return Err(PolarsError::ComputeError(format!("Measure {requested_measure} supports only {default_action} aggregation,
-                    but {requested_action} requested").into()))
+                    return Err(UltimaErr::UnsupportedAggregation {
+                        measure: requested_measure.clone(),
+                        supported: default_action.clone(),
+                        requested: requested_action.clone(),
+                    });
                 }
             }
 
             // Lookup action from the list of supported actions
             let Some(a) = crate::aggregations::BASE_CALCS.get(requested_action.as_str()) else {
-                return Err(PolarsError::ComputeError(format!("No action {requested_action} supported. Supported actions are: {:?}",
-                crate::aggregations::BASE_CALCS.keys()).into()))
+                return Err(UltimaErr::UnknownAggregation {
+                    aggregation: requested_action.clone(),
+                    available: crate::aggregations::BASE_CALCS
+                        .keys()
+                        .map(|a| a.to_string())
+                        .collect(),
+                });
             };
 
             match looked_up_measure {
                 Measure::Base(_) => Ok(vec![(
-                    (requested_measure, requested_action), (looked_up_measure, a))]
-                ),
-                Measure::Dependant(dm) =>{
-                    // TODO 
+                    (requested_measure, requested_action),
+                    (looked_up_measure, a),
+                )]),
+                Measure::Dependant(dm) => {
+                    // TODO
                     let children = &dm.depends_upon;
                     // get children
-                    let children_lookup =
-                        agg_measure_lookup(children, all_availiable_measures)?;
+                    let children_lookup = agg_measure_lookup(children, all_availiable_measures)?;
                     // Not adding self because dependant measures to be expressed separately
                     //children_lookup.push(((requested_measure, requested_action), (looked_up_measure, a)));
 
                     Ok(children_lookup)
-
                 }
             }
-        }
-        )
-        .collect::<PolarsResult<Vec<Vec<((&'b MeasureName, &'b AggregationName), (&'a Measure, &'a Aggregation))>>>>()?;
+        })
+        .collect::<UltiResult<
+            Vec<
+                Vec<(
+                    (&'b MeasureName, &'b AggregationName),
+                    (&'a Measure, &'a Aggregation),
+                )>,
+            >,
+        >>()?;
 
     Ok(res.into_iter().flatten().collect())
 }
@@ -318,6 +535,7 @@ pub(crate) fn agg_measure_to_expr(
 
     match measure {
         Measure::Base(m) => Ok(ProcessedMeasure::Base(ProcessedBaseMeasure {
+            rollup: agg.rollup(measure.name()),
             name: new_name,
             calculator,
             precomputefilter: m.precomputefilter.clone(),
@@ -1,6 +1,7 @@
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::errors::{UltiResult, UltimaErr};
 use crate::filters::{fltr_chain, AndOrFltrChain};
 
 /// DataSet must have column present
@@ -20,10 +21,16 @@ use crate::filters::{fltr_chain, AndOrFltrChain};
 pub struct Override {
     field: String,
     value: String,
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<Vec<crate::filters::FilterE>>))]
     filters: AndOrFltrChain,
 }
 
 impl Override {
+    /// Eg to resolve [crate::filters::FilterE::Descendants]
+    pub(crate) fn filters_mut(&mut self) -> &mut AndOrFltrChain {
+        &mut self.filters
+    }
+
     pub fn override_builder(&self, val: Expr) -> Expr {
         // Empty filter means the whole column will get overwritten
         let fltr = fltr_chain(&self.filters);
@@ -42,7 +49,25 @@ impl Override {
     pub fn lf_with_overwrite(&self, lf: LazyFrame) -> PolarsResult<LazyFrame> {
         let schema = lf.schema()?;
         let dt = schema.try_get(self.field.as_str())?;
+        let new_col_as_expr = self.override_builder(self.value_expr(dt)?);
+        Ok(lf.with_column(new_col_as_expr))
+    }
 
+    /// Same as [Override::lf_with_overwrite], but a missing column or a value which doesn't parse
+    /// is an [UltimaErr::InvalidOverride]
+    pub(crate) fn lf_with_checked_overwrite(&self, lf: LazyFrame) -> UltiResult<LazyFrame> {
+        let invalid = |e: PolarsError| UltimaErr::InvalidOverride {
+            field: self.field.clone(),
+            reason: e.to_string(),
+        };
+        let schema = lf.schema()?;
+        let dt = schema.try_get(self.field.as_str()).map_err(invalid)?;
+        let new_col_as_expr = self.override_builder(self.value_expr(dt).map_err(invalid)?);
+        Ok(lf.with_column(new_col_as_expr))
+    }
+
+    /// `value` as a literal of the column's type `dt`
+    fn value_expr(&self, dt: &DataType) -> PolarsResult<Expr> {
         let mut lt = Expr::Literal(LiteralValue::try_from(string_to_any(
             &self.value,
             dt,
@@ -52,8 +77,7 @@ impl Override {
         if let DataType::List(_) = dt {
             lt = lt.implode()
         }
-        let new_col_as_expr = self.override_builder(lt);
-        Ok(lf.with_column(new_col_as_expr))
+        Ok(lt)
     }
 }
 
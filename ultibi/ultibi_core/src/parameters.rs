@@ -0,0 +1,190 @@
+//! Parameterised requests (aka templates)
+//!
+//! Filters, calc_params and overrides of an [AggregationRequest] can contain placeholders,
+//! eg `{{cob}}` or `{{desk}}`, declared in [AggregationRequest::parameters].
+//! [AggregationRequest::with_parameters] validates the values and substitutes them.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    errors::{UltiResult, UltimaErr},
+    AggregationRequest,
+};
+
+/// Only these fields of a request can contain placeholders
+const PARAMETERISED_FIELDS: [&str; 5] =
+    ["filters", "calc_params", "overrides", "as_of", "compare_to"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ParameterType {
+    #[default]
+    String,
+    Number,
+    Integer,
+    Bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TemplateParameter {
+    /// Used as `{{name}}` in the request
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub param_type: ParameterType,
+    /// If None, the parameter is required
+    #[serde(default)]
+    pub default: Option<String>,
+    /// If not empty, the value must be one of these (eg list of desks)
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl TemplateParameter {
+    pub fn is_required(&self) -> bool {
+        self.default.is_none()
+    }
+
+    /// Checks the value against the type and options of the parameter
+    pub fn validate(&self, value: &str) -> UltiResult<()> {
+        let valid_type = match self.param_type {
+            ParameterType::String => true,
+            ParameterType::Number => value.parse::<f64>().is_ok(),
+            ParameterType::Integer => value.parse::<i64>().is_ok(),
+            ParameterType::Bool => value.parse::<bool>().is_ok(),
+        };
+        if !valid_type {
+            return Err(UltimaErr::InvalidParameter {
+                name: self.name.clone(),
+                reason: format!(
+                    "Parameter {} expects {:?}, got {value}",
+                    self.name, self.param_type
+                ),
+            });
+        }
+        if !self.options.is_empty() && !self.options.iter().any(|o| o == value) {
+            return Err(UltimaErr::InvalidParameter {
+                name: self.name.clone(),
+                reason: format!(
+                    "Parameter {} must be one of {:?}, got {value}",
+                    self.name, self.options
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl AggregationRequest {
+    /// Parameters without a default. The UI must ask for these
+    pub fn required_parameters(&self) -> Vec<&TemplateParameter> {
+        self.parameters.iter().filter(|p| p.is_required()).collect()
+    }
+
+    /// Names of all the placeholders used in the request
+    pub fn placeholders(&self) -> UltiResult<BTreeSet<String>> {
+        let mut res = BTreeSet::new();
+        let mut value = serde_json::to_value(self)?;
+        for field in PARAMETERISED_FIELDS {
+            if let Some(v) = value.get_mut(field) {
+                walk_strings(v, &mut |s| res.extend(placeholders(s)));
+            }
+        }
+        Ok(res)
+    }
+
+    /// Validates `values` and substitutes them (or defaults) in place of the placeholders.
+    /// The resulting request has no parameters
+    pub fn with_parameters(&self, values: &BTreeMap<String, String>) -> UltiResult<Self> {
+        if let Some(unknown) = values
+            .keys()
+            .find(|k| !self.parameters.iter().any(|p| &p.name == *k))
+        {
+            return Err(UltimaErr::InvalidParameter {
+                name: unknown.clone(),
+                reason: format!("Unknown parameter {unknown}"),
+            });
+        }
+
+        let mut resolved = BTreeMap::new();
+        for p in &self.parameters {
+            let value = values.get(&p.name).or(p.default.as_ref()).ok_or_else(|| {
+                UltimaErr::InvalidParameter {
+                    name: p.name.clone(),
+                    reason: format!("Missing parameter {}", p.name),
+                }
+            })?;
+            p.validate(value)?;
+            resolved.insert(p.name.clone(), value.clone());
+        }
+
+        if let Some(undeclared) = self
+            .placeholders()?
+            .into_iter()
+            .find(|name| !resolved.contains_key(name))
+        {
+            return Err(UltimaErr::InvalidParameter {
+                reason: format!("Placeholder {{{{{undeclared}}}}} is not declared in parameters"),
+                name: undeclared,
+            });
+        }
+
+        let mut value = serde_json::to_value(self)?;
+        for field in PARAMETERISED_FIELDS {
+            if let Some(v) = value.get_mut(field) {
+                walk_strings(v, &mut |s| *s = substitute(s, &resolved));
+            }
+        }
+        let mut res: AggregationRequest = serde_json::from_value(value)?;
+        res.parameters = vec![];
+        Ok(res)
+    }
+}
+
+fn walk_strings(value: &mut Value, f: &mut impl FnMut(&mut String)) {
+    match value {
+        Value::String(s) => f(s),
+        Value::Array(a) => a.iter_mut().for_each(|v| walk_strings(v, f)),
+        Value::Object(o) => o.values_mut().for_each(|v| walk_strings(v, f)),
+        _ => (),
+    }
+}
+
+/// Names between `{{` and `}}`
+fn placeholders(s: &str) -> Vec<String> {
+    let mut res = vec![];
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        res.push(after[..end].trim().to_string());
+        rest = &after[end + 2..];
+    }
+    res
+}
+
+fn substitute(s: &str, values: &BTreeMap<String, String>) -> String {
+    let mut res = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        res.push_str(&rest[..start]);
+        match values.get(after[..end].trim()) {
+            Some(v) => res.push_str(v),
+            None => res.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    res.push_str(rest);
+    res
+}
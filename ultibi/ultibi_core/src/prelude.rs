@@ -5,6 +5,7 @@ pub use super::execution::*;
 pub use super::helpers::searches::*;
 pub use super::io::*;
 pub use super::measure::*;
+pub use super::parameters::*;
 
 //Reexports
 pub use polars;
@@ -0,0 +1,178 @@
+//! Several dated snapshots (eg COB dates) of the data in one DataSet.
+//!
+//! Rows of the snapshots are told apart by the column named in the `snapshot_column` build param.
+//! Snapshots are loaded from the `[[snapshots]]` of the [crate::DataSourceConfig], or appended
+//! (see [crate::DataSet::append_frame]) with the column set.
+//!
+//! A request is on one snapshot, [AggregationRequest::as_of] (the last loaded if None).
+//! With [AggregationRequest::compare_to] the request is computed for each of the two snapshots
+//! on its own, so that non linear measures (eg FRTB charges) are not differenced row by row,
+//! and the results are joined on the groupby.
+
+use polars::prelude::{col, lit, DataFrame, DataType, IntoLazy, JoinArgs, JoinCoalesce, JoinType};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{UltiResult, UltimaErr},
+    execution::{
+        context::ExecutionContext,
+        execute_agg::{_finish_agg, exec_resolved},
+    },
+    filters::FilterE,
+    AggregationRequest, DataSet, GROUPING_ID,
+};
+
+/// Build param which names the column of the snapshots
+pub const SNAPSHOT_COLUMN: &str = "snapshot_column";
+/// Appended to a measure column for its value in [AggregationRequest::compare_to]
+pub const COMPARE_SUFFIX: &str = "_compare";
+/// Appended to a measure column for its change since [AggregationRequest::compare_to]
+pub const CHANGE_SUFFIX: &str = "_change";
+
+/// Joins the results of requests without groupby
+const NO_KEYS: &str = "__no_keys__";
+
+/// eg
+/// ```toml
+/// [[snapshots]]
+/// date = "2024-01-31"
+/// files = ["./data/Delta_20240131.csv"]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Value of the snapshot column for the rows of `files`
+    pub date: String,
+    pub files: Vec<String>,
+}
+
+impl AggregationRequest {
+    /// Restricts the request to the rows of one snapshot
+    fn on_snapshot(mut self, column: &str, date: &str) -> Self {
+        self.filters.push(vec![FilterE::Eq {
+            field: column.to_string(),
+            value: Some(date.to_string()),
+        }]);
+        self.as_of = None;
+        self.compare_to = None;
+        self
+    }
+
+    /// [AggregationRequest::as_of] becomes a filter, unless snapshots are compared
+    pub(crate) fn with_snapshot<DS: DataSet + ?Sized>(mut self, data: &DS) -> UltiResult<Self> {
+        let Some(column) = data.snapshot_column() else {
+            if self.as_of.is_some() || self.compare_to.is_some() {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "DataSet doesn't hold snapshots. Set the {SNAPSHOT_COLUMN} build param"
+                )));
+            }
+            return Ok(self);
+        };
+        let snapshots = data.snapshots()?;
+        if let Some(unknown) = self
+            .as_of
+            .iter()
+            .chain(self.compare_to.iter())
+            .find(|d| !snapshots.contains(d))
+        {
+            return Err(UltimaErr::InvalidRequest(format!(
+                "No snapshot {unknown}. Available snapshots are: {}",
+                snapshots.join(", ")
+            )));
+        }
+        let Some(as_of) = self.as_of.clone().or_else(|| snapshots.last().cloned()) else {
+            // No data at all
+            return Ok(self);
+        };
+        if self.compare_to.is_some() {
+            // See [exec_comparison]
+            self.as_of = Some(as_of);
+            return Ok(self);
+        }
+        Ok(self.on_snapshot(column, &as_of))
+    }
+}
+
+/// Computes `req` for [AggregationRequest::as_of] and [AggregationRequest::compare_to],
+/// then joins the results
+pub(crate) fn exec_comparison<DS: DataSet + ?Sized>(
+    data: &DS,
+    mut req: AggregationRequest,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> UltiResult<DataFrame> {
+    let (Some(column), Some(compare_to), Some(as_of)) = (
+        data.snapshot_column(),
+        req.compare_to.take(),
+        req.as_of.take(),
+    ) else {
+        unreachable!("Comparisons are validated (and as_of set) in with_snapshot")
+    };
+    // Applied to the joined results
+    let hide_zeros = std::mem::take(&mut req.hide_zeros);
+    let pivot = req.pivot.take();
+    let groupby = req.group_by();
+
+    let current = exec_resolved(data, req.clone().on_snapshot(column, &as_of), prepare, ctx)?;
+    let previous = exec_resolved(data, req.on_snapshot(column, &compare_to), prepare, ctx)?;
+
+    let (keys, measures): (Vec<String>, Vec<String>) = current
+        .get_column_names()
+        .into_iter()
+        .map(str::to_string)
+        .partition(|c| groupby.contains(c) || c == GROUPING_ID);
+
+    let mut previous = previous;
+    for m in &measures {
+        previous.rename(m, &format!("{m}{COMPARE_SUFFIX}"))?;
+    }
+    // Without groupby there is at most one row each, which is joined on a constant
+    let mut on = keys.iter().map(|k| col(k)).collect::<Vec<_>>();
+    if keys.is_empty() {
+        on.push(col(NO_KEYS));
+    }
+    let with_key = |df: DataFrame| {
+        let lf = df.lazy();
+        if keys.is_empty() {
+            lf.with_column(lit(0).alias(NO_KEYS))
+        } else {
+            lf
+        }
+    };
+    let mut args = JoinArgs::from(JoinType::Outer).with_coalesce(JoinCoalesce::CoalesceColumns);
+    args.join_nulls = true;
+    let mut joined = with_key(current).join(with_key(previous), on.clone(), on, args);
+    if keys.is_empty() {
+        joined = joined.drop([NO_KEYS]);
+    }
+    let joined = joined.collect()?;
+
+    let mut columns = keys;
+    let mut measure_columns = vec![];
+    let mut changes = vec![];
+    for m in measures {
+        let compared = format!("{m}{COMPARE_SUFFIX}");
+        measure_columns.extend([m.clone(), compared.clone()]);
+        // Eg lists have no change
+        if joined.column(&m)?.dtype().is_numeric() {
+            let change = format!("{m}{CHANGE_SUFFIX}");
+            // A row missing from one of the snapshots is a change from (or to) zero
+            changes.push(
+                (col(&m).cast(DataType::Float64).fill_null(lit(0.))
+                    - col(&compared).cast(DataType::Float64).fill_null(lit(0.)))
+                .alias(&change),
+            );
+            measure_columns.push(change);
+        }
+    }
+    columns.extend(measure_columns.iter().cloned());
+
+    _finish_agg(
+        joined,
+        vec![changes],
+        vec![],
+        &columns,
+        hide_zeros.then_some(&measure_columns[..]),
+        pivot.as_ref(),
+        ctx,
+    )
+}
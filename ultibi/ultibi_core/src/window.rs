@@ -0,0 +1,397 @@
+//! Measures evaluated over partitions of the result of a request, once it's aggregated,
+//! eg the share of each desk in the total of its legal entity, or the rank of desks by SA Charge within a region.
+//!
+//! Rows of different grouping sets are never in the same partition, so windows work with totals:
+//! the share of a subtotal is relative to the other subtotals of the same level.
+//!
+//! The parent of a share is the sum of its partition only for measures which add up (eg `["Balance", "sum"]`).
+//! Others (eg FRTB charges) are recomputed by the `partition_by` of the window, see [join_parents].
+//!
+//! A [crate::DataSet] can declare windows (see [crate::DataSet::windows]), which are then requested
+//! like any other measure, eg `["SA Charge Rank", "scalar"]`.
+
+use polars::{
+    chunked_array::ops::SortMultipleOptions,
+    prelude::{
+        col, lit, when, DataFrame, DataType, Expr, IntoLazy, JoinArgs, JoinType, RankMethod,
+        RankOptions, NULL,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aggregations::{AggregationName, BASE_CALCS},
+    datarequest::GroupingPlan,
+    errors::{UltiResult, UltimaErr},
+    execution::{
+        context::ExecutionContext,
+        execute_agg::{exec_resolved, grouping_id},
+    },
+    AggregationRequest, DataSet, GroupingSets, Measure, MeasureName, MeasuresMap, GROUPING_ID,
+};
+
+const ROW_INDEX: &str = "__window_row__";
+/// Joins the parents onto the result, even when the partition is empty
+const PARENT_KEY: &str = "__parent_key__";
+const PARENT_GROUPING_ID: &str = "__parent_grouping_id__";
+
+/// eg `{"name": "Desk share", "measure": ["SA Charge", "scalar"], "partition_by": ["LegalEntity"], "function": "share_of_parent"}`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WindowMeasure {
+    /// Name of the column in the output
+    pub name: String,
+    /// (Measure, Aggregation) the window is evaluated over, eg `["Balance", "sum"]`.
+    /// Can be a calculated measure or a window declared before this one, eg `["Share", "scalar"]`.
+    /// Computed, but not returned, if it's not requested
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<String>))]
+    pub measure: (MeasureName, AggregationName),
+    /// Groupby columns the rows are partitioned by, eg `["LegalEntity"]`.
+    /// All the rows (of the same grouping set) are one partition if empty
+    #[serde(default)]
+    pub partition_by: Vec<String>,
+    #[serde(flatten)]
+    pub function: WindowFunction,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "function", rename_all = "snake_case")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum WindowFunction {
+    /// Value divided by the value of its partition, ie the contribution to the parent.
+    /// The parent is recomputed unless the measure adds up
+    ShareOfParent,
+    /// 1 is the largest value, or the smallest if `ascending`. Ties share the lowest rank
+    Rank {
+        #[serde(default)]
+        ascending: bool,
+    },
+    /// Running total in the order of `order_by`
+    CumSum { order_by: Vec<String> },
+    /// Difference with the previous row in the order of `order_by`, eg vs the previous period
+    Diff { order_by: Vec<String> },
+}
+
+/// A [WindowMeasure] ready to be added to the result
+pub(crate) struct WindowStage {
+    order_by: Vec<String>,
+    expr: Expr,
+    /// Must be joined onto the result first, see [join_parents]
+    parent: Option<Parent>,
+}
+
+/// Measure of a [WindowFunction::ShareOfParent] which doesn't add up,
+/// hence computed by the partition of the window
+pub(crate) struct Parent {
+    /// Temporary column of the result, holding the value of the parent
+    column: String,
+    measure: (MeasureName, AggregationName),
+    partition_by: Vec<String>,
+}
+
+fn over(e: Expr, partition: &[Expr]) -> Expr {
+    if partition.is_empty() {
+        e
+    } else {
+        e.over(partition)
+    }
+}
+
+impl WindowMeasure {
+    fn order_by(&self) -> &[String] {
+        match &self.function {
+            WindowFunction::CumSum { order_by } | WindowFunction::Diff { order_by } => order_by,
+            _ => &[],
+        }
+    }
+
+    /// `column` is the name of the column of [WindowMeasure::measure] in the result.
+    /// If `recompute_parent` the share of parent is over a [Parent]
+    fn stage(&self, column: &str, grouped: bool, recompute_parent: bool) -> WindowStage {
+        let mut partition = self.partition_by.iter().map(|c| col(c)).collect::<Vec<_>>();
+        if grouped {
+            partition.push(col(GROUPING_ID))
+        }
+        let x = col(column);
+        let parent =
+            (recompute_parent && self.function == WindowFunction::ShareOfParent).then(|| Parent {
+                column: format!("__parent_of_{}__", self.name),
+                measure: self.measure.clone(),
+                partition_by: self.partition_by.clone(),
+            });
+        let expr = match (&self.function, &parent) {
+            (WindowFunction::ShareOfParent, Some(p)) => x / col(&p.column),
+            (WindowFunction::ShareOfParent, None) => x.clone() / over(x.sum(), &partition),
+            (WindowFunction::Rank { ascending }, _) => over(
+                x.rank(
+                    RankOptions {
+                        method: RankMethod::Min,
+                        descending: !ascending,
+                    },
+                    None,
+                ),
+                &partition,
+            ),
+            (WindowFunction::CumSum { .. }, _) => over(x.cum_sum(false), &partition),
+            (WindowFunction::Diff { .. }, _) => x.clone() - over(x.shift(lit(1)), &partition),
+        };
+        WindowStage {
+            order_by: self.order_by().to_vec(),
+            expr: expr.alias(&self.name),
+            parent,
+        }
+    }
+}
+
+impl AggregationRequest {
+    /// Requested measures which are windows declared by the DataSet are moved to [AggregationRequest::windows]
+    pub(crate) fn with_windows(mut self, declared: &[WindowMeasure]) -> UltiResult<Self> {
+        if declared.is_empty() {
+            return Ok(self);
+        }
+        if let Some(w) = self
+            .windows
+            .iter()
+            .find(|w| declared.iter().any(|d| d.name == w.name))
+        {
+            return Err(UltimaErr::InvalidRequest(format!(
+                "Window measure {} is declared by the DataSet already",
+                w.name
+            )));
+        }
+        let mut windows = vec![];
+        self.measures
+            .retain(|(m, _)| match declared.iter().find(|w| &w.name == m) {
+                Some(w) => {
+                    if !windows.contains(w) {
+                        windows.push(w.clone())
+                    }
+                    false
+                }
+                None => true,
+            });
+        // Windows of the DataSet go first, since the request's own might refer to them
+        windows.append(&mut self.windows);
+        self.windows = windows;
+        Ok(self)
+    }
+
+    /// Validates [AggregationRequest::windows].
+    /// Returns the measures they are over which are not requested (to be computed, but not returned),
+    /// and the stages in the order they are declared
+    pub(crate) fn window_stages(
+        &self,
+        available: &MeasuresMap,
+        grouped: bool,
+    ) -> UltiResult<(Vec<(MeasureName, AggregationName)>, Vec<WindowStage>)> {
+        let groupby = self.group_by();
+        let mut extra = vec![];
+        let mut stages = Vec::with_capacity(self.windows.len());
+        for (i, w) in self.windows.iter().enumerate() {
+            let earlier = &self.windows[..i];
+            if w.name.is_empty()
+                || available.contains_key(&w.name)
+                || self.measures.iter().any(|(m, _)| m == &w.name)
+                || self.calculated.iter().any(|cm| cm.name == w.name)
+                || earlier.iter().any(|e| e.name == w.name)
+            {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "Name of the window measure \"{}\" must be new and not empty",
+                    w.name
+                )));
+            }
+            if let Some(c) = w
+                .partition_by
+                .iter()
+                .chain(w.order_by())
+                .find(|c| !groupby.contains(c))
+            {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "Window measure {} is over {c}, which is not in the groupby",
+                    w.name
+                )));
+            }
+
+            let (measure, agg) = &w.measure;
+            let aggregation =
+                BASE_CALCS
+                    .get(agg.as_str())
+                    .ok_or_else(|| UltimaErr::UnknownAggregation {
+                        aggregation: agg.clone(),
+                        available: BASE_CALCS.keys().map(|a| a.to_string()).collect(),
+                    })?;
+            let over_window = earlier.iter().any(|e| &e.name == measure);
+            let produced_by_request =
+                over_window || self.calculated.iter().any(|cm| &cm.name == measure);
+            // The sum of a partition is its parent only if the measure of the DataSet is summed
+            let adds_up = matches!(agg.as_str(), "sum" | "count")
+                && matches!(available.get(measure), Some(Measure::Base(_)));
+            if w.function == WindowFunction::ShareOfParent && over_window && !adds_up {
+                return Err(UltimaErr::InvalidRequest(format!(
+                    "Share of parent {} can't be over window measure {measure}",
+                    w.name
+                )));
+            }
+            if !produced_by_request
+                && !self.measures.contains(&w.measure)
+                && !extra.contains(&w.measure)
+            {
+                extra.push(w.measure.clone())
+            }
+            stages.push(w.stage(&aggregation.new_name(measure), grouped, !adds_up));
+        }
+        Ok((extra, stages))
+    }
+}
+
+/// Whether [join_parents] has anything to join
+pub(crate) fn has_parents(stages: &[WindowStage]) -> bool {
+    stages.iter().any(|s| s.parent.is_some())
+}
+
+/// Joins the value of the [Parent] of each row onto the result of `req`.
+/// Parents are computed by the `partition_by` of their window, from the same rows as `req`.
+/// Rows of a grouping set which rolls up some of the partition are joined to the parent
+/// of the rest of it (eg the grand total)
+pub(crate) fn join_parents<DS: DataSet + ?Sized>(
+    data: &DS,
+    req: &AggregationRequest,
+    grouping: Option<&GroupingPlan>,
+    stages: &[WindowStage],
+    mut res: DataFrame,
+    prepare: bool,
+    ctx: &ExecutionContext,
+) -> UltiResult<DataFrame> {
+    let groupby = req.group_by();
+    let n = groupby.len();
+    let sets = match grouping {
+        Some(g) => g.sets.clone(),
+        None => vec![(0..n).collect()],
+    };
+
+    for parent in stages.iter().filter_map(|s| s.parent.as_ref()) {
+        let partition = parent
+            .partition_by
+            .iter()
+            .map(|c| {
+                groupby
+                    .iter()
+                    .position(|g| g == c)
+                    .expect("Validated in window_stages")
+            })
+            .collect::<Vec<_>>();
+        let k = partition.len();
+
+        // Positions in the partition of the columns each grouping set keeps.
+        // (grouping id of the set, grouping id of the parent)
+        let mut parent_sets: Vec<Vec<usize>> = vec![];
+        let mut ids = vec![];
+        for set in &sets {
+            let kept = (0..k)
+                .filter(|j| set.contains(&partition[*j]))
+                .collect::<Vec<_>>();
+            ids.push((grouping_id(set, n), grouping_id(&kept, k)));
+            if !parent_sets.contains(&kept) {
+                parent_sets.push(kept)
+            }
+        }
+        let grouped = parent_sets.len() > 1 || parent_sets[0].len() < k;
+
+        let mut parent_req = req.clone();
+        parent_req.groupby = partition.iter().map(|i| req.groupby[*i].clone()).collect();
+        parent_req.totals = false;
+        parent_req.grouping_sets = grouped.then(|| {
+            GroupingSets::Custom(
+                parent_sets
+                    .iter()
+                    .map(|set| {
+                        set.iter()
+                            .map(|j| parent.partition_by[*j].clone())
+                            .collect()
+                    })
+                    .collect(),
+            )
+        });
+        parent_req.pivot = None;
+        parent_req.hide_zeros = false;
+        parent_req.windows.clear();
+        // Calculated measures might be over any measure of the request
+        if !req.calculated.iter().any(|cm| cm.name == parent.measure.0) {
+            parent_req.measures = vec![parent.measure.clone()];
+            parent_req.calculated.clear();
+        }
+        let (measure, agg) = &parent.measure;
+        let column = BASE_CALCS
+            .get(agg.as_str())
+            .expect("Validated in window_stages")
+            .new_name(measure);
+
+        let mut keys = parent
+            .partition_by
+            .iter()
+            .map(|c| col(c))
+            .collect::<Vec<_>>();
+        keys.push(col(PARENT_KEY));
+        let mut selected = keys.clone();
+        selected.push(col(&column).alias(&parent.column));
+        let mut parents = exec_resolved(data, parent_req, prepare, ctx)?
+            .lazy()
+            .with_column(lit(0).alias(PARENT_KEY));
+        let mut lf = res.lazy().with_column(lit(0).alias(PARENT_KEY));
+        let mut temporary = vec![PARENT_KEY];
+        if grouped {
+            parents = parents.rename([GROUPING_ID], [PARENT_GROUPING_ID]);
+            let parent_id = ids.into_iter().fold(
+                lit(NULL).cast(DataType::UInt32),
+                |otherwise, (id, parent_id)| {
+                    when(col(GROUPING_ID).eq(lit(id)))
+                        .then(lit(parent_id))
+                        .otherwise(otherwise)
+                },
+            );
+            lf = lf.with_column(parent_id.alias(PARENT_GROUPING_ID));
+            keys.push(col(PARENT_GROUPING_ID));
+            selected.push(col(PARENT_GROUPING_ID));
+            temporary.push(PARENT_GROUPING_ID);
+        }
+
+        let mut args = JoinArgs::new(JoinType::Left);
+        args.join_nulls = true;
+        res = lf
+            .join(parents.select(selected), keys.clone(), keys, args)
+            .drop(temporary)
+            .collect()?;
+        ctx.checkpoint("windows")?;
+    }
+    Ok(res)
+}
+
+/// Adds the windows to the result, one after another
+pub(crate) fn apply_windows(
+    mut res: DataFrame,
+    stages: Vec<WindowStage>,
+    ctx: &ExecutionContext,
+) -> UltiResult<DataFrame> {
+    for WindowStage { order_by, expr, .. } in stages {
+        res = if order_by.is_empty() {
+            res.lazy().with_column(expr).collect()?
+        } else {
+            // Sort in the order of the window, and back into the order of the result
+            let sort_options = SortMultipleOptions::default()
+                .with_maintain_order(true)
+                .with_nulls_last(true);
+            res.lazy()
+                .with_row_index(ROW_INDEX, None)
+                .sort_by_exprs(
+                    order_by.iter().map(|c| col(c)).collect::<Vec<_>>(),
+                    sort_options,
+                )
+                .with_column(expr)
+                .sort_by_exprs([col(ROW_INDEX)], Default::default())
+                .drop([ROW_INDEX])
+                .collect()?
+        };
+        ctx.checkpoint("windows")?;
+    }
+    Ok(res)
+}
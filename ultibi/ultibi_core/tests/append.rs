@@ -0,0 +1,85 @@
+//! Rows appended to and deleted from an InMemory DataSet at runtime
+
+use polars::prelude::*;
+use ultibi_core::{
+    datasource::DataSource, filters::FilterE, new::NewSourcedDataSet, ComputeRequest, DataSet,
+    DataSetBase,
+};
+
+fn dataset() -> DataSetBase {
+    let df = polars::df!(
+        "TradeId" => ["T1", "T2", "T3"],
+        "Desk" => ["FX", "FX", "Rates"],
+        "Notional" => [10., 20., 30.]
+    )
+    .unwrap();
+
+    DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    )
+}
+
+fn notional_of_fx(data: &DataSetBase) -> f64 {
+    let req = serde_json::from_str::<ComputeRequest>(
+        r#"{"measures": [["Notional", "sum"]], "groupby": ["Desk"],
+        "filters": [[{"op": "Eq", "field": "Desk", "value": "FX"}]]}"#,
+    )
+    .unwrap();
+    data.compute(req)
+        .unwrap()
+        .column("Notional_sum")
+        .unwrap()
+        .sum::<f64>()
+        .unwrap()
+}
+
+#[test]
+fn append_and_delete_clean_the_cache() {
+    let mut data = dataset();
+    assert_eq!(notional_of_fx(&data), 30.);
+
+    // Columns in a different order and of a different type are aligned with the data
+    let new_trades = polars::df!(
+        "Notional" => [5i64],
+        "Desk" => ["FX"],
+        "TradeId" => ["T4"]
+    )
+    .unwrap();
+    assert_eq!(data.append_frame(new_trades).unwrap(), 1);
+    assert_eq!(notional_of_fx(&data), 35.);
+
+    let amended = vec![vec![FilterE::In {
+        field: "TradeId".to_string(),
+        value: vec![Some("T1".to_string()), Some("T3".to_string())],
+    }]];
+    assert_eq!(data.delete_where(&amended).unwrap(), 2);
+    assert_eq!(notional_of_fx(&data), 25.);
+    assert_eq!(data.get_column("TradeId").unwrap().len(), 2);
+}
+
+#[test]
+fn invalid_changes() {
+    let mut data = dataset();
+
+    let missing_column = polars::df!("TradeId" => ["T4"]).unwrap();
+    assert!(data.append_frame(missing_column).is_err());
+    let not_a_number = polars::df!(
+        "TradeId" => ["T4"],
+        "Desk" => ["FX"],
+        "Notional" => ["ten"]
+    )
+    .unwrap();
+    assert_eq!(
+        data.append_frame(not_a_number).unwrap_err().code(),
+        "invalid_request"
+    );
+    assert_eq!(
+        data.delete_where(&vec![]).unwrap_err().code(),
+        "invalid_request"
+    );
+    assert_eq!(data.get_column("TradeId").unwrap().len(), 3);
+}
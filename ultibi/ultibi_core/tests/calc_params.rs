@@ -0,0 +1,48 @@
+use ultibi_core::{validate_calc_params, CalcParamType, CalcParameter, CPM};
+
+#[test]
+fn param_types() {
+    let matrix = CalcParamType::Matrix {
+        rows: Some(2),
+        cols: Some(2),
+    };
+    assert!(matrix
+        .validate(r#"{"v": 1, "dim": [2, 2], "data": [1, 0.5, 0.5, 1]}"#)
+        .is_ok());
+    assert!(matrix
+        .validate(r#"{"v": 1, "dim": [2, 2], "data": [1, 0.5, 0.5]}"#)
+        .is_err());
+    assert!(matrix
+        .validate(r#"{"v": 1, "dim": [3, 3], "data": [1, 1, 1, 1, 1, 1, 1, 1, 1]}"#)
+        .is_err());
+
+    let vector = CalcParamType::Vector { len: Some(2) };
+    assert!(vector.validate("[0.1, 0.2]").is_ok());
+    assert!(vector.validate("[0.1]").is_err());
+    assert!(vector.validate(r#"["a", "b"]"#).is_err());
+
+    assert!(CalcParamType::Float.validate("0.5").is_ok());
+    assert!(CalcParamType::Float.validate("half").is_err());
+    assert!(CalcParamType::Bool.validate("yes").is_err());
+    assert!(CalcParamType::Strings.validate(r#"["BGN"]"#).is_ok());
+    assert!(CalcParamType::Any.validate("anything").is_ok());
+}
+
+#[test]
+fn strict_rejects_unknown() {
+    let declared = vec![CalcParameter {
+        name: "multiplier".to_string(),
+        param_type: CalcParamType::Float,
+        ..Default::default()
+    }];
+    let params = CPM::from([("multiplyer".to_string(), "2".to_string())]);
+
+    assert!(validate_calc_params(&declared, &params, false).is_ok());
+    let err = validate_calc_params(&declared, &params, true).unwrap_err();
+    assert_eq!(err.code(), "unknown_calc_param");
+
+    let params = CPM::from([("multiplier".to_string(), "two".to_string())]);
+    let err = validate_calc_params(&declared, &params, false).unwrap_err();
+    assert_eq!(err.code(), "invalid_calc_param");
+    assert_eq!(err.field(), Some("multiplier"));
+}
@@ -0,0 +1,89 @@
+//! Measures calculated from the other measures of a request
+
+use polars::prelude::*;
+use ultibi_core::{errors::UltimaErr, ComputeRequest, DataSet};
+mod common;
+
+fn compute(req: &str) -> Result<DataFrame, UltimaErr> {
+    let data_req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    (*common::TEST_DASET).as_ref().compute(data_req)
+}
+
+fn value_of(res: &DataFrame, state: &str, column: &str) -> f64 {
+    res.clone()
+        .lazy()
+        .filter(col("State").eq(lit(state)))
+        .collect()
+        .unwrap()
+        .column(column)
+        .unwrap()
+        .sum::<f64>()
+        .unwrap()
+}
+
+#[test]
+fn ratio_and_share_of_total() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"], ["Age", "sum"]],
+    "groupby": ["State"],
+    "calculated": [
+        {"name": "BalancePerAge", "expression": "[Balance_sum] / [Age]"},
+        {"name": "Share", "expression": "100 * [Balance] / total([Balance_sum])"},
+        {"name": "ShareLeft", "expression": "100 - [Share]"}
+    ]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(
+        res.get_column_names(),
+        [
+            "State",
+            "Balance_sum",
+            "Age_sum",
+            "BalancePerAge",
+            "Share",
+            "ShareLeft"
+        ]
+    );
+    assert_eq!(value_of(&res, "NY", "BalancePerAge"), 25. / 31.);
+    assert!((res.column("Share").unwrap().sum::<f64>().unwrap() - 100.).abs() < 1e-9);
+    assert!((value_of(&res, "California", "ShareLeft") - (100. - 100. * 40. / 85.)).abs() < 1e-9);
+}
+
+#[test]
+fn total_skips_subtotals() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": ["State"],
+    "grouping_sets": {"type": "grand_total"},
+    "calculated": [{"name": "Share", "expression": "[Balance_sum] / total([Balance_sum])"}]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    let grand_total = res.lazy().filter(col("State").is_null()).collect().unwrap();
+    assert_eq!(
+        grand_total.column("Share").unwrap().sum::<f64>().unwrap(),
+        1.
+    );
+}
+
+#[test]
+fn invalid_expressions() {
+    for calculated in [
+        r#"{"name": "X", "expression": "[Balance_sum] / [Name]"}"#,
+        r#"{"name": "X", "expression": "[Balance_sum] / "}"#,
+        r#"{"name": "X", "expression": "max([Balance_sum])"}"#,
+        r#"{"name": "Age", "expression": "[Balance_sum] * 2"}"#,
+    ] {
+        let err = compute(&format!(
+            r#"{{"measures": [["Balance", "sum"]], "groupby": ["State"], "calculated": [{calculated}]}}"#
+        ))
+        .unwrap_err();
+
+        assert_eq!(err.code(), "invalid_request", "{calculated}");
+    }
+}
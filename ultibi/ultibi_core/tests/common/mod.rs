@@ -46,16 +46,16 @@ pub static TEST_DASET_WITH_DEPENDANTS: Lazy<Arc<DataSetBase>> = Lazy::new(|| {
             }),
             depends_upon: vec![("Age".to_string(), "sum".to_string())],
             calc_params: vec![],
+            metadata: Default::default(),
         }),
-        DependantMeasure {
-            name: "NoSuchMeasureTest".to_string(),
-            calculator: Arc::new(|op: &CPM| {
+        DependantMeasure::new(
+            "NoSuchMeasureTest",
+            Arc::new(|op: &CPM| {
                 let n = op.get("count").unwrap().parse::<f64>().unwrap();
                 Ok(col("NoSuchMeasure_sum") / n.into())
             }),
-            depends_upon: vec![("NoSuchMeasure".to_string(), "sum".to_string())],
-            calc_params: vec![],
-        }
+            vec![("NoSuchMeasure".to_string(), "sum".to_string())],
+        )
         .into(),
     ];
 
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use ultibi_core::{errors::UltimaErr, ComputeRequest, DataSet, ExecutionContext};
+
+mod common;
+
+fn request() -> ComputeRequest {
+    let req = r#"
+    {"measures": [
+        ["Balance", "sum"]
+            ],
+    "groupby": ["State"],
+    "filters": []
+    }"#;
+    serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request")
+}
+
+#[test]
+fn cancelled_before_start() {
+    let ctx = ExecutionContext::new();
+    ctx.cancel();
+
+    let res = common::TEST_DASET
+        .as_ref()
+        .compute_with_context(request(), &ctx);
+
+    assert!(matches!(res, Err(UltimaErr::Cancelled(_))));
+}
+
+#[test]
+fn timed_out() {
+    let ctx = ExecutionContext::new().with_timeout(Duration::ZERO);
+    std::thread::sleep(Duration::from_millis(1));
+
+    let res = common::TEST_DASET
+        .as_ref()
+        .compute_with_context(request(), &ctx);
+
+    assert!(matches!(res, Err(UltimaErr::Timeout(_))));
+}
+
+#[test]
+fn not_cancelled() {
+    let ctx = ExecutionContext::new().with_timeout(Duration::from_secs(60));
+
+    let res = common::TEST_DASET
+        .as_ref()
+        .compute_with_context(request(), &ctx)
+        .expect("Calculation failed");
+
+    assert_eq!(res.height(), 3);
+}
+
+#[test]
+fn stage_timings() {
+    let ctx = ExecutionContext::new();
+
+    common::TEST_DASET
+        .as_ref()
+        .compute_with_context(request(), &ctx)
+        .expect("Calculation failed");
+
+    let timings = ctx.timings();
+    // Wall time covers all the stages
+    let elapsed = ctx.elapsed().expect("Timer was started");
+    assert!(elapsed >= timings.iter().map(|(_, d)| *d).sum::<std::time::Duration>());
+
+    let stages = timings
+        .into_iter()
+        .map(|(stage, _)| stage)
+        .collect::<Vec<_>>();
+    for stage in ["lookup", "filter", "groupby"] {
+        assert!(stages.iter().any(|s| s == stage), "{stage} not timed");
+    }
+
+    let (hits, misses) = ctx.cache_lookups();
+    assert_eq!(hits + misses, 1);
+}
@@ -33,3 +33,12 @@ fn config_build2() {
     let (_, _, _) = conf.build();
     //lf.collect().expect("Couldn't build");
 }
+
+#[test]
+fn config_input_files() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "data", "test_config.toml"]);
+
+    let conf = read_toml2::<DataSourceConfig>(path.to_str().unwrap()).unwrap();
+    assert_eq!(conf.input_files(), vec!["./tests/data/testset.csv"]);
+}
@@ -37,7 +37,23 @@ fn test_query_create() {
         .replace('\t', " ")
         .replace("    ", " ");
 
-    let res = ultibi_core::datasource::fltr_chain_to_sql_query("delta", &fltr);
+    let res = ultibi_core::datasource::fltr_chain_to_sql_query("delta", &fltr).unwrap();
 
     assert_eq!(expected, res);
 }
+
+#[test]
+#[cfg(feature = "db")]
+fn unresolved_descendants() {
+    use ultibi_core::{errors::UltimaErr, filters::FilterE};
+
+    let fltr = vec![vec![FilterE::Descendants {
+        hierarchy: "Books".into(),
+        node: "Rates".into(),
+        level: None,
+    }]];
+
+    let res = ultibi_core::datasource::fltr_chain_to_sql_query("delta", &fltr);
+
+    assert!(matches!(res, Err(UltimaErr::InvalidRequest(_))));
+}
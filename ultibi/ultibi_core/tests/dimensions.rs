@@ -0,0 +1,115 @@
+//! Computed groupby dimensions
+
+use polars::prelude::*;
+use ultibi_core::{
+    datasource::DataSource, errors::UltimaErr, new::NewSourcedDataSet, ComputeRequest, DataSet,
+    DataSetBase,
+};
+mod common;
+
+fn compute(req: &str) -> Result<DataFrame, UltimaErr> {
+    let data_req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    (*common::TEST_DASET).as_ref().compute(data_req)
+}
+
+fn balance_of(res: &DataFrame, dimension: &str, value: &str) -> f64 {
+    res.clone()
+        .lazy()
+        .filter(col(dimension).eq(lit(value)))
+        .collect()
+        .unwrap()
+        .column("Balance_sum")
+        .unwrap()
+        .sum::<f64>()
+        .unwrap()
+}
+
+#[test]
+fn bins() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": [{"name": "BalanceBucket", "column": "Balance", "type": "bins", "edges": [15, 25]}]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(res.height(), 3);
+    assert_eq!(balance_of(&res, "BalanceBucket", "<15"), 20.);
+    assert_eq!(balance_of(&res, "BalanceBucket", "15-25"), 35.);
+    assert_eq!(balance_of(&res, "BalanceBucket", ">=25"), 30.);
+}
+
+#[test]
+fn mapping_and_substring() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": [
+        {"name": "Coast", "column": "State", "type": "map",
+            "mapping": {"NY": "East", "California": "West"}, "default": "Other"},
+        {"name": "CityCode", "column": "City", "type": "substring", "offset": 0, "length": 3}
+    ],
+    "totals": true
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(res.height(), 7);
+    // Sun Diego and the subtotal of the West
+    assert_eq!(balance_of(&res, "Coast", "West"), 80.);
+    assert_eq!(balance_of(&res, "CityCode", "Buf"), 10.);
+}
+
+#[test]
+fn month_of_date() {
+    let cob = Series::new("COB", [19_000i32, 19_001, 19_030])
+        .cast(&DataType::Date)
+        .unwrap();
+    let df = DataFrame::new(vec![cob, Series::new("Notional", [10., 20., 30.])]).unwrap();
+    let data = DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    );
+
+    let req = serde_json::from_str::<ComputeRequest>(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": [{"name": "Month", "column": "COB", "type": "date_trunc", "every": "1mo"}]
+    }"#,
+    )
+    .unwrap();
+    let res = data.compute(req).expect("Calculation failed");
+
+    assert_eq!(res.column("Month").unwrap().dtype(), &DataType::Date);
+    assert_eq!(res.height(), 2);
+}
+
+#[test]
+fn decreasing_bin_edges() {
+    let err = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": [{"name": "BalanceBucket", "column": "Balance", "type": "bins", "edges": [25, 15]}]
+    }"#,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code(), "invalid_request");
+}
+
+#[test]
+fn name_of_an_existing_column() {
+    let err = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": [{"name": "City", "column": "City", "type": "substring", "offset": 0, "length": 3}]
+    }"#,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code(), "invalid_request");
+}
@@ -0,0 +1,61 @@
+//! Rows behind a cell
+
+use ultibi_core::{
+    errors::UltiResult, DataSet, DrillThroughPage, DrillThroughRequest, ExecutionContext,
+};
+mod common;
+
+fn drill_through(req: &str) -> UltiResult<DrillThroughPage> {
+    let req = serde_json::from_str::<DrillThroughRequest>(req).expect("Could not parse request");
+    (*common::TEST_DASET)
+        .as_ref()
+        .drill_through(req, &ExecutionContext::default())
+}
+
+#[test]
+fn rows_of_a_cell() {
+    let page = drill_through(
+        r#"
+    {"request": {"measures": [["Balance", "sum"]], "groupby": ["State", "City"]},
+    "cell": {"State": "California"},
+    "columns": ["City", "Name", "Balance"]
+    }"#,
+    )
+    .expect("Drill through failed");
+
+    assert_eq!(page.total, 2);
+    assert_eq!(page.rows.get_column_names(), ["City", "Name", "Balance"]);
+    assert_eq!(
+        page.rows.column("Balance").unwrap().sum::<f64>().unwrap(),
+        40.
+    );
+}
+
+#[test]
+fn pagination() {
+    let page = drill_through(
+        r#"
+    {"request": {"measures": [["Balance", "sum"]], "groupby": ["State"],
+        "filters": [[{"op": "Neq", "field": "State", "value": "Washington"}]]},
+    "offset": 3,
+    "limit": 2
+    }"#,
+    )
+    .expect("Drill through failed");
+
+    assert_eq!(page.total, 4);
+    assert_eq!(page.rows.height(), 1);
+}
+
+#[test]
+fn cell_outside_of_groupby() {
+    let err = drill_through(
+        r#"
+    {"request": {"measures": [["Balance", "sum"]], "groupby": ["State"]},
+    "cell": {"City": "Forks"}
+    }"#,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code(), "invalid_request");
+}
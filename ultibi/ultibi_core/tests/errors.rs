@@ -0,0 +1,56 @@
+use ultibi_core::{errors::UltimaErr, ComputeRequest, DataSet};
+
+mod common;
+
+fn compute(req: &str) -> UltimaErr {
+    let data_req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    common::TEST_DASET
+        .as_ref()
+        .compute(data_req)
+        .expect_err("Request should fail")
+}
+
+#[test]
+fn unknown_measure() {
+    let err = compute(r#"{"measures": [["NoSuchMeasure", "sum"]], "groupby": ["State"]}"#);
+
+    assert_eq!(err.code(), "unknown_measure");
+    assert_eq!(err.field(), Some("NoSuchMeasure"));
+    assert!(err.is_client_error());
+}
+
+#[test]
+fn unknown_aggregation() {
+    let err = compute(r#"{"measures": [["Balance", "NoSuchAction"]], "groupby": ["State"]}"#);
+
+    assert_eq!(err.code(), "unknown_aggregation");
+    assert_eq!(err.field(), Some("NoSuchAction"));
+}
+
+#[test]
+fn invalid_override() {
+    let err = compute(
+        r#"{"measures": [["Balance", "sum"]], "groupby": ["State"],
+        "overrides": [{"field": "Balance", "value": "not a number", "filters": []}]}"#,
+    );
+
+    assert_eq!(err.code(), "invalid_override");
+    assert_eq!(err.field(), Some("Balance"));
+    assert!(err.to_string().contains("could not be parsed"));
+}
+
+#[test]
+fn no_measures() {
+    let err = compute(r#"{"measures": [], "groupby": ["State"]}"#);
+
+    assert_eq!(err.code(), "invalid_request");
+    assert_eq!(err.field(), None);
+}
+
+#[test]
+fn timeout_is_not_client_error() {
+    let err = UltimaErr::Timeout(std::time::Duration::from_secs(1));
+
+    assert_eq!(err.code(), "timeout");
+    assert!(!err.is_client_error());
+}
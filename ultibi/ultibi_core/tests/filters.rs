@@ -45,3 +45,16 @@ fn fltr_eq_or_eq() {
         .expect("Couldn't find mean");
     assert_eq!(res_sum, 30.0)
 }
+
+#[test]
+fn column_filtered() {
+    let filters = serde_json::from_str(r#"[[{"op": "Eq", "field": "State", "value": "NY"}]]"#)
+        .expect("Could not parse filters");
+
+    let res = common::TEST_DASET
+        .as_ref()
+        .get_column_filtered("City", &filters)
+        .unwrap();
+
+    assert_eq!(res.len(), 2);
+}
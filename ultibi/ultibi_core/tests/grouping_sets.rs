@@ -0,0 +1,179 @@
+//! Subtotals with grouping sets
+
+use std::collections::BTreeMap;
+
+use polars::prelude::*;
+use ultibi_core::{
+    datasource::DataSource, errors::UltimaErr, ComputeRequest, DataSet, DataSetBase,
+    ExecutionContext, GROUPING_ID, MAX_GROUPS,
+};
+mod common;
+
+fn compute(req: &str) -> Result<DataFrame, UltimaErr> {
+    let data_req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    (*common::TEST_DASET).as_ref().compute(data_req)
+}
+
+fn rows_of(res: &DataFrame, grouping_id: u32) -> DataFrame {
+    res.clone()
+        .lazy()
+        .filter(col(GROUPING_ID).eq(lit(grouping_id)))
+        .collect()
+        .unwrap()
+}
+
+#[test]
+fn rollup() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": ["State", "City"],
+    "grouping_sets": {"type": "rollup"}
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    // 4 cities, 3 states and the grand total
+    assert_eq!(res.height(), 8);
+
+    let states = rows_of(&res, 1);
+    assert_eq!(states.height(), 3);
+    assert!(states.column("City").unwrap().is_null().all());
+
+    let total = rows_of(&res, 3);
+    assert_eq!(total.height(), 1);
+    assert_eq!(
+        total.column("Balance_sum").unwrap().sum::<f64>().unwrap(),
+        85.
+    );
+}
+
+#[test]
+fn cube() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": ["State", "City"],
+    "grouping_sets": {"type": "cube"}
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    let cities = rows_of(&res, 2);
+    assert_eq!(cities.height(), 4);
+    assert!(cities.column("State").unwrap().is_null().all());
+    assert_eq!(res.height(), 12);
+}
+
+#[test]
+fn grand_total_recomputes_non_additive() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"], ["Age", "mean"]],
+    "groupby": ["State"],
+    "grouping_sets": {"type": "grand_total"}
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(res.height(), 4);
+    let total = rows_of(&res, 1);
+    assert_eq!(
+        total.column("Balance_sum").unwrap().sum::<f64>().unwrap(),
+        85.
+    );
+    // Mean of all rows, not mean of the means of states
+    let age = total.column("Age_mean").unwrap().sum::<f64>().unwrap();
+    assert!((age - 17.38).abs() < 1e-9);
+}
+
+#[test]
+fn custom_sets() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": ["State", "City"],
+    "grouping_sets": {"type": "custom", "sets": [["City"], []]}
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(res.height(), 5);
+    assert_eq!(rows_of(&res, 0).height(), 0);
+}
+
+#[test]
+fn custom_set_outside_groupby() {
+    let err = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "groupby": ["State"],
+    "grouping_sets": {"type": "custom", "sets": [["City"]]}
+    }"#,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code(), "invalid_request");
+}
+
+#[test]
+fn too_many_groupby_columns() {
+    // One bit of the grouping id per column
+    let groupby = (0..33).map(|i| format!("Col{i}")).collect::<Vec<_>>();
+    for grouping_sets in [r#"{"type": "rollup"}"#, r#"{"type": "grand_total"}"#] {
+        let err = compute(&format!(
+            r#"{{"measures": [["Balance", "sum"]], "groupby": {}, "grouping_sets": {grouping_sets}}}"#,
+            serde_json::to_string(&groupby).unwrap()
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("limited to 32 groupby columns"));
+    }
+}
+
+#[test]
+fn subtotals_are_limited_as_the_finest_set() {
+    let n = MAX_GROUPS as u32 + 500;
+    let df = polars::df!(
+        "Book" => (0..n).map(|i| format!("Book{i}")).collect::<Vec<_>>(),
+        "Desk" => (0..n).map(|i| format!("Desk{}", i % 2)).collect::<Vec<_>>(),
+        "Notional" => vec![1.; n as usize]
+    )
+    .unwrap();
+    let data = DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        BTreeMap::new(),
+    );
+    let req = |groupby: &str| {
+        serde_json::from_str::<ComputeRequest>(&format!(
+            r#"{{"measures": [["Notional", "sum"]],
+            "groupby": [{groupby}],
+            "grouping_sets": {{"type": "rollup"}}
+            }}"#
+        ))
+        .unwrap()
+    };
+
+    let ctx = ExecutionContext::new();
+    let res = data
+        .compute_with_context(req(r#""Desk", "Book""#), &ctx)
+        .unwrap();
+    assert!(ctx.is_truncated());
+    assert_eq!(rows_of(&res, 0).height(), MAX_GROUPS);
+    // Subtotals are still rolled up from all the rows
+    assert_eq!(rows_of(&res, 1).height(), 2);
+    assert_eq!(
+        rows_of(&res, 3)
+            .column("Notional_sum")
+            .unwrap()
+            .sum::<f64>()
+            .unwrap(),
+        n as f64
+    );
+
+    let ctx = ExecutionContext::new();
+    data.compute_with_context(req(r#""Desk""#), &ctx).unwrap();
+    assert!(!ctx.is_truncated());
+}
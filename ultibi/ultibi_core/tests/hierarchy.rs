@@ -0,0 +1,160 @@
+//! Drill down and descendants filters
+
+use polars::prelude::*;
+use ultibi_core::{
+    datasource::DataSource, errors::UltimaErr, filters::FilterE, hierarchy::Hierarchy,
+    new::NewSourcedDataSet, ComputeRequest, DataSet, DataSetBase,
+};
+
+fn dataset() -> DataSetBase {
+    let df = polars::df!(
+        "Region" => ["EMEA", "EMEA", "EMEA", "APAC"],
+        "Country" => ["UK", "UK", "France", "Japan"],
+        "Desk" => ["FXOptions", "FXCash", "Swaps", "Rates"],
+        "Notional" => [10., 20., 30., 40.]
+    )
+    .unwrap();
+
+    let mut data = DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    );
+
+    let hierarchies = serde_json::from_str::<Vec<Hierarchy>>(
+        r#"[
+        {"name": "Geography", "kind": "levels", "levels": ["Region", "Country"]},
+        {"name": "Desks", "kind": "parent_child", "column": "Desk",
+            "parents": {"FX": "Markets", "Rates": "Markets",
+                "FXOptions": "FX", "FXCash": "FX", "Swaps": "Rates"}}
+    ]"#,
+    )
+    .unwrap();
+    data.set_hierarchies(hierarchies).unwrap();
+    data
+}
+
+fn compute(req: &str) -> Result<DataFrame, UltimaErr> {
+    let req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    dataset().compute(req)
+}
+
+fn notional_of(res: &DataFrame, dimension: &str, value: &str) -> f64 {
+    res.clone()
+        .lazy()
+        .filter(col(dimension).eq(lit(value)))
+        .collect()
+        .unwrap()
+        .column("Notional_sum")
+        .unwrap()
+        .sum::<f64>()
+        .unwrap()
+}
+
+#[test]
+fn descendants_filter() {
+    let res = compute(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": ["Desk"],
+    "filters": [[{"op": "Descendants", "hierarchy": "Desks", "node": "Rates"}]]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    // The node itself and its child
+    assert_eq!(res.height(), 2);
+    assert_eq!(notional_of(&res, "Desk", "Swaps"), 30.);
+    assert_eq!(notional_of(&res, "Desk", "Rates"), 40.);
+}
+
+#[test]
+fn drill_parent_child() {
+    let res = compute(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": [],
+    "drill": {"hierarchy": "Desks", "node": "Markets"}
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(res.height(), 2);
+    assert_eq!(notional_of(&res, "Desks", "FX"), 30.);
+    assert_eq!(notional_of(&res, "Desks", "Rates"), 70.);
+}
+
+#[test]
+fn drill_levels() {
+    let res = compute(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": [],
+    "drill": {"hierarchy": "Geography", "node": "EMEA", "level": "Region"}
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(res.get_column_names(), ["Country", "Notional_sum"]);
+    assert_eq!(res.height(), 2);
+    assert_eq!(notional_of(&res, "Country", "UK"), 30.);
+
+    let top = compute(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": [],
+    "drill": {"hierarchy": "Geography"}
+    }"#,
+    )
+    .expect("Calculation failed");
+    assert_eq!(top.get_column_names(), ["Region", "Notional_sum"]);
+}
+
+#[test]
+fn drill_below_leaf() {
+    let err = compute(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": [],
+    "drill": {"hierarchy": "Geography", "node": "UK", "level": "Country"}
+    }"#,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code(), "invalid_request");
+}
+
+#[test]
+fn descendants_outside_of_request_filters() {
+    // Overrides
+    let res = compute(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": ["Region"],
+    "overrides": [{"field": "Notional", "value": "0",
+        "filters": [[{"op": "Descendants", "hierarchy": "Desks", "node": "FX"}]]}]
+    }"#,
+    )
+    .expect("Calculation failed");
+    assert_eq!(notional_of(&res, "Region", "EMEA"), 30.);
+
+    let descendants_of_uk = vec![vec![FilterE::Descendants {
+        hierarchy: "Geography".to_string(),
+        node: "UK".to_string(),
+        level: Some("Country".to_string()),
+    }]];
+    let mut data = dataset();
+    assert_eq!(
+        data.get_column_filtered("Desk", &descendants_of_uk)
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(data.delete_where(&descendants_of_uk).unwrap(), 2);
+
+    // Can't be evaluated without the hierarchies
+    let err = data.get_lazyframe(&descendants_of_uk).unwrap_err();
+    assert!(matches!(err, UltimaErr::InvalidRequest(_)));
+}
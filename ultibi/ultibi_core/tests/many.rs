@@ -0,0 +1,74 @@
+use ultibi_core::{errors::UltimaErr, ComputeRequest, DataSet, ExecutionContext};
+
+mod common;
+
+fn request(groupby: &str, state: &str) -> ComputeRequest {
+    let req = format!(
+        r#"
+    {{"measures": [
+        ["Balance", "sum"],
+        ["DivAge", "scalar"]
+            ],
+    "groupby": [{groupby}],
+    "filters": [[{{"op": "Eq", "field": "State", "value": "{state}"}}]],
+    "calc_params": {{"count": "10"}}
+    }}"#
+    );
+    serde_json::from_str::<ComputeRequest>(&req).expect("Could not parse request")
+}
+
+#[test]
+fn same_as_one_by_one() {
+    let reqs = vec![
+        request(r#""State""#, "NY"),
+        request(r#""City""#, "NY"),
+        request(r#""State", "Sex""#, "Washington"),
+        request(r#""Sex""#, "NY"),
+    ];
+    let ds = common::TEST_DASET_WITH_DEPENDANTS.as_ref();
+
+    let many = ds.compute_many(reqs.clone(), &ExecutionContext::new());
+
+    assert_eq!(many.len(), reqs.len());
+    for (req, res) in reqs.into_iter().zip(many) {
+        let expected = ds.compute(req).expect("Calculation failed");
+        assert!(res
+            .expect("Batch calculation failed")
+            .equals_missing(&expected));
+    }
+}
+
+#[test]
+fn failure_is_per_request() {
+    let bad = serde_json::from_str::<ComputeRequest>(
+        r#"{"measures": [["NoSuchMeasure", "sum"]], "groupby": ["State"], "filters": []}"#,
+    )
+    .unwrap();
+    let reqs = vec![request(r#""State""#, "NY"), bad, request(r#""City""#, "NY")];
+
+    let res = common::TEST_DASET_WITH_DEPENDANTS
+        .as_ref()
+        .compute_many(reqs, &ExecutionContext::new());
+
+    assert!(res[0].is_ok());
+    assert!(res[1].is_err());
+    assert!(res[2].is_ok());
+}
+
+#[test]
+fn only_aggregations_are_batched() {
+    let reqs = vec![
+        request(r#""State""#, "NY"),
+        ComputeRequest::Breakdown,
+        request(r#""City""#, "NY"),
+    ];
+
+    let res = common::TEST_DASET_WITH_DEPENDANTS
+        .as_ref()
+        .compute_many(reqs, &ExecutionContext::new());
+
+    assert_eq!(res.len(), 3);
+    assert!(res[0].is_ok());
+    assert!(matches!(res[1], Err(UltimaErr::InvalidRequest(_))));
+    assert!(res[2].is_ok());
+}
@@ -0,0 +1,90 @@
+//! Dates, integers and booleans are grouped by as they are
+
+use polars::prelude::*;
+use ultibi_core::{
+    datasource::DataSource, fields_columns, new::NewSourcedDataSet, ComputeRequest, DataSet,
+    DataSetBase, GROUPING_ID,
+};
+
+fn dataset() -> DataSetBase {
+    let cob = Series::new("COB", [19_000i32, 19_000, 19_001, 19_001])
+        .cast(&DataType::Date)
+        .unwrap();
+    let df = DataFrame::new(vec![
+        cob,
+        Series::new("Bucket", [1i64, 2, 1, 2]),
+        Series::new("Exotic", [Some(true), None, Some(false), None]),
+        Series::new("Notional", [10., 20., 30., 40.]),
+    ])
+    .unwrap();
+
+    DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    )
+}
+
+#[test]
+fn dates_and_booleans_are_fields() {
+    let fields = fields_columns(dataset().get_schema().unwrap());
+
+    assert_eq!(fields, ["COB", "Bucket", "Exotic"]);
+}
+
+#[test]
+fn native_types_and_nulls_are_kept() {
+    let req = serde_json::from_str::<ComputeRequest>(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": ["COB", "Exotic"],
+    "grouping_sets": {"type": "rollup"}
+    }"#,
+    )
+    .unwrap();
+    let res = dataset().compute(req).expect("Calculation failed");
+
+    assert_eq!(res.column("COB").unwrap().dtype(), &DataType::Date);
+    assert_eq!(res.column("Exotic").unwrap().dtype(), &DataType::Boolean);
+
+    // Nulls of the data are told apart from subtotals by the grouping id
+    let null_exotic = res
+        .clone()
+        .lazy()
+        .filter(col("Exotic").is_null().and(col(GROUPING_ID).eq(lit(0u32))))
+        .collect()
+        .unwrap();
+    assert_eq!(null_exotic.height(), 2);
+
+    let per_cob = res
+        .lazy()
+        .filter(col(GROUPING_ID).eq(lit(1u32)))
+        .collect()
+        .unwrap();
+    assert_eq!(
+        per_cob
+            .column("Notional_sum")
+            .unwrap()
+            .sum::<f64>()
+            .unwrap(),
+        100.
+    );
+    assert_eq!(per_cob.height(), 2);
+}
+
+#[test]
+fn integer_dimension_without_totals() {
+    let req = serde_json::from_str::<ComputeRequest>(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "groupby": ["Bucket"]
+    }"#,
+    )
+    .unwrap();
+    let res = dataset().compute(req).expect("Calculation failed");
+
+    assert_eq!(res.column("Bucket").unwrap().dtype(), &DataType::Int64);
+    assert_eq!(res.height(), 2);
+}
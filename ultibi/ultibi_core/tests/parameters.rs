@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use ultibi_core::{AggregationRequest, ComputeRequest, DataSet};
+
+mod common;
+
+fn template() -> AggregationRequest {
+    let req = r#"
+    {"measures": [
+        ["Balance", "sum"]
+            ],
+    "groupby": ["State"],
+    "filters": [[{"op": "Eq", "field": "State", "value": "{{state}}"}]],
+    "parameters": [
+        {"name": "state", "options": ["NY", "Washington", "California"]},
+        {"name": "min_balance", "type": "Number", "default": "0"}
+    ]
+    }"#;
+    serde_json::from_str::<AggregationRequest>(req).expect("Could not parse request")
+}
+
+#[test]
+fn substitute_and_compute() {
+    let params = BTreeMap::from([("state".to_string(), "NY".to_string())]);
+    let req = template().with_parameters(&params).unwrap();
+
+    assert!(req.parameters.is_empty());
+    assert_eq!(
+        serde_json::to_value(&req.filters).unwrap()[0][0]["value"],
+        "NY"
+    );
+
+    let res = common::TEST_DASET
+        .as_ref()
+        .compute(ComputeRequest::Aggregation(req))
+        .unwrap();
+    assert_eq!(res.height(), 1);
+}
+
+#[test]
+fn required_parameters() {
+    let t = template();
+    let required = t.required_parameters();
+    assert_eq!(required.len(), 1);
+    assert_eq!(required[0].name, "state");
+    assert!(t.placeholders().unwrap().contains("state"));
+}
+
+#[test]
+fn invalid_parameters() {
+    let t = template();
+
+    // Missing
+    assert!(t.with_parameters(&BTreeMap::new()).is_err());
+    // Not one of the options
+    let params = BTreeMap::from([("state".to_string(), "Texas".to_string())]);
+    assert!(t.with_parameters(&params).is_err());
+    // Wrong type
+    let params = BTreeMap::from([
+        ("state".to_string(), "NY".to_string()),
+        ("min_balance".to_string(), "a lot".to_string()),
+    ]);
+    assert!(t.with_parameters(&params).is_err());
+    // Unknown
+    let params = BTreeMap::from([
+        ("state".to_string(), "NY".to_string()),
+        ("desk".to_string(), "FX".to_string()),
+    ]);
+    assert!(t.with_parameters(&params).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Missing parameter state")]
+fn compute_unresolved_template() {
+    common::TEST_DASET
+        .as_ref()
+        .compute(ComputeRequest::Aggregation(template()))
+        .unwrap();
+}
@@ -0,0 +1,130 @@
+//! Crosstab output
+
+use std::collections::BTreeMap;
+
+use polars::prelude::*;
+use ultibi_core::{datasource::DataSource, ComputeRequest, DataSet, DataSetBase};
+mod common;
+
+fn compute(req: &str) -> DataFrame {
+    let data_req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    (*common::TEST_DASET)
+        .as_ref()
+        .compute(data_req)
+        .expect("Calculation failed")
+}
+
+#[test]
+fn city_across() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "pivot": {"rows": ["State"], "column": "City", "order": ["Sun Diego"], "totals": true}
+    }"#,
+    );
+
+    assert_eq!(
+        res.get_column_names(),
+        [
+            "State",
+            "Sun Diego",
+            "Buffalo",
+            "New York",
+            "Forks",
+            "Total"
+        ]
+    );
+    assert_eq!(res.height(), 3);
+
+    let ny = res
+        .lazy()
+        .filter(col("State").eq(lit("NY")))
+        .collect()
+        .unwrap();
+    assert_eq!(ny.column("Total").unwrap().sum::<f64>().unwrap(), 25.);
+    assert!(ny.column("Forks").unwrap().is_null().all());
+}
+
+#[test]
+fn totals_of_non_additive() {
+    let res = compute(
+        r#"
+    {"measures": [["Balance", "sum"], ["Age", "mean"]],
+    "pivot": {"column": "State", "totals": true}
+    }"#,
+    );
+
+    assert_eq!(res.height(), 1);
+    assert_eq!(
+        res.column("Balance_sum_State_Total")
+            .unwrap()
+            .sum::<f64>()
+            .unwrap(),
+        85.
+    );
+    // Mean of all rows, not mean of the means of states
+    let age = res
+        .column("Age_mean_State_Total")
+        .unwrap()
+        .sum::<f64>()
+        .unwrap();
+    assert!((age - 17.38).abs() < 1e-9);
+}
+
+#[test]
+fn column_among_rows() {
+    let req = serde_json::from_str::<ComputeRequest>(
+        r#"
+    {"measures": [["Balance", "sum"]],
+    "pivot": {"rows": ["State"], "column": "State"}
+    }"#,
+    )
+    .unwrap();
+    let err = (*common::TEST_DASET).as_ref().compute(req).unwrap_err();
+
+    assert_eq!(err.code(), "invalid_request");
+}
+
+#[test]
+fn clashing_column_names() {
+    let df = polars::df!(
+        "Desk" => ["FX", "FX", "FX", "FX", "Rates"],
+        "Book" => [Some("Desk"), Some("Total"), Some("null"), None, Some("Total")],
+        "Notional" => [1., 2., 3., 4., 5.]
+    )
+    .unwrap();
+    let data = DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        BTreeMap::new(),
+    );
+    let req = serde_json::from_str::<ComputeRequest>(
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "pivot": {"rows": ["Desk"], "column": "Book", "totals": true}
+    }"#,
+    )
+    .unwrap();
+    let res = data.compute(req).expect("Calculation failed");
+
+    assert_eq!(
+        res.get_column_names(),
+        [
+            "Desk",
+            "Book_Desk",
+            "Book_Total",
+            "null",
+            "Book_null",
+            "Total"
+        ]
+    );
+    let fx = res
+        .lazy()
+        .filter(col("Desk").eq(lit("FX")))
+        .collect()
+        .unwrap();
+    assert_eq!(fx.column("Book_null").unwrap().sum::<f64>().unwrap(), 4.);
+    assert_eq!(fx.column("Total").unwrap().sum::<f64>().unwrap(), 10.);
+}
@@ -0,0 +1,155 @@
+//! Several COB dates in one DataSet, requested as_of one of them and compared to another
+
+use std::collections::BTreeMap;
+
+use polars::prelude::*;
+use ultibi_core::{
+    datasource::DataSource, errors::UltimaErr, new::NewSourcedDataSet, ComputeRequest, DataSet,
+    DataSetBase,
+};
+
+fn dataset() -> DataSetBase {
+    let df = polars::df!(
+        "COB" => ["2024-01-30", "2024-01-30", "2024-01-30", "2024-01-31", "2024-01-31", "2024-01-31"],
+        "Desk" => ["FX", "FX", "Rates", "FX", "FX", "Credit"],
+        "Notional" => [10., 20., 30., 15., 5., 7.]
+    )
+    .unwrap();
+
+    DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        BTreeMap::from([("snapshot_column".to_string(), "COB".to_string())]),
+    )
+}
+
+fn compute(data: &DataSetBase, req: &str) -> Result<DataFrame, UltimaErr> {
+    let req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    data.compute(req)
+}
+
+fn value_of(res: &DataFrame, desk: &str, column: &str) -> Option<f64> {
+    res.clone()
+        .lazy()
+        .filter(col("Desk").eq(lit(desk)))
+        .select([col(column).cast(DataType::Float64)])
+        .collect()
+        .unwrap()
+        .column(column)
+        .unwrap()
+        .f64()
+        .unwrap()
+        .get(0)
+}
+
+#[test]
+fn as_of_defaults_to_the_latest_snapshot() {
+    let data = dataset();
+    assert_eq!(data.snapshots().unwrap(), ["2024-01-30", "2024-01-31"]);
+
+    let req = r#"{"measures": [["Notional", "sum"]], "groupby": ["Desk"]}"#;
+    let latest = compute(&data, req).unwrap();
+    assert_eq!(latest.height(), 2);
+    assert_eq!(value_of(&latest, "FX", "Notional_sum"), Some(20.));
+
+    let previous = compute(
+        &data,
+        r#"{"measures": [["Notional", "sum"]], "groupby": ["Desk"], "as_of": "2024-01-30"}"#,
+    )
+    .unwrap();
+    assert_eq!(value_of(&previous, "FX", "Notional_sum"), Some(30.));
+    assert_eq!(value_of(&previous, "Credit", "Notional_sum"), None);
+
+    // Appended rows of a new date become the latest snapshot
+    let mut data = data;
+    let new_cob = polars::df!(
+        "COB" => ["2024-02-01"],
+        "Desk" => ["FX"],
+        "Notional" => [1.]
+    )
+    .unwrap();
+    data.append_frame(new_cob).unwrap();
+    let latest = compute(&data, req).unwrap();
+    assert_eq!(value_of(&latest, "FX", "Notional_sum"), Some(1.));
+}
+
+#[test]
+fn compare_to() {
+    let res = compute(
+        &dataset(),
+        r#"
+    {"measures": [["Notional", "sum"], ["Notional", "max"]],
+    "groupby": ["Desk"],
+    "as_of": "2024-01-31",
+    "compare_to": "2024-01-30"
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(
+        res.get_column_names(),
+        [
+            "Desk",
+            "Notional_sum",
+            "Notional_sum_compare",
+            "Notional_sum_change",
+            "Notional_max",
+            "Notional_max_compare",
+            "Notional_max_change"
+        ]
+    );
+    assert_eq!(res.height(), 3);
+    assert_eq!(value_of(&res, "FX", "Notional_sum_change"), Some(-10.));
+    // Rows of only one of the snapshots change from or to zero
+    assert_eq!(value_of(&res, "Rates", "Notional_sum"), None);
+    assert_eq!(value_of(&res, "Rates", "Notional_sum_change"), Some(-30.));
+    assert_eq!(value_of(&res, "Credit", "Notional_sum_change"), Some(7.));
+    // Non linear measures are computed for each date, then differenced
+    assert_eq!(value_of(&res, "FX", "Notional_max"), Some(15.));
+    assert_eq!(value_of(&res, "FX", "Notional_max_compare"), Some(20.));
+    assert_eq!(value_of(&res, "FX", "Notional_max_change"), Some(-5.));
+}
+
+#[test]
+fn compare_to_without_groupby() {
+    // Credit is only in the latest snapshot
+    let res = compute(
+        &dataset(),
+        r#"
+    {"measures": [["Notional", "sum"]],
+    "filters": [[{"op": "Eq", "field": "Desk", "value": "Credit"}]],
+    "compare_to": "2024-01-30"
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(res.height(), 1);
+    let change = res.column("Notional_sum_change").unwrap().f64().unwrap();
+    assert_eq!(change.get(0), Some(7.));
+}
+
+#[test]
+fn invalid_snapshots() {
+    let err = compute(
+        &dataset(),
+        r#"{"measures": [["Notional", "sum"]], "groupby": ["Desk"], "compare_to": "2023-12-29"}"#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, UltimaErr::InvalidRequest(_)));
+
+    let without_snapshots = DataSetBase::from_vec(
+        DataSource::InMemory(polars::df!("Notional" => [1.]).unwrap()),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    );
+    let err = compute(
+        &without_snapshots,
+        r#"{"measures": [["Notional", "sum"]], "as_of": "2024-01-31"}"#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, UltimaErr::InvalidRequest(_)));
+}
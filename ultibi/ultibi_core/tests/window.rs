@@ -0,0 +1,189 @@
+//! Measures evaluated over partitions of the result
+
+use polars::prelude::*;
+use ultibi_core::{
+    datasource::DataSource, errors::UltimaErr, new::NewSourcedDataSet, window::WindowMeasure,
+    ComputeRequest, DataSet, DataSetBase,
+};
+
+fn dataset() -> DataSetBase {
+    let df = polars::df!(
+        "Region" => ["EMEA", "EMEA", "EMEA", "APAC", "APAC"],
+        "Desk" => ["FX", "FX", "Rates", "FX", "Rates"],
+        "Month" => [2, 1, 1, 1, 2],
+        "Charge" => [30., 10., 20., 40., 5.]
+    )
+    .unwrap();
+
+    let mut data = DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    );
+
+    let windows = serde_json::from_str::<Vec<WindowMeasure>>(
+        r#"[{"name": "Charge Rank", "measure": ["Charge", "sum"],
+            "partition_by": ["Region"], "function": "rank"}]"#,
+    )
+    .unwrap();
+    data.set_windows(windows).unwrap();
+    data
+}
+
+fn compute(req: &str) -> Result<DataFrame, UltimaErr> {
+    let req = serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request");
+    dataset().compute(req)
+}
+
+fn value_of(res: &DataFrame, predicate: Expr, column: &str) -> Option<f64> {
+    res.clone()
+        .lazy()
+        .filter(predicate)
+        .select([col(column).cast(DataType::Float64)])
+        .collect()
+        .unwrap()
+        .column(column)
+        .unwrap()
+        .f64()
+        .unwrap()
+        .get(0)
+}
+
+fn cell(region: &str, desk: &str) -> Expr {
+    col("Region").eq(lit(region)).and(col("Desk").eq(lit(desk)))
+}
+
+#[test]
+fn share_of_parent_with_totals() {
+    let res = compute(
+        r#"
+    {"measures": [["Charge", "sum"]],
+    "groupby": ["Region", "Desk"],
+    "grouping_sets": {"type": "rollup"},
+    "windows": [
+        {"name": "Share of Region", "measure": ["Charge", "sum"],
+            "partition_by": ["Region"], "function": "share_of_parent"},
+        {"name": "Share of Total", "measure": ["Charge", "sum"], "function": "share_of_parent"}
+    ]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    assert_eq!(
+        value_of(&res, cell("EMEA", "FX"), "Share of Region"),
+        Some(40. / 60.)
+    );
+    assert_eq!(
+        value_of(&res, cell("APAC", "Rates"), "Share of Total"),
+        Some(5. / 105.)
+    );
+
+    // Subtotals are shares of the other subtotals of the same level
+    let emea = col("Region").eq(lit("EMEA")).and(col("Desk").is_null());
+    assert_eq!(value_of(&res, emea.clone(), "Share of Region"), Some(1.));
+    assert_eq!(value_of(&res, emea, "Share of Total"), Some(60. / 105.));
+    let grand_total = col("Region").is_null();
+    assert_eq!(value_of(&res, grand_total, "Share of Total"), Some(1.));
+}
+
+#[test]
+fn rank_declared_on_dataset() {
+    let res = compute(
+        r#"
+    {"measures": [["Charge Rank", "scalar"]],
+    "groupby": ["Region", "Desk"]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    // The measure the window is over is not requested, hence not returned
+    assert_eq!(res.get_column_names(), ["Region", "Desk", "Charge Rank"]);
+    assert_eq!(value_of(&res, cell("EMEA", "FX"), "Charge Rank"), Some(1.));
+    assert_eq!(
+        value_of(&res, cell("EMEA", "Rates"), "Charge Rank"),
+        Some(2.)
+    );
+    assert_eq!(value_of(&res, cell("APAC", "FX"), "Charge Rank"), Some(1.));
+}
+
+#[test]
+fn running_total_and_difference() {
+    let res = compute(
+        r#"
+    {"measures": [["Charge", "sum"]],
+    "groupby": ["Desk", "Month"],
+    "windows": [
+        {"name": "YTD", "measure": ["Charge", "sum"], "partition_by": ["Desk"],
+            "function": "cum_sum", "order_by": ["Month"]},
+        {"name": "MoM", "measure": ["Charge", "sum"], "partition_by": ["Desk"],
+            "function": "diff", "order_by": ["Month"]}
+    ]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    let period =
+        |desk: &str, month: i32| col("Desk").eq(lit(desk)).and(col("Month").eq(lit(month)));
+    assert_eq!(value_of(&res, period("FX", 1), "YTD"), Some(50.));
+    assert_eq!(value_of(&res, period("FX", 2), "YTD"), Some(80.));
+    assert_eq!(value_of(&res, period("Rates", 2), "YTD"), Some(25.));
+    assert_eq!(value_of(&res, period("FX", 1), "MoM"), None);
+    assert_eq!(value_of(&res, period("FX", 2), "MoM"), Some(-20.));
+    assert_eq!(value_of(&res, period("Rates", 2), "MoM"), Some(-15.));
+}
+
+#[test]
+fn invalid_windows() {
+    for window in [
+        r#"{"name": "X", "measure": ["Charge", "sum"], "partition_by": ["Month"], "function": "rank"}"#,
+        r#"{"name": "X", "measure": ["Charge", "sum"], "function": "cum_sum", "order_by": ["Month"]}"#,
+        r#"{"name": "Charge Rank", "measure": ["Charge", "sum"], "function": "rank"}"#,
+        r#"{"name": "X", "measure": ["Charge", "nosuchagg"], "function": "rank"}"#,
+    ] {
+        let err = compute(&format!(
+            r#"{{"measures": [["Charge", "sum"]], "groupby": ["Desk"], "windows": [{window}]}}"#
+        ))
+        .unwrap_err();
+
+        assert!(
+            matches!(
+                err,
+                UltimaErr::InvalidRequest(_) | UltimaErr::UnknownAggregation { .. }
+            ),
+            "{window}"
+        );
+    }
+}
+
+#[test]
+fn share_of_parent_which_is_recomputed() {
+    let res = compute(
+        r#"
+    {"measures": [["Charge", "mean"]],
+    "groupby": ["Region", "Desk"],
+    "grouping_sets": {"type": "rollup"},
+    "windows": [
+        {"name": "Share of Region", "measure": ["Charge", "mean"],
+            "partition_by": ["Region"], "function": "share_of_parent"}
+    ]
+    }"#,
+    )
+    .expect("Calculation failed");
+
+    // Means don't add up: the parent is the mean of the Region, not the sum of the means of its Desks
+    assert_eq!(
+        value_of(&res, cell("APAC", "FX"), "Share of Region"),
+        Some(40. / 22.5)
+    );
+    assert_eq!(
+        value_of(&res, cell("EMEA", "Rates"), "Share of Region"),
+        Some(1.)
+    );
+    let apac = col("Region").eq(lit("APAC")).and(col("Desk").is_null());
+    assert_eq!(value_of(&res, apac, "Share of Region"), Some(1.));
+    // The Region of the grand total is rolled up, hence its parent is the grand total
+    let grand_total = col("Region").is_null();
+    assert_eq!(value_of(&res, grand_total, "Share of Region"), Some(1.));
+}
@@ -1,16 +1,7 @@
-//! This crate accrues Ultibi In/Out operations 
+//! This crate accrues Ultibi In/Out operations
 
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
-}
+pub mod readers;
+pub mod writers;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use readers::{read, read_csv, read_ipc_stream, read_json, read_parquet};
+pub use writers::{write, write_csv, write_ipc_stream, write_json, write_parquet, OutputFormat};
@@ -0,0 +1,63 @@
+//! Deserialises frames sent by clients, eg rows to append to a DataSet
+
+use std::io::Cursor;
+
+use polars::prelude::{CsvReader, DataFrame, IpcStreamReader, ParquetReader, SerReader};
+use ultibi_core::errors::UltiResult;
+
+use crate::OutputFormat;
+
+/// Reads `body` in the format it was written in, see [crate::write]
+pub fn read(body: &[u8], format: OutputFormat) -> UltiResult<DataFrame> {
+    match format {
+        OutputFormat::Json => read_json(body),
+        OutputFormat::Csv => read_csv(body),
+        OutputFormat::Parquet => read_parquet(body),
+        OutputFormat::Arrow => read_ipc_stream(body),
+    }
+}
+
+/// Polars' JSON column format, as returned by `execute`
+pub fn read_json(body: &[u8]) -> UltiResult<DataFrame> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+/// With a header. Types are inferred
+pub fn read_csv(body: &[u8]) -> UltiResult<DataFrame> {
+    Ok(CsvReader::new(Cursor::new(body)).finish()?)
+}
+
+pub fn read_parquet(body: &[u8]) -> UltiResult<DataFrame> {
+    Ok(ParquetReader::new(Cursor::new(body)).finish()?)
+}
+
+/// Arrow IPC stream
+pub fn read_ipc_stream(body: &[u8]) -> UltiResult<DataFrame> {
+    Ok(IpcStreamReader::new(Cursor::new(body)).finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::df;
+
+    use super::*;
+    use crate::write;
+
+    #[test]
+    fn roundtrip() {
+        let df = df!(
+            "State" => ["NY", "Washington"],
+            "Balance" => [25.0, 20.0]
+        )
+        .unwrap();
+        for format in [
+            OutputFormat::Json,
+            OutputFormat::Csv,
+            OutputFormat::Parquet,
+            OutputFormat::Arrow,
+        ] {
+            let body = write(&mut df.clone(), format).unwrap();
+            assert_eq!(read(&body, format).unwrap(), df, "{format:?}");
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Serialises results of computations into formats suitable for download
+
+use std::str::FromStr;
+
+use polars::prelude::{CsvWriter, DataFrame, IpcStreamWriter, ParquetWriter, SerWriter};
+use serde::{Deserialize, Serialize};
+use ultibi_core::errors::UltiResult;
+
+/// Supported output formats
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Polars' JSON column format
+    #[default]
+    Json,
+    Csv,
+    Parquet,
+    /// Arrow IPC stream
+    Arrow,
+}
+
+impl OutputFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "application/json",
+            OutputFormat::Csv => "text/csv",
+            OutputFormat::Parquet => "application/vnd.apache.parquet",
+            OutputFormat::Arrow => "application/vnd.apache.arrow.stream",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Parquet => "parquet",
+            OutputFormat::Arrow => "arrows",
+        }
+    }
+
+    /// Maps a mime type (as in an Accept header) to a format
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        match mime {
+            "application/json" => Some(OutputFormat::Json),
+            "text/csv" => Some(OutputFormat::Csv),
+            "application/vnd.apache.parquet" | "application/x-parquet" => {
+                Some(OutputFormat::Parquet)
+            }
+            "application/vnd.apache.arrow.stream" => Some(OutputFormat::Arrow),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "parquet" => Ok(OutputFormat::Parquet),
+            "arrow" | "ipc" => Ok(OutputFormat::Arrow),
+            _ => Err(format!(
+                "Unsupported format {s}. Supported formats are: json, csv, parquet, arrow"
+            )),
+        }
+    }
+}
+
+/// Writes `df` in the requested format
+pub fn write(df: &mut DataFrame, format: OutputFormat) -> UltiResult<Vec<u8>> {
+    match format {
+        OutputFormat::Json => write_json(df),
+        OutputFormat::Csv => write_csv(df),
+        OutputFormat::Parquet => write_parquet(df),
+        OutputFormat::Arrow => write_ipc_stream(df),
+    }
+}
+
+/// Same format as returned by `execute` by default
+pub fn write_json(df: &DataFrame) -> UltiResult<Vec<u8>> {
+    Ok(serde_json::to_vec(df)?)
+}
+
+/// Note: nested columns (eg Lists) are not supported by CSV
+pub fn write_csv(df: &mut DataFrame) -> UltiResult<Vec<u8>> {
+    let mut buf = vec![];
+    CsvWriter::new(&mut buf).include_header(true).finish(df)?;
+    Ok(buf)
+}
+
+pub fn write_parquet(df: &mut DataFrame) -> UltiResult<Vec<u8>> {
+    let mut buf = vec![];
+    ParquetWriter::new(&mut buf).finish(df)?;
+    Ok(buf)
+}
+
+/// Arrow IPC stream. Polars specific types (eg string views) are converted to
+/// standard Arrow types so that any Arrow reader can consume the stream.
+pub fn write_ipc_stream(df: &mut DataFrame) -> UltiResult<Vec<u8>> {
+    let mut buf = vec![];
+    IpcStreamWriter::new(&mut buf)
+        .with_pl_flavor(false)
+        .finish(df)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use polars::df;
+    use polars::prelude::{IpcStreamReader, ParquetReader, SerReader};
+
+    use super::*;
+
+    fn frame() -> DataFrame {
+        df!(
+            "State" => ["NY", "Washington"],
+            "Balance_sum" => [25.0, 20.0]
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn csv() {
+        let res = write_csv(&mut frame()).unwrap();
+        assert_eq!(
+            String::from_utf8(res).unwrap(),
+            "State,Balance_sum\nNY,25.0\nWashington,20.0\n"
+        );
+    }
+
+    #[test]
+    fn parquet_roundtrip() {
+        let mut df = frame();
+        let res = write_parquet(&mut df).unwrap();
+        let back = ParquetReader::new(Cursor::new(res)).finish().unwrap();
+        assert_eq!(back, df);
+    }
+
+    #[test]
+    fn ipc_stream_roundtrip() {
+        let mut df = frame();
+        let res = write_ipc_stream(&mut df).unwrap();
+        let back = IpcStreamReader::new(Cursor::new(res)).finish().unwrap();
+        assert_eq!(back, df);
+    }
+
+    #[test]
+    fn format_from_mime() {
+        assert_eq!(OutputFormat::from_mime("text/csv"), Some(OutputFormat::Csv));
+        assert_eq!(OutputFormat::from_mime("text/html"), None);
+        assert_eq!("Arrow".parse::<OutputFormat>(), Ok(OutputFormat::Arrow));
+        assert!("xlsx".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Administration of the server. Only for admins, see [crate::auth::Entitlement::admin]
+
+use std::sync::RwLock;
+
+use actix_web::{
+    get, post,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use anyhow::Context;
+use serde::Serialize;
+use tokio::task;
+use ultibi_core::{errors::UltiResult, filters::AndOrFltrChain, DataSet};
+use utoipa::ToSchema;
+
+use crate::{
+    audit::AuditLog,
+    auth::{AuthError, AuthenticatedUser},
+    errors::ApiError,
+    helpers::body_format,
+    reload::{LoadStatus, ReloadError, Reloader},
+};
+
+/// Outcome of a change of the data
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct DataChange {
+    /// Number of rows appended or deleted
+    rows: usize,
+}
+
+fn admin_only(user: &AuthenticatedUser) -> Result<(), AuthError> {
+    match user.entitlement.admin {
+        true => Ok(()),
+        false => Err(AuthError::Forbidden(format!(
+            "{} is not an administrator",
+            user.name
+        ))),
+    }
+}
+
+fn reloader(reloader: Option<Data<Reloader>>) -> Result<Data<Reloader>> {
+    reloader.ok_or_else(|| {
+        actix_web::error::ErrorNotImplemented("Reload is not set up for this server")
+    })
+}
+
+#[utoipa::path(
+    context_path = "/api",
+    get,
+    responses(
+        (status = 200, description = "Status of the last reload", body = LoadStatus),
+        (status = 501, description = "Reload is not set up")
+    )
+)]
+#[get("/admin/reload")]
+pub(crate) async fn reload_status(
+    user: AuthenticatedUser,
+    r: Option<Data<Reloader>>,
+) -> Result<HttpResponse> {
+    admin_only(&user)?;
+    Ok(HttpResponse::Ok().json(reloader(r)?.status()))
+}
+
+#[utoipa::path(
+    context_path = "/api",
+    post,
+    responses(
+        (status = 202, description = "Reload started. Poll GET /admin/reload for the outcome", body = LoadStatus),
+        (status = 409, description = "A reload is already in progress"),
+        (status = 501, description = "Reload is not set up")
+    )
+)]
+#[tracing::instrument(name = "Reload", skip(user, r))]
+#[post("/admin/reload")]
+pub(crate) async fn reload(
+    user: AuthenticatedUser,
+    r: Option<Data<Reloader>>,
+) -> Result<HttpResponse> {
+    admin_only(&user)?;
+    tracing::info!("Reload requested by {}", user.name);
+    match reloader(r)?.spawn_reload() {
+        Ok(status) => Ok(HttpResponse::Accepted().json(status)),
+        Err(ReloadError::InProgress) => Err(actix_web::error::ErrorConflict(
+            "A reload is already in progress",
+        )),
+        Err(ReloadError::Failed(e)) => Err(actix_web::error::ErrorInternalServerError(e)),
+    }
+}
+
+#[utoipa::path(
+    context_path = "/api",
+    post,
+    request_body(content = DataFrame, description = "Rows to append, with the columns of the data before it's prepared, including the joined attributes and hierarchies. Format is determined by the Content-Type",
+        content_type = ["application/json", "text/csv", "application/vnd.apache.parquet", "application/vnd.apache.arrow.stream"]),
+    responses(
+        (status = 200, description = "Rows have been validated, prepared and appended. Cache is cleaned", body = DataChange),
+        (status = 400, description = "Invalid rows (eg a required column is missing)", body = ApiError),
+        (status = 415, description = "Unsupported Content-Type", body = ApiError),
+        (status = 500, description = "Data can't be appended to (eg it's not In Memory)", body = ApiError)
+    )
+)]
+#[tracing::instrument(name = "Append", skip(user, http_req, data, audit, body))]
+#[post("/admin/data/append")]
+pub(crate) async fn append(
+    user: AuthenticatedUser,
+    http_req: HttpRequest,
+    data: Data<RwLock<dyn DataSet>>,
+    audit: Data<AuditLog>,
+    body: web::Bytes,
+) -> Result<HttpResponse> {
+    admin_only(&user)?;
+    let format = body_format(&http_req)?;
+
+    let name = user.name.clone();
+    let res = task::spawn_blocking(move || {
+        let df = ultibi_io::read(&body, format)?;
+        let mut ds = data.write().expect("Poisonned RwLock");
+        audit.append_frame(&mut *ds, df, &name)
+    })
+    .await
+    .context("Failed to spawn blocking task.")
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    data_change(res, "appended", &user)
+}
+
+#[utoipa::path(
+    context_path = "/api",
+    post,
+    request_body(content = Vec<Vec<FilterE>>, description = "Rows to delete. Required", content_type = "application/json",
+        example = json!([[{"op": "Eq", "field": "TradeId", "value": "EQ14"}]])
+    ),
+    responses(
+        (status = 200, description = "Rows have been deleted. Cache is cleaned", body = DataChange),
+        (status = 400, description = "Invalid filters (eg none)", body = ApiError),
+        (status = 500, description = "Data can't be deleted from (eg it's not In Memory)", body = ApiError)
+    )
+)]
+#[tracing::instrument(name = "Delete", skip(user, data, audit))]
+#[post("/admin/data/delete")]
+pub(crate) async fn delete(
+    user: AuthenticatedUser,
+    data: Data<RwLock<dyn DataSet>>,
+    audit: Data<AuditLog>,
+    filters: web::Json<AndOrFltrChain>,
+) -> Result<HttpResponse> {
+    admin_only(&user)?;
+    let filters = filters.into_inner();
+
+    let name = user.name.clone();
+    let res = task::spawn_blocking(move || {
+        let mut ds = data.write().expect("Poisonned RwLock");
+        audit.delete_where(&mut *ds, filters, &name)
+    })
+    .await
+    .context("Failed to spawn blocking task.")
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    data_change(res, "deleted", &user)
+}
+
+fn data_change(
+    res: UltiResult<usize>,
+    action: &str,
+    user: &AuthenticatedUser,
+) -> Result<HttpResponse> {
+    match res {
+        Ok(rows) => {
+            tracing::info!("{} {action} {rows} rows", user.name);
+            Ok(HttpResponse::Ok().json(DataChange { rows }))
+        }
+        Err(e) => {
+            tracing::error!("Rows could not be {action}: {:?}", e);
+            Err(ApiError::from(e).into())
+        }
+    }
+}
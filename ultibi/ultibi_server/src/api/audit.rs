@@ -0,0 +1,160 @@
+//! List audit entries and replay them against the current data
+
+use std::sync::RwLock;
+
+use actix_web::{
+    get,
+    http::StatusCode,
+    post,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use serde::Deserialize;
+use tokio::task;
+use ultibi_core::DataSet;
+use utoipa::IntoParams;
+
+use crate::{
+    audit::{AuditEntry, AuditId, AuditLog},
+    auth::AuthenticatedUser,
+    errors::ApiError,
+    helpers::{formatted_response, negotiate_format, with_truncation, FormatParams},
+    jobs::JobRegistry,
+    metrics::Metrics,
+};
+
+const DEFAULT_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct AuditParams {
+    /// Only entries of this user. Users who are not admins only see their own entries
+    user: Option<String>,
+    #[serde(default)]
+    offset: usize,
+    /// Defaults to 100
+    limit: Option<usize>,
+}
+
+/// Admins can see every entry, others only their own
+fn can_see(entry: &AuditEntry, user: &AuthenticatedUser) -> bool {
+    user.entitlement.admin || entry.user == user.name
+}
+
+async fn visible_entry(
+    audit: &Data<AuditLog>,
+    id: AuditId,
+    user: &AuthenticatedUser,
+) -> Result<AuditEntry> {
+    let audit = audit.clone();
+    task::spawn_blocking(move || audit.get(id))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .filter(|e| can_see(e, user))
+        .ok_or_else(|| actix_web::error::ErrorNotFound(format!("No audit entry {id}")))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    params(AuditParams),
+    responses(
+        (status = 200, description = "Audit entries, most recent first", body = [AuditEntry])
+    )
+)]
+#[get("/audit")]
+pub(crate) async fn audit_entries(
+    user: AuthenticatedUser,
+    audit: Data<AuditLog>,
+    params: web::Query<AuditParams>,
+) -> Result<HttpResponse> {
+    let params = params.into_inner();
+    let filter = match user.entitlement.admin {
+        true => params.user,
+        false => Some(user.name),
+    };
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+
+    let entries = task::spawn_blocking(move || audit.list(filter.as_deref(), params.offset, limit))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    params(("id" = u64, Path, description = "Audit entry id")),
+    responses(
+        (status = 200, description = "Audit entry", body = AuditEntry),
+        (status = 404, description = "No such entry")
+    )
+)]
+#[get("/audit/{id}")]
+pub(crate) async fn audit_entry(
+    user: AuthenticatedUser,
+    path: web::Path<AuditId>,
+    audit: Data<AuditLog>,
+) -> Result<HttpResponse> {
+    let entry = visible_entry(&audit, path.into_inner(), &user).await?;
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    post,
+    params(("id" = u64, Path, description = "Audit entry id"), FormatParams),
+    responses(
+        (status = 200, description = "Result of the request of the entry, against the current data", body = DataFrame),
+        (status = 400, description = "The request is no longer valid (eg the measure was removed), or the entry is not a request", body = ApiError),
+        (status = 404, description = "No such entry"),
+        (status = 500, description = "Computation failed", body = ApiError)
+    )
+)]
+#[tracing::instrument(name = "Replay", skip(user, http_req, data, audit, metrics, jobs))]
+#[post("/audit/{id}/replay")]
+pub(crate) async fn replay(
+    user: AuthenticatedUser,
+    http_req: HttpRequest,
+    path: web::Path<AuditId>,
+    data: Data<RwLock<dyn DataSet>>,
+    audit: Data<AuditLog>,
+    metrics: Data<Metrics>,
+    jobs: Data<JobRegistry>,
+    params: web::Query<FormatParams>,
+) -> Result<HttpResponse> {
+    let format = negotiate_format(&http_req, &params)?;
+    let id = path.into_inner();
+    let ctx = jobs.context(None);
+
+    let entry = visible_entry(&audit, id, &user).await?;
+    let Some(request) = entry.request else {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            format!("Entry {id} is a change of the data, not a request"),
+        )
+        .into());
+    };
+    // Current entitlements of the user apply, not the ones at the time of the entry
+    let r = user.entitlement.restrict(request)?;
+
+    let compute_ctx = ctx.clone();
+    let res = task::spawn_blocking(move || {
+        let source = format!("replay of {id}");
+        let ds = data.read().expect("Poisonned RwLock");
+        let res = audit.compute(&*ds, r, &compute_ctx, &user.name, &source);
+        metrics.observe_compute(&compute_ctx, &res);
+        ultibi_io::write(&mut res?, format)
+    })
+    .await
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    match res {
+        Ok(body) => Ok(with_truncation(formatted_response(body, format), &ctx)),
+        Err(e) => {
+            tracing::error!("Failed to replay {id}: {:?}", e);
+            Err(ApiError::from(e).into())
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Several compute requests in one call (eg all the widgets of a dashboard)
+//! Requests sharing filters, overrides and additional rows share the work, see [DataSet::compute_many]
+
+use std::sync::RwLock;
+
+use actix_web::{
+    post,
+    web::{self, Data},
+    HttpResponse, Result,
+};
+use serde::Serialize;
+use tokio::task;
+use ultibi_core::{ComputeRequest, DataFrame, DataSet};
+use utoipa::ToSchema;
+
+use crate::{
+    audit::AuditLog, auth::AuthenticatedUser, errors::ApiError, jobs::JobRegistry, metrics::Metrics,
+};
+
+/// Outcome of one request of the batch
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchResult {
+    Ok(DataFrame),
+    Error(ApiError),
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    post,
+    request_body(content = Vec<ComputeRequest>, description = "Requests to compute together", content_type = "application/json"),
+    responses(
+        (status = 200, description = "One result per request, in the order of the requests", body = [BatchResult])
+    )
+)]
+#[tracing::instrument(name = "Batch Execution", skip(user, data, jobs, audit, metrics))]
+#[post("/batch")]
+pub(crate) async fn execute_batch(
+    user: AuthenticatedUser,
+    data: Data<RwLock<dyn DataSet>>,
+    jobs: Data<JobRegistry>,
+    audit: Data<AuditLog>,
+    metrics: Data<Metrics>,
+    reqs: web::Json<Vec<ComputeRequest>>,
+) -> Result<HttpResponse> {
+    let rs = reqs
+        .into_inner()
+        .into_iter()
+        .map(|r| user.entitlement.restrict(r))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let ctx = jobs.context(None);
+
+    let res = task::spawn_blocking(move || {
+        let ds = data.read().expect("Poisonned RwLock");
+        let res = audit.compute_many(&*ds, rs, &ctx, &user.name, "batch");
+        metrics.observe_computations(&ctx, &res);
+        res
+    })
+    .await
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let body = res
+        .into_iter()
+        .map(|r| match r {
+            Ok(df) => BatchResult::Ok(df),
+            Err(e) => {
+                tracing::error!("Failed to execute batch request: {:?}", e);
+                BatchResult::Error(e.into())
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(body))
+}
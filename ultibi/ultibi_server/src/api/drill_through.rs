@@ -0,0 +1,103 @@
+//! Rows behind a cell of a result, see [DataSet::drill_through]
+
+use std::sync::RwLock;
+
+use actix_web::{
+    http::header::{HeaderName, HeaderValue},
+    post,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use anyhow::Context;
+use tokio::task;
+use ultibi_core::{errors::UltimaErr, ComputeRequest, DataSet, DrillThroughRequest};
+
+use crate::{
+    audit::AuditLog,
+    auth::AuthenticatedUser,
+    errors::ApiError,
+    helpers::{formatted_response, negotiate_format, FormatParams},
+    jobs::JobRegistry,
+    metrics::Metrics,
+};
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    post,
+    params(FormatParams),
+    request_body(content = DrillThroughRequest, description = "Cell of the result of a request", content_type = "application/json",
+        example = json!({
+            "request": {
+                "measures": [["EQ DeltaCharge Medium", "scalar"]],
+                "groupby": ["Desk", "BucketBCBS"],
+                "filters": [[{"op": "Eq", "field": "Group", "value": "Ultima"}]]
+            },
+            "cell": {"Desk": "EQOptions", "BucketBCBS": "5"},
+            "measure": "EQ DeltaCharge Medium",
+            "offset": 0,
+            "limit": 100
+        })
+    ),
+    responses(
+        (status = 200, description = "A page of the prepared rows. Number of rows across all pages is in the X-Total-Count header", body = DataFrame,
+         content_type = ["application/json", "text/csv", "application/vnd.apache.parquet", "application/vnd.apache.arrow.stream"]),
+        (status = 403, description = "Request is outside of the user's entitlements", body = String, content_type = "text/plain"),
+        (status = 400, description = "Invalid request (eg the cell is not in the groupby) or unsupported format", body = ApiError),
+        (status = 500, description = "Drill through failed", body = ApiError),
+        (status = 504, description = "Drill through timed out", body = ApiError)
+    )
+)]
+#[tracing::instrument(
+    name = "Drill Through",
+    skip(user, data, jobs, audit, metrics, http_req)
+)]
+#[post("/drillthrough")]
+pub(crate) async fn drill_through(
+    user: AuthenticatedUser,
+    http_req: HttpRequest,
+    data: Data<RwLock<dyn DataSet>>,
+    jobs: Data<JobRegistry>,
+    audit: Data<AuditLog>,
+    metrics: Data<Metrics>,
+    params: web::Query<FormatParams>,
+    req: web::Json<DrillThroughRequest>,
+) -> Result<HttpResponse> {
+    let format = negotiate_format(&http_req, &params)?;
+    let mut r = req.into_inner();
+    // Same entitlements as for the computation of the cell
+    r.request = match user
+        .entitlement
+        .restrict(ComputeRequest::Aggregation(r.request))?
+    {
+        ComputeRequest::Aggregation(ar) => ar,
+        _ => unreachable!("Restricting keeps the kind of the request"),
+    };
+    let ctx = jobs.context(None);
+
+    let res = task::spawn_blocking(move || {
+        let ds = data.read().expect("Poisonned RwLock");
+        let res = audit.drill_through(&*ds, r, &ctx, &user.name);
+        metrics.observe_compute(&ctx, &res);
+        let mut page = res?;
+        let body = ultibi_io::write(&mut page.rows, format)?;
+        Ok::<_, UltimaErr>((body, page.total))
+    })
+    .await
+    .context("Failed to spawn blocking task.")
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    match res {
+        Ok((body, total)) => {
+            let mut res = formatted_response(body, format);
+            res.headers_mut().insert(
+                HeaderName::from_static("x-total-count"),
+                HeaderValue::from(total),
+            );
+            Ok(res)
+        }
+        Err(e) => {
+            tracing::error!("Failed to drill through: {:?}", e);
+            Err(ApiError::from(e).into())
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Asynchronous counterpart of `execute`
+//! Submit a request, poll its status, fetch the result or cancel it
+
+use std::{sync::RwLock, time::Duration};
+
+use actix_web::{
+    delete, get, post,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use serde::Deserialize;
+use tokio::task;
+use ultibi_core::{errors::UltimaErr, ComputeRequest, DataSet};
+use utoipa::IntoParams;
+
+use crate::{
+    audit::AuditLog,
+    auth::AuthenticatedUser,
+    errors::ApiError,
+    helpers::{formatted_response, negotiate_format, FormatParams},
+    jobs::{JobId, JobRegistry, JobStatus},
+    metrics::Metrics,
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct JobParams {
+    /// Seconds. Overrides the server's default job timeout
+    timeout: Option<u64>,
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    post,
+    params(JobParams),
+    request_body(content = ComputeRequest, description = "What do you want to calculate", content_type = "application/json"),
+    responses(
+        (status = 202, description = "Job was submitted", body = JobInfo,
+         headers(("Location" = String, description = "Absolute path of the job, eg /api/FRTB/jobs/1")))
+    )
+)]
+#[tracing::instrument(
+    name = "Job Submission",
+    skip(user, http_req, data, jobs, audit, metrics)
+)]
+#[post("/jobs")]
+pub(crate) async fn submit_job(
+    user: AuthenticatedUser,
+    http_req: HttpRequest,
+    data: Data<RwLock<dyn DataSet>>,
+    jobs: Data<JobRegistry>,
+    audit: Data<AuditLog>,
+    metrics: Data<Metrics>,
+    params: web::Query<JobParams>,
+    req: web::Json<ComputeRequest>,
+) -> Result<HttpResponse> {
+    let r = user.entitlement.restrict(req.into_inner())?;
+    let ctx = jobs.context(params.timeout.map(Duration::from_secs));
+    let id = jobs.submit(ctx.clone(), &user.name);
+
+    let registry = jobs.clone();
+    let owner = user.name.clone();
+    task::spawn(async move {
+        let compute_ctx = ctx.clone();
+        let handle = task::spawn_blocking(move || {
+            let ds = data.read().expect("Poisonned RwLock");
+            let res = audit.compute(&*ds, r, &compute_ctx, &owner, "job");
+            metrics.observe_compute(&compute_ctx, &res);
+            res
+        });
+
+        // Polars can't be interrupted, so on timeout we mark the job and let
+        // the computation stop at its next checkpoint
+        let res = match ctx.timeout() {
+            Some(t) => match tokio::time::timeout(t, handle).await {
+                Ok(res) => res,
+                Err(_) => {
+                    ctx.cancel();
+                    registry.finish(id, JobStatus::TimedOut, None);
+                    return;
+                }
+            },
+            None => handle.await,
+        };
+
+        match res {
+            Ok(Ok(df)) => registry.finish(id, JobStatus::Finished, Some(df)),
+            Ok(Err(UltimaErr::Cancelled(_))) => registry.finish(id, JobStatus::Cancelled, None),
+            Ok(Err(UltimaErr::Timeout(_))) => registry.finish(id, JobStatus::TimedOut, None),
+            Ok(Err(e)) => {
+                tracing::error!("Failed to execute job {id}: {:?}", e);
+                registry.finish(id, JobStatus::Failed(e.to_string()), None)
+            }
+            Err(e) => registry.finish(id, JobStatus::Failed(e.to_string()), None),
+        }
+    });
+
+    let info = jobs.info(id, &user.name);
+    Ok(HttpResponse::Accepted()
+        .insert_header(("Location", format!("{}/{id}", http_req.path())))
+        .json(info))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    params(("id" = u64, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "Status of the job", body = JobInfo),
+        (status = 404, description = "No such job (or it expired)")
+    )
+)]
+#[get("/jobs/{id}")]
+pub(crate) async fn job_status(
+    user: AuthenticatedUser,
+    path: web::Path<JobId>,
+    jobs: Data<JobRegistry>,
+) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    match jobs.info(id, &user.name) {
+        Some(info) => Ok(HttpResponse::Ok().json(info)),
+        None => Err(actix_web::error::ErrorNotFound(format!("No job {id}"))),
+    }
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    params(("id" = u64, Path, description = "Job id"), FormatParams),
+    responses(
+        (status = 200, description = "Result of the finished job", body = DataFrame,
+         content_type = ["application/json", "text/csv", "application/vnd.apache.parquet", "application/vnd.apache.arrow.stream"]),
+        (status = 202, description = "Job is still running", body = JobInfo),
+        (status = 404, description = "No such job (or it expired)"),
+        (status = 417, description = "Job failed, was cancelled or timed out", body = JobInfo)
+    )
+)]
+#[get("/jobs/{id}/result")]
+pub(crate) async fn job_result(
+    user: AuthenticatedUser,
+    http_req: HttpRequest,
+    path: web::Path<JobId>,
+    jobs: Data<JobRegistry>,
+    params: web::Query<FormatParams>,
+) -> Result<HttpResponse> {
+    let format = negotiate_format(&http_req, &params)?;
+    let id = path.into_inner();
+    match jobs.result(id, &user.name) {
+        Some((_, Some(mut df))) => {
+            let body = task::spawn_blocking(move || ultibi_io::write(&mut df, format))
+                .await
+                .map_err(actix_web::error::ErrorInternalServerError)?
+                .map_err(ApiError::from)?;
+            Ok(formatted_response(body, format))
+        }
+        Some((info, None)) if info.status == JobStatus::Running => {
+            Ok(HttpResponse::Accepted().json(info))
+        }
+        Some((info, None)) => Ok(HttpResponse::ExpectationFailed().json(info)),
+        None => Err(actix_web::error::ErrorNotFound(format!("No job {id}"))),
+    }
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    delete,
+    params(("id" = u64, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "Job was cancelled (or had already finished)", body = JobInfo),
+        (status = 404, description = "No such job (or it expired)")
+    )
+)]
+#[delete("/jobs/{id}")]
+pub(crate) async fn cancel_job(
+    user: AuthenticatedUser,
+    path: web::Path<JobId>,
+    jobs: Data<JobRegistry>,
+) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    match jobs.cancel(id, &user.name) {
+        Some(info) => Ok(HttpResponse::Ok().json(info)),
+        None => Err(actix_web::error::ErrorNotFound(format!("No job {id}"))),
+    }
+}
@@ -1,2 +1,9 @@
+pub mod admin;
+pub mod audit;
+pub mod batch;
+pub mod drill_through;
+pub mod jobs;
 pub mod open_api;
 pub mod routers;
+pub mod status;
+pub mod templates;
@@ -1,11 +1,38 @@
-//! This is Work in progress
+//! OpenAPI spec of the server, served at `/api-doc/openapi.json`
+//!
+//! Every route registered in [routers::configure] must be listed in `paths`
+//! (see `tests/open_api.rs`)
 
-use crate::api::routers;
+use std::collections::BTreeMap;
+
+use crate::api::admin::DataChange;
+use crate::api::batch::BatchResult;
+use crate::api::status::DataSetStatus;
+use crate::api::{admin, audit, batch, drill_through, jobs, routers, status, templates};
+use crate::audit::{AuditDetail, AuditEntry};
+use crate::errors::ApiError;
+use crate::jobs::{JobInfo, JobStatus};
+use crate::reload::LoadStatus;
+use crate::templates::StoredTemplate;
 use ultibi_core::{
-    add_row::AdditionalRows, filters::FilterE, overrides::Override, reports::report::Report,
-    AggregationRequest,
+    add_row::AdditionalRows,
+    calculated::CalculatedMeasure,
+    dimensions::{BinEdges, ComputedDimension, Dimension, DimensionExpr},
+    filters::FilterE,
+    hierarchy::{Drill, Hierarchy, HierarchyKind},
+    overrides::Override,
+    reports::report::Report,
+    window::{WindowFunction, WindowMeasure},
+    AggregationRequest, CalcParamType, CalcParameter, ComputeRequest, DrillThroughRequest,
+    GroupingSets, MeasureMetadata, ParameterType, Pivot, ReportRequest, TemplateParameter,
+};
+use utoipa::{
+    openapi::{
+        security::{Http, HttpAuthScheme, SecurityRequirement, SecurityScheme},
+        ContentBuilder, ObjectBuilder, ResponseBuilder, SchemaType,
+    },
+    Modify, OpenApi, ToSchema,
 };
-use utoipa::OpenApi;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -14,18 +41,122 @@ use utoipa::OpenApi;
     ),
     paths(
         routers::execute,
+        batch::execute_batch,
+        drill_through::drill_through,
         routers::column_search,
         routers::dataset_info,
-        routers::templates,
+        templates::templates,
+        templates::template,
+        templates::create_template,
+        templates::update_template,
+        templates::delete_template,
+        templates::execute_template,
         routers::overridable_columns,
         routers::aggtypes,
         routers::describe,
+        routers::health_check,
+        routers::prometheus_metrics,
+        status::live,
+        status::ready,
+        status::status,
+        admin::reload_status,
+        admin::reload,
+        admin::append,
+        admin::delete,
+        jobs::submit_job,
+        jobs::job_status,
+        jobs::job_result,
+        jobs::cancel_job,
+        audit::audit_entries,
+        audit::audit_entry,
+        audit::replay,
     ),
     components(
-        schemas(AggregationRequest, FilterE, AdditionalRows, Override, Report)
+        schemas(ComputeRequest, AggregationRequest, CalculatedMeasure, DrillThroughRequest, Dimension, ComputedDimension, DimensionExpr, BinEdges, GroupingSets, Pivot, Drill, Hierarchy, HierarchyKind, WindowMeasure, WindowFunction, ReportRequest, FilterE, AdditionalRows, Override, Report,
+            CalcParameter, CalcParamType, MeasureMetadata, DataFrame, Series, DataSetInfo, ApiError,
+            JobInfo, JobStatus, AuditEntry, AuditDetail, BatchResult, LoadStatus, DataChange, DataSetStatus, StoredTemplate, TemplateParameter, ParameterType)
     ),
+    modifiers(&Authentication),
     tags(
         (name = "Ultima BI", description = "Ultimate Business Intellegence endpoints.")
     ),
 )]
-pub(crate) struct ApiDoc;
+pub struct ApiDoc;
+
+/// Documents how a DataFrame is (de)serialised to JSON
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct DataFrame {
+    columns: Vec<Series>,
+}
+
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct Series {
+    name: String,
+    /// Polars DataType, eg String or Float64
+    datatype: String,
+    #[schema(value_type = Vec<Object>)]
+    values: Vec<()>,
+}
+
+/// Documents how a DataSet is serialised (see `impl Serialize for dyn DataSet`)
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct DataSetInfo {
+    /// Columns which can be used in groupby and filters
+    fields: Vec<String>,
+    /// Measure name to the aggregation it is restricted to, if any
+    measures: BTreeMap<String, Option<String>>,
+    measures_metadata: BTreeMap<String, MeasureMetadata>,
+    calc_params: Vec<CalcParameter>,
+    hierarchies: Vec<Hierarchy>,
+    /// Requested by name, like the measures
+    windows: Vec<WindowMeasure>,
+    /// Dates which can be requested as_of (or compared to), the last one being the default
+    snapshots: Vec<String>,
+}
+
+/// Every operation is authenticated (see [crate::auth]) unless it says otherwise
+struct Authentication;
+
+impl Modify for Authentication {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "basic",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Basic)),
+        );
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+        );
+
+        let unauthorized = ResponseBuilder::new()
+            .description("Missing or invalid credentials")
+            .content(
+                "text/plain",
+                ContentBuilder::new()
+                    .schema(ObjectBuilder::new().schema_type(SchemaType::String))
+                    .build(),
+            )
+            .build();
+
+        for item in openapi.paths.paths.values_mut() {
+            for op in item.operations.values_mut() {
+                // Operations which are not authenticated set `security(())`
+                if op.security.is_some() {
+                    continue;
+                }
+                op.security = Some(vec![
+                    SecurityRequirement::new::<_, _, &str>("basic", []),
+                    SecurityRequirement::new::<_, _, &str>("bearer", []),
+                ]);
+                op.responses
+                    .responses
+                    .entry("401".to_string())
+                    .or_insert_with(|| unauthorized.clone().into());
+            }
+        }
+    }
+}
@@ -9,38 +9,104 @@ use anyhow::Context;
 use serde::Deserialize;
 use tokio::task;
 use ultibi_core::{
-    aggregations::BASE_CALCS, errors::UltiResult, AggregationRequest, ComputeRequest, DataFrame,
-    DataSet,
+    aggregations::BASE_CALCS, errors::UltiResult, ComputeRequest, DataFrame, DataSet,
 };
 use utoipa::IntoParams;
 
+use crate::audit::AuditLog;
+use crate::auth::AuthenticatedUser;
+use crate::errors::ApiError;
+use crate::helpers::{
+    formatted_response, negotiate_format, with_server_timing, with_truncation, FormatParams,
+    TimingsParams,
+};
+use crate::jobs::JobRegistry;
+use crate::metrics::Metrics;
+use crate::reload::Reloader;
+
+use super::admin::{append, delete, reload, reload_status};
+use super::audit::{audit_entries, audit_entry, replay};
+use super::batch::execute_batch;
+use super::drill_through::drill_through;
+use super::jobs::{cancel_job, job_result, job_status, submit_job};
+use super::status::{live, ready, status};
+use super::templates::{
+    create_template, delete_template, execute_template, template, templates, update_template,
+};
+
 #[derive(Deserialize, IntoParams)]
 struct Pagination {
+    /// Starts at 0
     page: usize,
+    /// Values containing this substring
     pattern: String,
 }
 const PER_PAGE: u16 = 100;
 
+/// Not authenticated. If the data can be reloaded, the status of the last reload
+/// is in the `X-Load-Status` header (see [crate::reload::LoadStatus])
+#[utoipa::path(
+    context_path = "/api",
+    get,
+    responses(
+        (status = 200, description = "The server is up. Use /ready to check if the data is loaded")
+    ),
+    security(())
+)]
 #[get("/health_check")]
-async fn health_check(_: HttpRequest) -> impl Responder {
+async fn health_check(_: HttpRequest, reloader: Option<Data<Reloader>>) -> impl Responder {
+    let mut res = HttpResponse::Ok();
+    if let Some(r) = reloader {
+        res.insert_header(("X-Load-Status", r.status().state()));
+    }
+    res
+}
+
+/// Prometheus scrape endpoint. Not authenticated, see [crate::metrics]
+#[utoipa::path(
+    context_path = "/api",
+    get,
+    responses(
+        (status = 200, description = "Metrics in Prometheus text format", body = String, content_type = "text/plain")
+    ),
+    security(())
+)]
+#[get("/metrics")]
+pub(crate) async fn prometheus_metrics(
+    metrics: Data<Metrics>,
+    jobs: Data<JobRegistry>,
+) -> impl Responder {
     HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics.render(jobs.in_flight()))
 }
 
-#[utoipa::path]
-// /{column_name}?page=2&per_page=30
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    params(("column_name" = String, Path, description = "Column to search in"), Pagination),
+    responses(
+        (status = 200, description = "Unique values of the column containing the pattern. 100 per page", body = [String]),
+        (status = 400, description = "No such column", body = ApiError),
+        (status = 500, description = "Search failed", body = ApiError)
+    )
+)]
+// /{column_name}?page=2&pattern=NY
 #[get("/columns/{column_name}")]
 async fn column_search(
+    user: AuthenticatedUser,
     path: web::Path<String>,
     page: web::Query<Pagination>,
     data: Data<RwLock<dyn DataSet>>,
 ) -> Result<HttpResponse> {
     let column_name = path.into_inner();
     let (page, pat) = (page.page, page.pattern.clone());
+    let filters = user.entitlement.filters;
     let res = task::spawn_blocking(move || {
         let srs = data
             .read()
             .expect("Poisonned RwLock")
-            .get_column(&column_name)?;
+            .get_column_filtered(&column_name, &filters)?;
 
         let search = ultibi_core::helpers::searches::filter_contains_unique(&srs, &pat)?;
         let first = page * PER_PAGE as usize;
@@ -59,13 +125,19 @@ async fn column_search(
         ))),
         Err(e) => {
             tracing::error!("Failed to execute query: {:?}", e);
-            Err(actix_web::error::ErrorExpectationFailed(e))
+            Err(ApiError::from(e).into())
         }
     }
 }
-#[utoipa::path(get)]
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    responses(
+        (status = 200, description = "What can be calculated", body = DataSetInfo)
+    )
+)]
 #[get("")]
-async fn dataset_info(_: HttpRequest, ds: Data<RwLock<dyn DataSet>>) -> impl Responder {
+async fn dataset_info(_: AuthenticatedUser, ds: Data<RwLock<dyn DataSet>>) -> impl Responder {
     let a = ds.read().unwrap();
     let body = serde_json::to_string(&*a).unwrap();
     //web::Json(&*a)
@@ -75,71 +147,101 @@ async fn dataset_info(_: HttpRequest, ds: Data<RwLock<dyn DataSet>>) -> impl Res
 }
 
 #[utoipa::path(
+    context_path = "/api/FRTB",
     post,
-    request_body(content = AggregationRequest, description = "What do you want to calculate", content_type = "application/json",
-        example = json!(r#"
-        {   "filters": [{"op":"Eq", "field":"Group", "value":"Ultima"}],
-    
+    params(FormatParams, TimingsParams),
+    request_body(content = ComputeRequest, description = "What do you want to calculate", content_type = "application/json",
+        example = json!({
+            "filters": [[{"op": "Eq", "field": "Group", "value": "Ultima"}]],
             "groupby": ["RiskClass", "Desk"],
-            
-            "overrides": [{   "field": "SensWeights",
-                              "value": "[0.005]",
-                              "filters": [
-                                        [{"op":"Eq", "field":"RiskClass", "value":"DRC_nonSec"}],
-                                        [{"op":"Eq", "field":"CreditQuality", "value":"AA"}]
-                                        ]
-                        }],
-            
-            "measures": [
-                ["DRC nonSec CapitalCharge", "scalar"]
-                    ],
-            "type": "AggregationRequest",
-            
+            "overrides": [{
+                "field": "SensWeights",
+                "value": "[0.005]",
+                "filters": [
+                    [{"op": "Eq", "field": "RiskClass", "value": "DRC_nonSec"}],
+                    [{"op": "Eq", "field": "CreditQuality", "value": "AA"}]
+                ]
+            }],
+            "measures": [["DRC nonSec CapitalCharge", "scalar"]],
             "hide_zeros": true,
             "calc_params": {
                 "jurisdiction": "BCBS",
                 "apply_fx_curv_div": "true",
                 "drc_offset": "false"
-            }}
-    "#)
+            }
+        })
     ),
     responses(
-        (status = 200, description = "Result of the compute request",body = DataFrame,
-         content_type = "application/json", 
-         example=json!(
-            r#"{"columns":[{"name":"RiskCategory","datatype":"Utf8","values":["DRC","Vega","Delta"]},{"name":"COB","datatype":"Utf8","values":["22/07/2022","22/07/2022","22/07/2022"]},{"name":"SA Charge","datatype":"Float64","values":[12777.688636772913,417064.5099482173,169292.7255377446]}]}"#
-        ))
+        (status = 200, description = "Result of the compute request. Header x-result-truncated is set if a grouping set had more rows than the limit", body = DataFrame,
+         content_type = ["application/json", "text/csv", "application/vnd.apache.parquet", "application/vnd.apache.arrow.stream"],
+         example = json!({"columns": [
+            {"name": "RiskCategory", "datatype": "String", "values": ["DRC", "Vega", "Delta"]},
+            {"name": "COB", "datatype": "String", "values": ["22/07/2022", "22/07/2022", "22/07/2022"]},
+            {"name": "SA Charge", "datatype": "Float64", "values": [12777.688636772913, 417064.5099482173, 169292.7255377446]}
+         ]})
+        ),
+        (status = 403, description = "Request is outside of the user's entitlements", body = String, content_type = "text/plain"),
+        (status = 400, description = "Invalid request (eg unknown measure or invalid override) or unsupported format", body = ApiError),
+        (status = 500, description = "Computation failed", body = ApiError),
+        (status = 504, description = "Computation timed out", body = ApiError)
     )
 )]
-#[tracing::instrument(name = "Request Execution", skip(data))]
+#[tracing::instrument(
+    name = "Request Execution",
+    skip(user, data, jobs, audit, metrics, http_req)
+)]
 #[post("")]
 pub(crate) async fn execute(
+    user: AuthenticatedUser,
+    http_req: HttpRequest,
     data: Data<RwLock<dyn DataSet>>,
+    jobs: Data<JobRegistry>,
+    audit: Data<AuditLog>,
+    metrics: Data<Metrics>,
+    params: web::Query<FormatParams>,
+    timings: web::Query<TimingsParams>,
     req: web::Json<ComputeRequest>,
 ) -> Result<HttpResponse> {
-    let r = req.into_inner();
-    // TODO kill this OS thread if it is hanging (see spawn_blocking docs for ideas)
-    let res = task::spawn_blocking(move || data.read().expect("Poisonned RwLock").compute(r))
-        .await
-        .context("Failed to spawn blocking task.")
-        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let format = negotiate_format(&http_req, &params)?;
+    let r = user.entitlement.restrict(req.into_inner())?;
+    // Long running requests should rather be submitted as jobs, see [super::jobs]
+    // Here we only stop the computation (at its next checkpoint) if it exceeds the default timeout
+    let ctx = jobs.context(None);
+    let compute_ctx = ctx.clone();
+    let res = task::spawn_blocking(move || {
+        let ds = data.read().expect("Poisonned RwLock");
+        let res = audit.compute(&*ds, r, &compute_ctx, &user.name, "execute");
+        metrics.observe_compute(&compute_ctx, &res);
+        ultibi_io::write(&mut res?, format)
+    })
+    .await
+    .context("Failed to spawn blocking task.")
+    .map_err(actix_web::error::ErrorInternalServerError)?;
 
     match res {
-        Ok(df) => Ok(HttpResponse::Ok().json(df)),
+        Ok(body) if timings.timings => Ok(with_server_timing(
+            with_truncation(formatted_response(body, format), &ctx),
+            &ctx,
+        )),
+        Ok(body) => Ok(with_truncation(formatted_response(body, format), &ctx)),
         Err(e) => {
             tracing::error!("Failed to execute query: {:?}", e);
-            Err(actix_web::error::ErrorExpectationFailed(e))
+            Err(ApiError::from(e).into())
         }
     }
 }
-#[utoipa::path]
-#[get("/templates")]
-async fn templates(_: HttpRequest, templates: Data<Vec<AggregationRequest>>) -> impl Responder {
-    web::Json(templates)
-}
-#[utoipa::path(get)]
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    responses(
+        (status = 200, description = "Columns which can be overridden", body = [String])
+    )
+)]
 #[get("/overrides")]
-async fn overridable_columns(data: Data<RwLock<dyn DataSet>>) -> impl Responder {
+async fn overridable_columns(
+    _: AuthenticatedUser,
+    data: Data<RwLock<dyn DataSet>>,
+) -> impl Responder {
     web::Json(data.read().expect("Poisonned RwLock").overridable_columns())
 }
 
@@ -150,43 +252,91 @@ pub fn configure() -> impl FnOnce(&mut ServiceConfig) {
                 .service(aggtypes)
                 .service(describe)
                 .service(health_check)
+                .service(live)
+                .service(ready)
+                .service(status)
+                .service(prometheus_metrics)
+                .service(reload_status)
+                .service(reload)
+                .service(append)
+                .service(delete)
                 //TODO change FRTB to DataSet
                 .service(
                     web::scope("/FRTB")
                         .service(dataset_info)
                         .service(execute)
+                        .service(execute_batch)
+                        .service(drill_through)
                         .service(column_search)
                         .service(templates)
-                        .service(overridable_columns),
+                        .service(template)
+                        .service(create_template)
+                        .service(update_template)
+                        .service(delete_template)
+                        .service(execute_template)
+                        .service(overridable_columns)
+                        .service(submit_job)
+                        .service(job_status)
+                        .service(job_result)
+                        .service(cancel_job)
+                        .service(audit_entries)
+                        .service(audit_entry)
+                        .service(replay),
                 ),
         );
     }
 }
 
 // Not Ultibi DataSet Specific
-#[utoipa::path]
+#[utoipa::path(
+    context_path = "/api",
+    get,
+    responses(
+        (status = 200, description = "Aggregation types a measure can be aggregated with", body = [String])
+    )
+)]
 #[get("/aggtypes")]
-async fn aggtypes() -> impl Responder {
+async fn aggtypes(_: AuthenticatedUser) -> impl Responder {
     let res = BASE_CALCS.iter().map(|(x, _)| *x).collect::<Vec<&str>>();
     web::Json(res)
 }
 
-#[utoipa::path]
-#[tracing::instrument(name = "Describe", skip(jdf))]
+#[utoipa::path(
+    context_path = "/api",
+    post,
+    params(FormatParams),
+    request_body(content = DataFrame, description = "Frame to describe", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Statistics of the numeric columns of the frame", body = DataFrame,
+         content_type = ["application/json", "text/csv", "application/vnd.apache.parquet", "application/vnd.apache.arrow.stream"]),
+        (status = 400, description = "Unsupported format", body = ApiError),
+        (status = 500, description = "Failed to describe the frame", body = ApiError)
+    )
+)]
+#[tracing::instrument(name = "Describe", skip(jdf, http_req))]
 #[post("/describe")]
-async fn describe(jdf: web::Json<DataFrame>) -> Result<HttpResponse> {
+async fn describe(
+    _: AuthenticatedUser,
+    http_req: HttpRequest,
+    params: web::Query<FormatParams>,
+    jdf: web::Json<DataFrame>,
+) -> Result<HttpResponse> {
+    let format = negotiate_format(&http_req, &params)?;
     let df = jdf.into_inner();
     // TODO kill this OS thread if it is hanging (see spawn_blocking docs for ideas)
-    let res = task::spawn_blocking(move || crate::helpers::describe(df, None))
-        .await
-        .context("Failed to spawn blocking task.")
-        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let res = task::spawn_blocking(move || {
+        let mut df = crate::helpers::describe(df, None)?;
+        ultibi_io::write(&mut df, format)
+    })
+    .await
+    .context("Failed to spawn blocking task.")
+    .map_err(actix_web::error::ErrorInternalServerError)?;
 
     match res {
-        Ok(df) => Ok(HttpResponse::Ok().json(df)),
+        Ok(body) => Ok(formatted_response(body, format)),
         Err(e) => {
             tracing::error!("Failed to execute query: {:?}", e);
-            Err(actix_web::error::ErrorExpectationFailed(e))
+            Err(ApiError::from(e).into())
         }
     }
 }
@@ -0,0 +1,171 @@
+//! Probes for load balancers/orchestrators and the status of the DataSet
+//!
+//! `/live` - the process is up and serving HTTP. `/ready` - data has been validated and
+//! prepared, ie requests can be routed here. Neither is authenticated and both have an empty body.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+use actix_web::{get, web::Data, HttpResponse, Responder, Result};
+use anyhow::Context;
+use serde::Serialize;
+use tokio::task;
+use ultibi_core::{
+    errors::UltiResult,
+    polars::prelude::{col, len, DataType},
+    DataSet,
+};
+use utoipa::ToSchema;
+
+use crate::audit::AuditLog;
+use crate::auth::AuthenticatedUser;
+use crate::reload::{LoadStatus, Reloader};
+
+/// Column which holds the Close Of Business date, if any
+const COB: &str = "COB";
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DataSetStatus {
+    pub ready: bool,
+    /// Status of the last reload. None if reload is not set up
+    pub load: Option<LoadStatus>,
+    /// Seconds since UNIX epoch. None if unknown
+    pub loaded_at: Option<u64>,
+    pub data_version: String,
+    /// Distinct values of the COB column, if the data has one
+    pub cob: Vec<String>,
+    pub rows: usize,
+    /// Column name to DataType
+    pub schema: BTreeMap<String, String>,
+    /// Number of cached results. None if the DataSet has no cache
+    pub cache_size: Option<usize>,
+    pub build_params: BTreeMap<String, String>,
+}
+
+/// Without a [Reloader] the data is loaded before the server starts
+fn is_ready(reloader: Option<&Reloader>) -> bool {
+    reloader.map_or(true, |r| r.is_ready())
+}
+
+#[utoipa::path(
+    context_path = "/api",
+    get,
+    responses(
+        (status = 200, description = "The server is up")
+    ),
+    security(())
+)]
+#[get("/live")]
+pub(crate) async fn live() -> impl Responder {
+    HttpResponse::Ok()
+}
+
+#[utoipa::path(
+    context_path = "/api",
+    get,
+    responses(
+        (status = 200, description = "Data is loaded, validated and prepared"),
+        (status = 503, description = "Data is not loaded yet or the initial load failed")
+    ),
+    security(())
+)]
+#[get("/ready")]
+pub(crate) async fn ready(reloader: Option<Data<Reloader>>) -> impl Responder {
+    let mut res = match is_ready(reloader.as_deref().map(Arc::as_ref)) {
+        true => HttpResponse::Ok(),
+        false => HttpResponse::ServiceUnavailable(),
+    };
+    if let Some(r) = reloader {
+        res.insert_header(("X-Load-Status", r.status().state()));
+    }
+    res
+}
+
+#[utoipa::path(
+    context_path = "/api",
+    get,
+    responses(
+        (status = 200, description = "Status of the DataSet", body = DataSetStatus)
+    )
+)]
+#[get("/status")]
+pub(crate) async fn status(
+    _: AuthenticatedUser,
+    data: Data<RwLock<dyn DataSet>>,
+    audit: Data<AuditLog>,
+    reloader: Option<Data<Reloader>>,
+) -> Result<HttpResponse> {
+    // Counting rows might scan the source
+    let res = task::spawn_blocking(move || {
+        let ds = data.read().expect("Poisonned RwLock");
+        dataset_status(
+            &*ds,
+            reloader.as_deref().map(Arc::as_ref),
+            audit.data_version(),
+        )
+    })
+    .await
+    .context("Failed to spawn blocking task.")
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    match res {
+        Ok(body) => Ok(HttpResponse::Ok().json(body)),
+        Err(e) => {
+            tracing::error!("Failed to get DataSet status: {:?}", e);
+            Err(actix_web::error::ErrorInternalServerError(e))
+        }
+    }
+}
+
+fn dataset_status(
+    ds: &dyn DataSet,
+    reloader: Option<&Reloader>,
+    data_version: String,
+) -> UltiResult<DataSetStatus> {
+    let schema = ds.get_schema()?;
+    let lf = ds.get_lazyframe(&vec![])?;
+
+    let rows = lf
+        .clone()
+        .select([len().cast(DataType::UInt64)])
+        .collect()?
+        .get_columns()[0]
+        .u64()?
+        .get(0)
+        .unwrap_or_default() as usize;
+
+    let cob = match schema.get(COB) {
+        Some(_) => {
+            let cobs = lf
+                .select([col(COB)
+                    .unique()
+                    .sort(Default::default())
+                    .cast(DataType::String)])
+                .collect()?;
+            cobs.column(COB)?
+                .str()?
+                .into_iter()
+                .flatten()
+                .map(str::to_string)
+                .collect()
+        }
+        None => vec![],
+    };
+
+    Ok(DataSetStatus {
+        ready: is_ready(reloader),
+        load: reloader.map(|r| r.status()),
+        loaded_at: reloader.and_then(|r| r.loaded_at()),
+        data_version,
+        cob,
+        rows,
+        schema: schema
+            .iter()
+            .map(|(name, dtype)| (name.to_string(), dtype.to_string()))
+            .collect(),
+        cache_size: ds.as_cacheable().map(|c| c.get_cache().len()),
+        build_params: ds.build_params().clone(),
+    })
+}
@@ -0,0 +1,190 @@
+//! Save, rename, share and delete requests (see [crate::templates])
+
+use std::{collections::BTreeMap, sync::RwLock};
+
+use actix_web::{
+    delete, get, post, put,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Responder, Result,
+};
+use serde::Deserialize;
+use tokio::task;
+use ultibi_core::{AggregationRequest, DataSet};
+use utoipa::IntoParams;
+
+use crate::{
+    audit::AuditLog,
+    auth::AuthenticatedUser,
+    errors::ApiError,
+    helpers::{formatted_response, negotiate_format, with_truncation, FormatParams},
+    jobs::JobRegistry,
+    metrics::Metrics,
+    templates::TemplateStore,
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct ShareParams {
+    /// Make the template visible to everyone. On update, keeps the current value if omitted
+    shared: Option<bool>,
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    responses(
+        (status = 200, description = "Templates visible to the user, with their owner", body = [StoredTemplate])
+    )
+)]
+#[get("/templates")]
+pub(crate) async fn templates(
+    user: AuthenticatedUser,
+    store: Data<TemplateStore>,
+) -> impl Responder {
+    web::Json(store.visible(&user))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    get,
+    params(("name" = String, Path, description = "Template name")),
+    responses(
+        (status = 200, description = "Template with its owner", body = StoredTemplate),
+        (status = 404, description = "No such template")
+    )
+)]
+#[get("/templates/{name}")]
+pub(crate) async fn template(
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+    store: Data<TemplateStore>,
+) -> Result<HttpResponse> {
+    let template = store.get(&path.into_inner(), &user)?;
+    Ok(HttpResponse::Ok().json(template))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    post,
+    params(ShareParams),
+    request_body(content = AggregationRequest, description = "Request to save. Its name must not be taken by your templates or a shared one", content_type = "application/json"),
+    responses(
+        (status = 201, description = "Template was saved", body = StoredTemplate),
+        (status = 400, description = "Request has no name"),
+        (status = 409, description = "Template with this name already exists")
+    )
+)]
+#[post("/templates")]
+pub(crate) async fn create_template(
+    user: AuthenticatedUser,
+    store: Data<TemplateStore>,
+    params: web::Query<ShareParams>,
+    req: web::Json<AggregationRequest>,
+) -> Result<HttpResponse> {
+    let template = store.create(req.into_inner(), params.shared.unwrap_or(false), &user)?;
+    Ok(HttpResponse::Created().json(template))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    put,
+    params(("name" = String, Path, description = "Template name"), ShareParams),
+    request_body(content = AggregationRequest, description = "New request. A different name renames the template", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Template was updated", body = StoredTemplate),
+        (status = 403, description = "Template belongs to someone else"),
+        (status = 404, description = "No such template"),
+        (status = 409, description = "Template with the new name already exists")
+    )
+)]
+#[put("/templates/{name}")]
+pub(crate) async fn update_template(
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+    store: Data<TemplateStore>,
+    params: web::Query<ShareParams>,
+    req: web::Json<AggregationRequest>,
+) -> Result<HttpResponse> {
+    let template = store.update(&path.into_inner(), req.into_inner(), params.shared, &user)?;
+    Ok(HttpResponse::Ok().json(template))
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    delete,
+    params(("name" = String, Path, description = "Template name")),
+    responses(
+        (status = 204, description = "Template was deleted"),
+        (status = 403, description = "Template belongs to someone else"),
+        (status = 404, description = "No such template")
+    )
+)]
+#[delete("/templates/{name}")]
+pub(crate) async fn delete_template(
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+    store: Data<TemplateStore>,
+) -> Result<HttpResponse> {
+    store.delete(&path.into_inner(), &user)?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    context_path = "/api/FRTB",
+    post,
+    params(("name" = String, Path, description = "Template name"), FormatParams),
+    request_body(content = BTreeMap<String, String>, description = "Values of the parameters of the template. Defaults apply to the omitted ones", content_type = "application/json",
+        example = json!({"cob": "22/07/2022", "desk": "FXOptions"})),
+    responses(
+        (status = 200, description = "Result of the template with the parameters substituted", body = DataFrame),
+        (status = 404, description = "No such template"),
+        (status = 400, description = "Invalid parameters or request", body = ApiError),
+        (status = 500, description = "Computation failed", body = ApiError),
+        (status = 504, description = "Computation timed out", body = ApiError)
+    )
+)]
+#[tracing::instrument(
+    name = "Template Execution",
+    skip(user, http_req, store, data, jobs, audit, metrics)
+)]
+#[post("/templates/{name}/execute")]
+pub(crate) async fn execute_template(
+    user: AuthenticatedUser,
+    http_req: HttpRequest,
+    path: web::Path<String>,
+    store: Data<TemplateStore>,
+    data: Data<RwLock<dyn DataSet>>,
+    jobs: Data<JobRegistry>,
+    audit: Data<AuditLog>,
+    metrics: Data<Metrics>,
+    params: web::Query<FormatParams>,
+    values: web::Json<BTreeMap<String, String>>,
+) -> Result<HttpResponse> {
+    let format = negotiate_format(&http_req, &params)?;
+    let name = path.into_inner();
+    let template = store.get(&name, &user)?;
+    let request = template
+        .request
+        .with_parameters(&values)
+        .map_err(ApiError::from)?;
+    let r = user.entitlement.restrict(request.into())?;
+    let ctx = jobs.context(None);
+
+    let compute_ctx = ctx.clone();
+    let res = task::spawn_blocking(move || {
+        let source = format!("template {name}");
+        let ds = data.read().expect("Poisonned RwLock");
+        let res = audit.compute(&*ds, r, &compute_ctx, &user.name, &source);
+        metrics.observe_compute(&compute_ctx, &res);
+        ultibi_io::write(&mut res?, format)
+    })
+    .await
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    match res {
+        Ok(body) => Ok(with_truncation(formatted_response(body, format), &ctx)),
+        Err(e) => {
+            tracing::error!("Failed to execute template: {:?}", e);
+            Err(ApiError::from(e).into())
+        }
+    }
+}
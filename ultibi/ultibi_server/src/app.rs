@@ -14,29 +14,51 @@ use actix_web_static_files::ResourceFiles;
 include!(concat!(env!("OUT_DIR"), "/generated.rs"));
 
 use crate::api::{open_api::ApiDoc, routers};
+use crate::audit::AuditLog;
+use crate::auth::Security;
+use crate::jobs::JobRegistry;
+use crate::metrics::{Metrics, RequestMetrics};
+use crate::templates::TemplateStore;
 pub fn build_app(
     listener: TcpListener,
     ds: Arc<RwLock<dyn DataSet>>,
-    _templates: Vec<AggregationRequest>,
+    templates: Vec<AggregationRequest>,
+    audit: Arc<AuditLog>,
+    metrics: Arc<Metrics>,
 ) -> std::io::Result<Server> {
     let ds = Data::from(ds);
     //let streaming = Data::new(streaming);
     let openapi = ApiDoc::openapi();
 
-    let _templates = Data::new(_templates);
+    let templates = Data::new(
+        TemplateStore::from_env(templates)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?,
+    );
+    let jobs = Data::new(JobRegistry::from_env());
+    let audit = Data::from(audit);
+    let metrics = Data::from(metrics);
+    let security = Data::new(
+        Security::from_env()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?,
+    );
 
     let server = HttpServer::new(move || {
         let generated = generate();
 
         App::new()
             .wrap(Logger::default())
+            .wrap(RequestMetrics)
             .configure(routers::configure())
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-doc/openapi.json", openapi.clone()),
             )
             .service(ResourceFiles::new("/", generated))
             .app_data(ds.clone())
-            .app_data(_templates.clone())
+            .app_data(templates.clone())
+            .app_data(jobs.clone())
+            .app_data(security.clone())
+            .app_data(audit.clone())
+            .app_data(metrics.clone())
         //.app_data(streaming.clone())
     })
     .listen(listener)?
@@ -0,0 +1,463 @@
+//! Audit log of compute requests
+//!
+//! Every [ComputeRequest] (whether executed directly, as a job, via Arrow Flight or replayed),
+//! every drill through to the underlying rows and every change of the data (append or delete)
+//! is appended as a JSON line to `$AUDIT_LOG`, together with the user, the time, the version
+//! of the data it ran against, how long it took and its outcome. Without `$AUDIT_LOG` the log
+//! is kept in memory only.
+//! The file is never rewritten, only appended to. Where each entry is in the file is kept
+//! in memory, so that listing and getting entries reads only those entries.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Cursor, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, RwLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use ultibi_core::{
+    errors::{UltiResult, UltimaErr},
+    filters::AndOrFltrChain,
+    ComputeRequest, DataFrame, DataSet, DrillThroughPage, DrillThroughRequest, ExecutionContext,
+};
+use utoipa::ToSchema;
+
+pub type AuditId = u64;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    pub id: AuditId,
+    pub user: String,
+    /// Milliseconds since UNIX epoch
+    pub timestamp: u64,
+    /// execute, batch, job, flight, template, replay, drillthrough, append or delete
+    pub source: String,
+    /// None if the entry is a change of the data, see [AuditDetail]
+    #[serde(default)]
+    pub request: Option<ComputeRequest>,
+    pub data_version: String,
+    pub duration_ms: u64,
+    /// (rows, columns) of the result, if succeeded
+    pub shape: Option<(usize, usize)>,
+    pub error: Option<String>,
+    /// What else the entry is about, eg the cell of a drill through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<AuditDetail>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditDetail {
+    /// A page of the rows behind a cell of the result of the request. See [DrillThroughRequest]
+    DrillThrough {
+        cell: BTreeMap<String, Option<String>>,
+        measure: Option<String>,
+        columns: Vec<String>,
+        offset: usize,
+        limit: usize,
+    },
+    /// Rows appended to the data. See [DataSet::append_frame]
+    Append {
+        /// Number of rows appended, if succeeded
+        rows: Option<usize>,
+    },
+    /// Rows deleted from the data. See [DataSet::delete_where]
+    Delete {
+        #[schema(value_type = Vec<Vec<ultibi_core::filters::FilterE>>)]
+        filters: AndOrFltrChain,
+        /// Number of rows deleted, if succeeded
+        rows: Option<usize>,
+    },
+}
+
+/// Where an entry is in the file
+struct Location {
+    id: AuditId,
+    user: String,
+    offset: u64,
+    len: usize,
+}
+
+/// Locations of the entries, in the order of the file
+#[derive(Default)]
+struct Index {
+    entries: Vec<Location>,
+    /// Positions in `entries`
+    by_id: HashMap<AuditId, usize>,
+    by_user: HashMap<String, Vec<usize>>,
+}
+
+impl Index {
+    fn push(&mut self, location: Location) {
+        let pos = self.entries.len();
+        self.by_id.insert(location.id, pos);
+        self.by_user
+            .entry(location.user.clone())
+            .or_default()
+            .push(pos);
+        self.entries.push(location);
+    }
+}
+
+pub struct AuditLog {
+    /// If None, entries are kept in `memory`
+    path: Option<PathBuf>,
+    next_id: AtomicU64,
+    /// Opened on the first write. Held while appending
+    file: Mutex<Option<File>>,
+    /// Lines of a log without a file
+    memory: RwLock<Vec<u8>>,
+    /// Updated once an entry is written
+    index: RwLock<Index>,
+    data_version: RwLock<String>,
+}
+
+impl AuditLog {
+    /// Continues numbering of the entries already in `path`
+    pub fn new(path: impl Into<PathBuf>, data_version: String) -> Self {
+        Self::with_path(Some(path.into()), data_version)
+    }
+
+    /// Entries are lost on restart
+    pub fn in_memory(data_version: String) -> Self {
+        Self::with_path(None, data_version)
+    }
+
+    fn with_path(path: Option<PathBuf>, data_version: String) -> Self {
+        let index = path.as_deref().map(index_entries).unwrap_or_default();
+        let next_id = index.entries.iter().map(|e| e.id + 1).max().unwrap_or(1);
+        Self {
+            path,
+            next_id: AtomicU64::new(next_id),
+            file: Mutex::new(None),
+            memory: RwLock::new(vec![]),
+            index: RwLock::new(index),
+            data_version: RwLock::new(data_version),
+        }
+    }
+
+    /// Reads `AUDIT_LOG` and `DATA_VERSION`
+    pub fn from_env() -> Self {
+        Self::with_path(
+            env::var("AUDIT_LOG").ok().map(PathBuf::from),
+            env::var("DATA_VERSION").unwrap_or_else(|_| "initial".to_string()),
+        )
+    }
+
+    pub fn data_version(&self) -> String {
+        self.data_version.read().expect("Poisonned RwLock").clone()
+    }
+
+    /// Call whenever the data of the DataSet changes
+    pub fn set_data_version(&self, version: String) {
+        *self.data_version.write().expect("Poisonned RwLock") = version;
+    }
+
+    /// Computes `r` and records it
+    pub fn compute(
+        &self,
+        ds: &dyn DataSet,
+        r: ComputeRequest,
+        ctx: &ExecutionContext,
+        user: &str,
+        source: &str,
+    ) -> UltiResult<DataFrame> {
+        let started = Instant::now();
+        let res = ds.compute_with_context(r.clone(), ctx);
+        self.record(user, source, r, started.elapsed(), &res);
+        res
+    }
+
+    /// Computes `rs` together (see [DataSet::compute_many]) and records each of them.
+    /// All the entries get the duration of the whole batch
+    pub fn compute_many(
+        &self,
+        ds: &dyn DataSet,
+        rs: Vec<ComputeRequest>,
+        ctx: &ExecutionContext,
+        user: &str,
+        source: &str,
+    ) -> Vec<UltiResult<DataFrame>> {
+        let started = Instant::now();
+        let res = ds.compute_many(rs.clone(), ctx);
+        let duration = started.elapsed();
+        for (r, result) in rs.into_iter().zip(&res) {
+            self.record(user, source, r, duration, result);
+        }
+        res
+    }
+
+    /// Drills through (see [DataSet::drill_through]) and records it, with the page of rows as the result
+    pub fn drill_through(
+        &self,
+        ds: &dyn DataSet,
+        r: DrillThroughRequest,
+        ctx: &ExecutionContext,
+        user: &str,
+    ) -> UltiResult<DrillThroughPage> {
+        let started = Instant::now();
+        let res = ds.drill_through(r.clone(), ctx);
+        let DrillThroughRequest {
+            request,
+            cell,
+            measure,
+            columns,
+            offset,
+            limit,
+        } = r;
+        let detail = AuditDetail::DrillThrough {
+            cell,
+            measure,
+            columns,
+            offset,
+            limit,
+        };
+        self.append_entry(
+            user,
+            "drillthrough",
+            Some(ComputeRequest::Aggregation(request)),
+            started.elapsed(),
+            res.as_ref().map(|page| Some(page.rows.shape())),
+            Some(detail),
+        );
+        res
+    }
+
+    /// Appends `df` to the data (see [DataSet::append_frame]) and records it
+    pub fn append_frame(
+        &self,
+        ds: &mut dyn DataSet,
+        df: DataFrame,
+        user: &str,
+    ) -> UltiResult<usize> {
+        let started = Instant::now();
+        let res = ds.append_frame(df);
+        let detail = AuditDetail::Append {
+            rows: res.as_ref().ok().copied(),
+        };
+        self.append_entry(
+            user,
+            "append",
+            None,
+            started.elapsed(),
+            res.as_ref().map(|_| None),
+            Some(detail),
+        );
+        res
+    }
+
+    /// Deletes the rows which satisfy `filters` (see [DataSet::delete_where]) and records it
+    pub fn delete_where(
+        &self,
+        ds: &mut dyn DataSet,
+        filters: AndOrFltrChain,
+        user: &str,
+    ) -> UltiResult<usize> {
+        let started = Instant::now();
+        let res = ds.delete_where(&filters);
+        let detail = AuditDetail::Delete {
+            filters,
+            rows: res.as_ref().ok().copied(),
+        };
+        self.append_entry(
+            user,
+            "delete",
+            None,
+            started.elapsed(),
+            res.as_ref().map(|_| None),
+            Some(detail),
+        );
+        res
+    }
+
+    /// Appends an entry. Failure to write is logged but does not fail the request
+    pub fn record(
+        &self,
+        user: &str,
+        source: &str,
+        request: ComputeRequest,
+        duration: Duration,
+        result: &UltiResult<DataFrame>,
+    ) -> AuditId {
+        let shape = result.as_ref().map(|df| Some(df.shape()));
+        self.append_entry(user, source, Some(request), duration, shape, None)
+    }
+
+    fn append_entry(
+        &self,
+        user: &str,
+        source: &str,
+        request: Option<ComputeRequest>,
+        duration: Duration,
+        shape: Result<Option<(usize, usize)>, &UltimaErr>,
+        detail: Option<AuditDetail>,
+    ) -> AuditId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let entry = AuditEntry {
+            id,
+            user: user.to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default(),
+            source: source.to_string(),
+            request,
+            data_version: self.data_version(),
+            duration_ms: duration.as_millis() as u64,
+            shape: shape.as_ref().ok().copied().flatten(),
+            error: shape.err().map(|e| e.to_string()),
+            detail,
+        };
+
+        if let Err(e) = self.append(&entry) {
+            tracing::error!("Failed to write audit entry {id}: {e}");
+        }
+        id
+    }
+
+    fn append(&self, entry: &AuditEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock().expect("Poisonned Mutex");
+        let offset = match &self.path {
+            None => {
+                let mut memory = self.memory.write().expect("Poisonned RwLock");
+                memory.extend_from_slice(&line);
+                (memory.len() - line.len()) as u64
+            }
+            Some(path) => {
+                if file.is_none() {
+                    *file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+                }
+                let f = file.as_mut().expect("Just opened");
+                // Appended at the end, whatever the position of the handle
+                let offset = f.metadata()?.len();
+                f.write_all(&line)?;
+                f.flush()?;
+                offset
+            }
+        };
+
+        self.index
+            .write()
+            .expect("Poisonned RwLock")
+            .push(Location {
+                id: entry.id,
+                user: entry.user.clone(),
+                offset,
+                len: line.len(),
+            });
+        Ok(())
+    }
+
+    /// Most recent first. If `user` is provided, only their entries
+    pub fn list(&self, user: Option<&str>, offset: usize, limit: usize) -> Vec<AuditEntry> {
+        let index = self.index.read().expect("Poisonned RwLock");
+        let positions: Vec<usize> = match user {
+            Some(u) => index
+                .by_user
+                .get(u)
+                .map(|p| p.iter().rev().skip(offset).take(limit).copied().collect())
+                .unwrap_or_default(),
+            None => (0..index.entries.len())
+                .rev()
+                .skip(offset)
+                .take(limit)
+                .collect(),
+        };
+        self.read(positions.into_iter().map(|p| &index.entries[p]))
+    }
+
+    pub fn get(&self, id: AuditId) -> Option<AuditEntry> {
+        let index = self.index.read().expect("Poisonned RwLock");
+        let pos = *index.by_id.get(&id)?;
+        self.read([&index.entries[pos]]).pop()
+    }
+
+    fn read<'a>(&self, locations: impl IntoIterator<Item = &'a Location>) -> Vec<AuditEntry> {
+        let mut locations = locations.into_iter().peekable();
+        if locations.peek().is_none() {
+            return vec![];
+        }
+        let Some(path) = &self.path else {
+            let memory = self.memory.read().expect("Poisonned RwLock");
+            return read_at(&mut Cursor::new(&memory[..]), locations);
+        };
+        match File::open(path) {
+            Ok(mut file) => read_at(&mut file, locations),
+            Err(e) => {
+                tracing::error!("Failed to open audit log: {e}");
+                vec![]
+            }
+        }
+    }
+}
+
+fn read_at<'a>(
+    log: &mut (impl Read + Seek),
+    locations: impl Iterator<Item = &'a Location>,
+) -> Vec<AuditEntry> {
+    let mut read = |location: &Location| -> std::io::Result<AuditEntry> {
+        let mut line = vec![0; location.len];
+        log.seek(SeekFrom::Start(location.offset))?;
+        log.read_exact(&mut line)?;
+        Ok(serde_json::from_slice(&line)?)
+    };
+    locations
+        .filter_map(|l| match read(l) {
+            Ok(e) => Some(e),
+            Err(e) => {
+                tracing::error!("Failed to read audit entry {}: {e}", l.id);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Only what the index needs of an entry
+#[derive(Deserialize)]
+struct Key {
+    id: AuditId,
+    user: String,
+}
+
+fn index_entries(path: &Path) -> Index {
+    let mut index = Index::default();
+    let Ok(file) = File::open(path) else {
+        return index;
+    };
+    let mut reader = BufReader::new(file);
+    let (mut offset, mut line) = (0, vec![]);
+    loop {
+        line.clear();
+        let len = match reader.read_until(b'\n', &mut line) {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(e) => {
+                tracing::warn!("Failed to read audit log: {e}");
+                break;
+            }
+        };
+        if !line.iter().all(u8::is_ascii_whitespace) {
+            match serde_json::from_slice::<Key>(&line) {
+                Ok(Key { id, user }) => index.push(Location {
+                    id,
+                    user,
+                    offset,
+                    len,
+                }),
+                Err(e) => tracing::warn!("Skipping malformed audit entry: {e}"),
+            }
+        }
+        offset += len as u64;
+    }
+    index
+}
@@ -0,0 +1,373 @@
+//! Authentication and per user entitlements
+//!
+//! Configured via env variables, see [Security::from_env]:
+//! * `AUTH_MODE=none` (default) - no authentication, everyone has full rights
+//! * `AUTH_MODE=token` - `Authorization: Bearer $AUTH_TOKEN`, the user being `$AUTH_TOKEN_USER`
+//! * `AUTH_MODE=basic` - basic auth against `$AUTH_BASIC_FILE`, one `user:password` per line.
+//!   Passwords are stored in plain text, so this is meant for tests and local set ups
+//! * `AUTH_MODE=jwt` - `Authorization: Bearer <jwt>`, verified with `$AUTH_JWT_SECRET` (HS256)
+//!   or with the PEM public key at `$AUTH_JWT_PUBLIC_KEY` (RS256). The user is the `sub` claim.
+//!   Set `$AUTH_JWT_AUDIENCE` to validate the `aud` claim
+//!
+//! `$ENTITLEMENTS_FILE` (JSON) maps users to [Entitlement]s:
+//! ```json
+//! {
+//!     "users": {
+//!         "alice": {"filters": [[{"op":"Eq", "field":"Desk", "value":"FXOptions"}]], "can_override": true}
+//!     },
+//!     "default": {"filters": [[{"op":"Eq", "field":"Desk", "value":"None"}]]}
+//! }
+//! ```
+//! Without an entitlements file every authenticated user has full rights.
+
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{Display, Formatter},
+    fs,
+    future::{ready, Ready},
+};
+
+use actix_web::{
+    dev::Payload,
+    http::{header, StatusCode},
+    web::Data,
+    FromRequest, HttpRequest, HttpResponse, ResponseError,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use ultibi_core::{filters::AndOrFltrChain, ComputeRequest};
+
+pub const ANONYMOUS: &str = "anonymous";
+
+#[derive(Debug)]
+pub enum AuthError {
+    /// Missing or invalid credentials. `challenge` goes into `WWW-Authenticate`
+    Unauthorized {
+        reason: String,
+        challenge: &'static str,
+    },
+    /// Authenticated, but not allowed to do this
+    Forbidden(String),
+    /// Server is misconfigured
+    Config(String),
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::Unauthorized { reason, .. } => write!(f, "Unauthorized: {reason}"),
+            AuthError::Forbidden(reason) => write!(f, "Forbidden: {reason}"),
+            AuthError::Config(reason) => write!(f, "Invalid authentication set up: {reason}"),
+        }
+    }
+}
+
+impl ResponseError for AuthError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AuthError::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            AuthError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AuthError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut res = HttpResponse::build(self.status_code());
+        if let AuthError::Unauthorized { challenge, .. } = self {
+            res.insert_header((header::WWW_AUTHENTICATE, *challenge));
+        }
+        res.body(self.to_string())
+    }
+}
+
+/// What a user is allowed to see and do
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Entitlement {
+    /// ANDed into the filters of every request and column search of the user
+    #[serde(default)]
+    pub filters: AndOrFltrChain,
+    #[serde(default)]
+    pub can_override: bool,
+    #[serde(default)]
+    pub can_add_rows: bool,
+    /// Can see and replay everyone's audit entries
+    #[serde(default)]
+    pub admin: bool,
+}
+
+impl Entitlement {
+    pub fn unrestricted() -> Self {
+        Self {
+            filters: vec![],
+            can_override: true,
+            can_add_rows: true,
+            admin: true,
+        }
+    }
+
+    /// Applies the entitlement to a request: restrictions are ANDed into its filters.
+    /// Fails if the request contains overrides or additional rows the user can't send
+    pub fn restrict(&self, req: ComputeRequest) -> Result<ComputeRequest, AuthError> {
+        match req {
+            ComputeRequest::Aggregation(mut ar) => {
+                if !self.can_override && !ar.overrides.is_empty() {
+                    return Err(AuthError::Forbidden(
+                        "You are not allowed to send overrides".to_string(),
+                    ));
+                }
+                if !self.can_add_rows && !ar.add_row.rows.is_empty() {
+                    return Err(AuthError::Forbidden(
+                        "You are not allowed to add rows".to_string(),
+                    ));
+                }
+                ar.filters.extend(self.filters.iter().cloned());
+                Ok(ComputeRequest::Aggregation(ar))
+            }
+            other if self.filters.is_empty() => Ok(other),
+            _ => Err(AuthError::Forbidden(
+                "Only aggregation requests can be restricted to your entitlements".to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Entitlements {
+    #[serde(default)]
+    pub users: HashMap<String, Entitlement>,
+    /// Applies to the users not listed in `users`. If None, such users are Forbidden
+    #[serde(default)]
+    pub default: Option<Entitlement>,
+}
+
+pub enum AuthConfig {
+    None,
+    StaticToken {
+        token: String,
+        user: String,
+    },
+    /// user -> password
+    Basic(HashMap<String, String>),
+    Jwt {
+        key: DecodingKey,
+        validation: Validation,
+    },
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+pub struct AuthenticatedUser {
+    pub name: String,
+    pub entitlement: Entitlement,
+}
+
+impl AuthenticatedUser {
+    pub fn anonymous() -> Self {
+        Self {
+            name: ANONYMOUS.to_string(),
+            entitlement: Entitlement::unrestricted(),
+        }
+    }
+}
+
+/// Authentication together with entitlements
+/// Add as `app_data` to protect the endpoints which take [AuthenticatedUser]
+pub struct Security {
+    pub auth: AuthConfig,
+    /// If None, every authenticated user has full rights
+    pub entitlements: Option<Entitlements>,
+}
+
+impl Default for Security {
+    fn default() -> Self {
+        Self {
+            auth: AuthConfig::None,
+            entitlements: None,
+        }
+    }
+}
+
+impl Security {
+    pub fn new(auth: AuthConfig, entitlements: Option<Entitlements>) -> Self {
+        Self { auth, entitlements }
+    }
+
+    /// See module level docs for the variables
+    pub fn from_env() -> Result<Self, AuthError> {
+        let var = |name: &str| {
+            env::var(name).map_err(|_| AuthError::Config(format!("{name} must be set")))
+        };
+        let read = |path: String| {
+            fs::read(&path).map_err(|e| AuthError::Config(format!("Failed to read {path}: {e}")))
+        };
+
+        let mode = env::var("AUTH_MODE").unwrap_or_else(|_| "none".to_string());
+        let auth = match mode.to_lowercase().as_str() {
+            "none" => AuthConfig::None,
+            "token" => AuthConfig::StaticToken {
+                token: var("AUTH_TOKEN")?,
+                user: env::var("AUTH_TOKEN_USER").unwrap_or_else(|_| "ultima".to_string()),
+            },
+            "basic" => {
+                let file = String::from_utf8_lossy(&read(var("AUTH_BASIC_FILE")?)?).to_string();
+                AuthConfig::Basic(parse_basic_file(&file))
+            }
+            "jwt" => {
+                let (key, mut validation) = match env::var("AUTH_JWT_SECRET") {
+                    Ok(secret) => (
+                        DecodingKey::from_secret(secret.as_bytes()),
+                        Validation::new(Algorithm::HS256),
+                    ),
+                    Err(_) => (
+                        DecodingKey::from_rsa_pem(&read(var("AUTH_JWT_PUBLIC_KEY")?)?)
+                            .map_err(|e| AuthError::Config(e.to_string()))?,
+                        Validation::new(Algorithm::RS256),
+                    ),
+                };
+                if let Ok(aud) = env::var("AUTH_JWT_AUDIENCE") {
+                    validation.set_audience(&[aud]);
+                }
+                AuthConfig::Jwt { key, validation }
+            }
+            other => return Err(AuthError::Config(format!("Unknown AUTH_MODE {other}"))),
+        };
+
+        let entitlements = match env::var("ENTITLEMENTS_FILE") {
+            Ok(path) => Some(
+                serde_json::from_slice::<Entitlements>(&read(path)?)
+                    .map_err(|e| AuthError::Config(format!("Invalid entitlements: {e}")))?,
+            ),
+            Err(_) => None,
+        };
+
+        Ok(Self::new(auth, entitlements))
+    }
+
+    /// `authorization` is the value of the Authorization header
+    pub fn authenticate(
+        &self,
+        authorization: Option<&str>,
+    ) -> Result<AuthenticatedUser, AuthError> {
+        let name = match &self.auth {
+            AuthConfig::None => return Ok(AuthenticatedUser::anonymous()),
+            AuthConfig::StaticToken { token, user } => {
+                match constant_time_eq(bearer(authorization)?, token) {
+                    true => user.clone(),
+                    false => return Err(unauthorized("Invalid token", BEARER)),
+                }
+            }
+            AuthConfig::Basic(users) => {
+                let (user, password) = basic(authorization)?;
+                // Unknown users take as long as a wrong password
+                let valid = match users.get(&user) {
+                    Some(p) => constant_time_eq(&password, p),
+                    None => {
+                        constant_time_eq(&password, &password);
+                        false
+                    }
+                };
+                match valid {
+                    true => user,
+                    false => return Err(unauthorized("Invalid user or password", BASIC)),
+                }
+            }
+            AuthConfig::Jwt { key, validation } => {
+                decode::<Claims>(bearer(authorization)?, key, validation)
+                    .map_err(|e| unauthorized(&e.to_string(), BEARER))?
+                    .claims
+                    .sub
+            }
+        };
+
+        let entitlement = match &self.entitlements {
+            None => Entitlement::unrestricted(),
+            Some(ents) => ents
+                .users
+                .get(&name)
+                .or(ents.default.as_ref())
+                .cloned()
+                .ok_or_else(|| AuthError::Forbidden(format!("No entitlements for {name}")))?,
+        };
+
+        Ok(AuthenticatedUser { name, entitlement })
+    }
+}
+
+const BASIC: &str = "Basic realm=\"ultibi\"";
+const BEARER: &str = "Bearer";
+
+fn unauthorized(reason: &str, challenge: &'static str) -> AuthError {
+    AuthError::Unauthorized {
+        reason: reason.to_string(),
+        challenge,
+    }
+}
+
+fn bearer(authorization: Option<&str>) -> Result<&str, AuthError> {
+    authorization
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or_else(|| unauthorized("Missing bearer token", BEARER))
+}
+
+fn basic(authorization: Option<&str>) -> Result<(String, String), AuthError> {
+    let invalid = || unauthorized("Missing or malformed basic credentials", BASIC);
+    let encoded = authorization
+        .and_then(|h| h.strip_prefix("Basic "))
+        .ok_or_else(invalid)?;
+    let decoded = STANDARD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|d| String::from_utf8(d).ok())
+        .ok_or_else(invalid)?;
+    decoded
+        .split_once(':')
+        .map(|(u, p)| (u.to_string(), p.to_string()))
+        .ok_or_else(invalid)
+}
+
+/// Takes as long whichever byte differs, so that secrets can't be guessed byte by byte
+/// from the response times. Only the length of the secret leaks
+fn constant_time_eq(given: &str, secret: &str) -> bool {
+    let (given, secret) = (given.as_bytes(), secret.as_bytes());
+    let diff = secret
+        .iter()
+        .enumerate()
+        .fold((given.len() != secret.len()) as u8, |diff, (i, b)| {
+            diff | (b ^ given.get(i).copied().unwrap_or(0))
+        });
+    std::hint::black_box(diff) == 0
+}
+
+/// `user:password` per line. Empty lines and lines starting with `#` are skipped
+pub fn parse_basic_file(file: &str) -> HashMap<String, String> {
+    file.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_once(':'))
+        .map(|(u, p)| (u.to_string(), p.to_string()))
+        .collect()
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = AuthError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let res = match req.app_data::<Data<Security>>() {
+            Some(security) => security.authenticate(
+                req.headers()
+                    .get(header::AUTHORIZATION)
+                    .and_then(|h| h.to_str().ok()),
+            ),
+            // No Security set up means no authentication
+            None => Ok(AuthenticatedUser::anonymous()),
+        };
+        ready(res)
+    }
+}
@@ -0,0 +1,83 @@
+//! Errors of the computations as returned by the API
+//!
+//! The body is JSON, eg `{"code": "unknown_measure", "message": "...", "field": "Balance"}`.
+//! `code` is stable (see [UltimaErr::code]) whereas `message` is meant for humans.
+
+use std::fmt::{Display, Formatter};
+
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde::Serialize;
+use ultibi_core::errors::UltimaErr;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiError {
+    /// Kind of the error, eg unknown_measure, invalid_override or timeout
+    pub code: String,
+    pub message: String,
+    /// The offending measure, column, calc param etc, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(skip)]
+    status: StatusCode,
+}
+
+impl ApiError {
+    /// For errors which don't come from the computation
+    pub fn new(status: StatusCode, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+            field: None,
+            status,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+}
+
+/// Errors caused by the request are 400. The rest depends on what went wrong
+pub fn status_of(err: &UltimaErr) -> StatusCode {
+    match err {
+        e if e.is_client_error() => StatusCode::BAD_REQUEST,
+        UltimaErr::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+        UltimaErr::Cancelled(_) => StatusCode::CONFLICT,
+        UltimaErr::DataSource(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+impl From<&UltimaErr> for ApiError {
+    fn from(err: &UltimaErr) -> Self {
+        Self {
+            code: err.code().to_string(),
+            message: err.to_string(),
+            field: err.field().map(str::to_string),
+            status: status_of(err),
+        }
+    }
+}
+
+impl From<UltimaErr> for ApiError {
+    fn from(err: UltimaErr) -> Self {
+        Self::from(&err)
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status).json(self)
+    }
+}
@@ -0,0 +1,287 @@
+//! Arrow Flight endpoint for programmatic clients
+//!
+//! * `DoGet` - the ticket is a JSON serialised [ComputeRequest]. Streams the result as record batches
+//! * `GetSchema` / `ListFlights` - Arrow schema of the DataSet. Fields, measures and calc params
+//!   (same as `GET /FRTB`) are attached to the schema metadata under [DATASET_METADATA_KEY]
+//!
+//! Enable with the `flight` feature and set `FLIGHT_ADDRESS` (eg 127.0.0.1:50051).
+//! Clients authenticate with the `authorization` metadata, same as the Authorization header
+//! of the HTTP API (see [crate::auth])
+
+use std::{
+    io::Cursor,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+};
+
+use arrow_array::RecordBatch;
+use arrow_flight::{
+    encode::FlightDataEncoderBuilder,
+    flight_service_server::{FlightService, FlightServiceServer},
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaAsIpc, SchemaResult, Ticket,
+};
+use arrow_ipc::{reader::StreamReader, writer::IpcWriteOptions};
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use tokio::{net::TcpListener, task};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{transport::Server, Request, Response, Status, Streaming};
+use ultibi_core::{errors::UltimaErr, ComputeRequest, DataFrame, DataSet};
+
+use crate::{
+    audit::AuditLog,
+    auth::{AuthError, AuthenticatedUser, Security},
+    metrics::Metrics,
+};
+
+/// Schema metadata key holding the JSON description of the DataSet
+pub const DATASET_METADATA_KEY: &str = "ultibi.dataset";
+
+/// Descriptor path under which the DataSet is listed
+pub const DATASET_PATH: &str = "dataset";
+
+pub struct UltibiFlightService {
+    ds: Arc<RwLock<dyn DataSet>>,
+    security: Arc<Security>,
+    audit: Option<Arc<AuditLog>>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl From<AuthError> for Status {
+    fn from(e: AuthError) -> Self {
+        match e {
+            AuthError::Unauthorized { .. } => Status::unauthenticated(e.to_string()),
+            AuthError::Forbidden(_) => Status::permission_denied(e.to_string()),
+            AuthError::Config(_) => Status::internal(e.to_string()),
+        }
+    }
+}
+
+/// gRPC counterpart of [crate::errors::status_of]
+fn compute_status(e: UltimaErr) -> Status {
+    match e {
+        e if e.is_client_error() => Status::invalid_argument(e.to_string()),
+        UltimaErr::Timeout(_) => Status::deadline_exceeded(e.to_string()),
+        UltimaErr::Cancelled(_) => Status::cancelled(e.to_string()),
+        UltimaErr::DataSource(_) => Status::unavailable(e.to_string()),
+        e => Status::internal(e.to_string()),
+    }
+}
+
+impl UltibiFlightService {
+    /// No authentication, audit or metrics.
+    /// See [Self::with_security], [Self::with_audit] and [Self::with_metrics]
+    pub fn new(ds: Arc<RwLock<dyn DataSet>>) -> Self {
+        Self {
+            ds,
+            security: Default::default(),
+            audit: None,
+            metrics: None,
+        }
+    }
+
+    pub fn with_security(mut self, security: Arc<Security>) -> Self {
+        self.security = security;
+        self
+    }
+
+    pub fn with_audit(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn authenticate<T>(&self, request: &Request<T>) -> Result<AuthenticatedUser, Status> {
+        let authorization = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok());
+        Ok(self.security.authenticate(authorization)?)
+    }
+
+    /// Arrow schema of the DataSet, with its description in the metadata
+    fn dataset_schema(&self) -> Result<Schema, Status> {
+        let ds = self.ds.read().expect("Poisonned RwLock");
+        let schema = ds
+            .get_schema()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let description =
+            serde_json::to_string(&*ds).map_err(|e| Status::internal(e.to_string()))?;
+
+        let (arrow_schema, _) = to_arrow(&mut DataFrame::empty_with_schema(&schema))?;
+        Ok(arrow_schema
+            .as_ref()
+            .clone()
+            .with_metadata([(DATASET_METADATA_KEY.to_string(), description)].into()))
+    }
+
+    fn dataset_info(&self) -> Result<FlightInfo, Status> {
+        let schema = self.dataset_schema()?;
+        let descriptor = FlightDescriptor::new_path(vec![DATASET_PATH.into()]);
+        FlightInfo::new()
+            .try_with_schema(&schema)
+            .map(|info| info.with_descriptor(descriptor))
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+}
+
+/// Polars and arrow-rs don't share memory layouts, so we go through IPC
+/// (see [ultibi_io::write_ipc_stream])
+fn to_arrow(df: &mut DataFrame) -> Result<(SchemaRef, Vec<RecordBatch>), Status> {
+    let buf = ultibi_io::write_ipc_stream(df).map_err(|e| Status::internal(e.to_string()))?;
+    let reader = StreamReader::try_new(Cursor::new(buf), None)
+        .map_err(|e| Status::internal(e.to_string()))?;
+    let schema = reader.schema();
+    let batches = reader
+        .collect::<Result<Vec<RecordBatch>, ArrowError>>()
+        .map_err(|e| Status::internal(e.to_string()))?;
+    Ok((schema, batches))
+}
+
+#[tonic::async_trait]
+impl FlightService for UltibiFlightService {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("Handshake is not required"))
+    }
+
+    async fn list_flights(
+        &self,
+        request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        self.authenticate(&request)?;
+        let info = self.dataset_info()?;
+        Ok(Response::new(futures::stream::iter([Ok(info)]).boxed()))
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        self.authenticate(&request)?;
+        Ok(Response::new(self.dataset_info()?))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("PollFlightInfo is not supported"))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        self.authenticate(&request)?;
+        let schema = self.dataset_schema()?;
+        let options = IpcWriteOptions::default();
+        let res = SchemaAsIpc::new(&schema, &options)
+            .try_into()
+            .map_err(|e: ArrowError| Status::internal(e.to_string()))?;
+        Ok(Response::new(res))
+    }
+
+    #[tracing::instrument(name = "Flight DoGet", skip(self, request))]
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let user = self.authenticate(&request)?;
+        let ticket = request.into_inner();
+        let req = serde_json::from_slice::<ComputeRequest>(&ticket.ticket)
+            .map_err(|e| Status::invalid_argument(format!("Invalid ComputeRequest: {e}")))?;
+        let req = user.entitlement.restrict(req)?;
+
+        let ds = self.ds.clone();
+        let audit = self.audit.clone();
+        let metrics = self.metrics.clone();
+        let (schema, batches) = task::spawn_blocking(move || {
+            let ds = ds.read().expect("Poisonned RwLock");
+            let ctx = Default::default();
+            let res = match audit {
+                Some(audit) => audit.compute(&*ds, req, &ctx, &user.name, "flight"),
+                None => ds.compute_with_context(req, &ctx),
+            };
+            if let Some(metrics) = metrics {
+                metrics.observe_compute(&ctx, &res);
+            }
+            let mut df = res.map_err(compute_status)?;
+            to_arrow(&mut df)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))??;
+
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .build(futures::stream::iter(batches.into_iter().map(Ok)))
+            .map_err(Status::from);
+
+        Ok(Response::new(stream.boxed()))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("DoPut is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("DoAction is not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(futures::stream::empty().boxed()))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("DoExchange is not supported"))
+    }
+}
+
+/// Serves the Flight endpoint on `addr`
+pub async fn serve(
+    addr: SocketAddr,
+    service: UltibiFlightService,
+) -> Result<(), tonic::transport::Error> {
+    Server::builder()
+        .add_service(FlightServiceServer::new(service))
+        .serve(addr)
+        .await
+}
+
+/// Same as [serve], but on an already bound listener (eg on a random port)
+pub async fn serve_with_listener(
+    listener: TcpListener,
+    service: UltibiFlightService,
+) -> Result<(), tonic::transport::Error> {
+    Server::builder()
+        .add_service(FlightServiceServer::new(service))
+        .serve_with_incoming(TcpListenerStream::new(listener))
+        .await
+}
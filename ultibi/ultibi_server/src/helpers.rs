@@ -1,10 +1,122 @@
+use actix_web::{
+    http::{
+        header::{Accept, Header, HeaderName, HeaderValue},
+        StatusCode,
+    },
+    HttpMessage, HttpRequest, HttpResponse,
+};
+use serde::Deserialize;
 use ultibi_core::{
     polars::{
         prelude::{DataType, IdxSize, IntoLazy, NamedFrom, QuantileInterpolOptions, Schema},
         series::Series,
     },
-    DataFrame, PolarsResult,
+    DataFrame, ExecutionContext, PolarsResult,
 };
+use ultibi_io::OutputFormat;
+use utoipa::IntoParams;
+
+use crate::errors::ApiError;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct FormatParams {
+    /// json (default), csv, parquet or arrow (IPC stream).
+    /// Takes priority over the Accept header
+    format: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct TimingsParams {
+    /// If true, time spent in each stage of the computation is returned
+    /// in the `Server-Timing` header
+    #[serde(default)]
+    pub(crate) timings: bool,
+}
+
+/// Picks the format of the response body: `?format=` first, then the Accept header
+/// (in order of preference). Defaults to JSON
+pub(crate) fn negotiate_format(
+    req: &HttpRequest,
+    params: &FormatParams,
+) -> actix_web::Result<OutputFormat> {
+    if let Some(f) = &params.format {
+        return f
+            .parse::<OutputFormat>()
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "unsupported_format", e).into());
+    }
+
+    let accepted = Accept::parse(req)
+        .map(|accept| accept.ranked())
+        .unwrap_or_default();
+
+    Ok(accepted
+        .iter()
+        .find_map(|mime| OutputFormat::from_mime(mime.essence_str()))
+        .unwrap_or_default())
+}
+
+/// Format of a request body (see [ultibi_io::read]), from its Content-Type. Defaults to JSON
+pub(crate) fn body_format(req: &HttpRequest) -> actix_web::Result<OutputFormat> {
+    match req.mime_type() {
+        Ok(Some(mime)) => OutputFormat::from_mime(mime.essence_str()).ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "unsupported_format",
+                format!("Unsupported Content-Type {mime}"),
+            )
+            .into()
+        }),
+        Ok(None) => Ok(OutputFormat::default()),
+        Err(e) => {
+            Err(ApiError::new(StatusCode::BAD_REQUEST, "unsupported_format", e.to_string()).into())
+        }
+    }
+}
+
+/// Header set on a result which was cut to [ultibi_core::MAX_GROUPS] rows per grouping set
+pub(crate) const TRUNCATED_HEADER: &str = "x-result-truncated";
+
+/// Tells the caller if the result of `ctx` was truncated
+pub(crate) fn with_truncation(mut res: HttpResponse, ctx: &ExecutionContext) -> HttpResponse {
+    if ctx.is_truncated() {
+        res.headers_mut().insert(
+            HeaderName::from_static(TRUNCATED_HEADER),
+            HeaderValue::from_static("true"),
+        );
+    }
+    res
+}
+
+/// Wraps a body written by [ultibi_io::write]
+pub(crate) fn formatted_response(body: Vec<u8>, format: OutputFormat) -> HttpResponse {
+    let mut res = HttpResponse::Ok();
+    res.content_type(format.content_type());
+    if format != OutputFormat::Json {
+        res.insert_header((
+            "Content-Disposition",
+            format!(
+                "attachment; filename=\"result.{}\"",
+                format.file_extension()
+            ),
+        ));
+    }
+    res.body(body)
+}
+
+/// `Server-Timing` header (milliseconds per stage), as understood by browsers' dev tools
+pub(crate) fn with_server_timing(mut res: HttpResponse, ctx: &ExecutionContext) -> HttpResponse {
+    let timings = ctx
+        .timings()
+        .iter()
+        .map(|(stage, d)| format!("{stage};dur={:.3}", d.as_secs_f64() * 1000.0))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Ok(value) = HeaderValue::from_str(&timings) {
+        res.headers_mut()
+            .insert(HeaderName::from_static("server-timing"), value);
+    }
+    res
+}
 
 /// We override Polars' describe function to better fit our needs
 pub fn describe(df: DataFrame, percentiles: Option<&[f64]>) -> PolarsResult<DataFrame> {
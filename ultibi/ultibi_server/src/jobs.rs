@@ -0,0 +1,198 @@
+//! Registry of asynchronous compute jobs
+//!
+//! A job is a [ComputeRequest] submitted via `POST /jobs`, which runs in the background.
+//! Clients poll for its status, fetch the result once finished, or cancel it.
+//! Finished jobs (and their results) are kept for `retention` and then dropped.
+//! Jobs are only visible to the user who submitted them.
+
+use std::{
+    collections::HashMap,
+    env,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use ultibi_core::{DataFrame, ExecutionContext};
+use utoipa::ToSchema;
+
+pub type JobId = u64;
+
+/// By default finished results are kept for 10 minutes
+const DEFAULT_RETENTION_SECS: u64 = 600;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "status", content = "error")]
+pub enum JobStatus {
+    Running,
+    Finished,
+    Failed(String),
+    Cancelled,
+    TimedOut,
+}
+
+impl JobStatus {
+    pub fn is_done(&self) -> bool {
+        !matches!(self, JobStatus::Running)
+    }
+}
+
+pub(crate) struct Job {
+    owner: String,
+    status: JobStatus,
+    ctx: ExecutionContext,
+    submitted_at: SystemTime,
+    finished: Option<Instant>,
+    result: Option<DataFrame>,
+}
+
+/// What the client sees when polling a job
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JobInfo {
+    pub id: JobId,
+    #[serde(flatten)]
+    pub status: JobStatus,
+    /// Seconds since UNIX epoch
+    pub submitted_at: u64,
+    /// Seconds, if a timeout was set for this job
+    pub timeout: Option<u64>,
+}
+
+pub struct JobRegistry {
+    next_id: AtomicU64,
+    jobs: RwLock<HashMap<JobId, Job>>,
+    /// How long finished jobs are kept
+    pub retention: Duration,
+    /// Applied to jobs which don't specify their own timeout
+    pub default_timeout: Option<Duration>,
+}
+
+impl Default for JobRegistry {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_RETENTION_SECS), None)
+    }
+}
+
+impl JobRegistry {
+    pub fn new(retention: Duration, default_timeout: Option<Duration>) -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            jobs: Default::default(),
+            retention,
+            default_timeout,
+        }
+    }
+
+    /// Reads `JOB_RETENTION_SECS` and `JOB_TIMEOUT_SECS`
+    pub fn from_env() -> Self {
+        let secs = |var: &str| {
+            env::var(var)
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .map(Duration::from_secs)
+        };
+        Self::new(
+            secs("JOB_RETENTION_SECS").unwrap_or(Duration::from_secs(DEFAULT_RETENTION_SECS)),
+            secs("JOB_TIMEOUT_SECS"),
+        )
+    }
+
+    /// Context for a new computation. `timeout` takes priority over [JobRegistry::default_timeout]
+    pub fn context(&self, timeout: Option<Duration>) -> ExecutionContext {
+        match timeout.or(self.default_timeout) {
+            Some(t) => ExecutionContext::new().with_timeout(t),
+            None => ExecutionContext::new(),
+        }
+    }
+
+    /// Registers a new running job of `owner`
+    pub fn submit(&self, ctx: ExecutionContext, owner: &str) -> JobId {
+        self.purge_expired();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let job = Job {
+            owner: owner.to_string(),
+            status: JobStatus::Running,
+            ctx,
+            submitted_at: SystemTime::now(),
+            finished: None,
+            result: None,
+        };
+        self.jobs.write().expect("Poisonned RwLock").insert(id, job);
+        id
+    }
+
+    /// Records the outcome of a job. Ignored if the job has already been
+    /// marked as done (eg cancelled or timed out by the client)
+    pub fn finish(&self, id: JobId, status: JobStatus, result: Option<DataFrame>) {
+        let mut jobs = self.jobs.write().expect("Poisonned RwLock");
+        if let Some(job) = jobs.get_mut(&id) {
+            if !job.status.is_done() {
+                job.status = status;
+                job.result = result;
+                job.finished = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Cancels a running job. Returns None if `owner` has no such job
+    pub fn cancel(&self, id: JobId, owner: &str) -> Option<JobInfo> {
+        self.info(id, owner)?;
+        self.finish(id, JobStatus::Cancelled, None);
+        let jobs = self.jobs.read().expect("Poisonned RwLock");
+        jobs.get(&id).map(|job| {
+            job.ctx.cancel();
+            info(id, job)
+        })
+    }
+
+    pub fn info(&self, id: JobId, owner: &str) -> Option<JobInfo> {
+        self.purge_expired();
+        let jobs = self.jobs.read().expect("Poisonned RwLock");
+        jobs.get(&id)
+            .filter(|job| job.owner == owner)
+            .map(|job| info(id, job))
+    }
+
+    /// Status and, if finished, the result
+    pub fn result(&self, id: JobId, owner: &str) -> Option<(JobInfo, Option<DataFrame>)> {
+        self.purge_expired();
+        let jobs = self.jobs.read().expect("Poisonned RwLock");
+        jobs.get(&id)
+            .filter(|job| job.owner == owner)
+            .map(|job| (info(id, job), job.result.clone()))
+    }
+
+    /// Number of jobs still running
+    pub fn in_flight(&self) -> usize {
+        let jobs = self.jobs.read().expect("Poisonned RwLock");
+        jobs.values().filter(|job| !job.status.is_done()).count()
+    }
+
+    /// Drops jobs which finished longer than `retention` ago
+    pub fn purge_expired(&self) {
+        let retention = self.retention;
+        self.jobs
+            .write()
+            .expect("Poisonned RwLock")
+            .retain(|_, job| match job.finished {
+                Some(finished) => finished.elapsed() < retention,
+                None => true,
+            });
+    }
+}
+
+fn info(id: JobId, job: &Job) -> JobInfo {
+    JobInfo {
+        id,
+        status: job.status.clone(),
+        submitted_at: job
+            .submitted_at
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        timeout: job.ctx.timeout().map(|t| t.as_secs()),
+    }
+}
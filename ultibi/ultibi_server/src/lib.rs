@@ -12,7 +12,16 @@ use log::info;
 
 pub mod api;
 mod app;
+pub mod audit;
+pub mod auth;
+pub mod errors;
+#[cfg(feature = "flight")]
+pub mod flight;
 mod helpers;
+pub mod jobs;
+pub mod metrics;
+pub mod reload;
+pub mod templates;
 mod visual;
 
 pub use visual::VisualDataSet;
@@ -50,5 +59,31 @@ fn run_server(ds: Arc<RwLock<dyn DataSet>>) {
     let url = format!("http://localhost:{port}");
     dbg!(url);
 
-    runtime.block_on(crate::app::build_app(listener, ds, vec![]).expect("Failed to bind address"));
+    // Shared by all the endpoints, so that entries are numbered consistently
+    let audit = Arc::new(audit::AuditLog::from_env());
+    let metrics = Arc::new(metrics::Metrics::default());
+
+    #[cfg(feature = "flight")]
+    if let Some(flight_addr) = env::var("FLIGHT_ADDRESS")
+        .ok()
+        .and_then(|addr| addr.parse::<SocketAddr>().ok())
+    {
+        info!("Arrow Flight: grpc://{flight_addr}");
+        let service = flight::UltibiFlightService::new(ds.clone())
+            .with_security(Arc::new(
+                auth::Security::from_env().expect("Invalid authentication set up"),
+            ))
+            .with_audit(audit.clone())
+            .with_metrics(metrics.clone());
+        runtime.spawn(async move {
+            if let Err(e) = crate::flight::serve(flight_addr, service).await {
+                log::error!("Arrow Flight server failed: {e}");
+            }
+        });
+    }
+
+    runtime.block_on(
+        crate::app::build_app(listener, ds, vec![], audit, metrics)
+            .expect("Failed to bind address"),
+    );
 }
@@ -0,0 +1,257 @@
+//! Prometheus style metrics, served at `GET /api/metrics`
+//!
+//! HTTP requests are counted (by method, route and status) and timed by the [RequestMetrics]
+//! middleware. Computations are observed via [Metrics::observe_compute], which records the
+//! wall time of the computation, the time spent in each stage of the execution and the cache
+//! hits/misses (see [ExecutionContext]). Lazy stages only time the planning,
+//! see [ExecutionContext::timings].
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    future::{ready, Future, Ready},
+    pin::Pin,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    web::Data,
+    Error,
+};
+use ultibi_core::{errors::UltiResult, ExecutionContext};
+
+/// Upper bounds (seconds) of the latency histograms
+const BUCKETS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 60.0,
+];
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Not cumulative, one per [BUCKETS]
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, d: Duration) {
+        let secs = d.as_secs_f64();
+        if let Some(i) = BUCKETS.iter().position(|le| secs <= *le) {
+            self.buckets[i] += 1;
+        }
+        self.sum += secs;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (le, n) in BUCKETS.iter().zip(self.buckets) {
+            cumulative += n;
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{labels}{sep}le=\"{le}\"}} {cumulative}"
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{{labels}{sep}le=\"+Inf\"}} {}",
+            self.count
+        );
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// (method, route, status)
+    requests: BTreeMap<(String, String, u16), u64>,
+    /// (method, route)
+    latencies: BTreeMap<(String, String), Histogram>,
+    computations: u64,
+    compute_errors: u64,
+    compute_latency: Histogram,
+    stages: BTreeMap<String, Histogram>,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+/// Add as `app_data`, together with [RequestMetrics]
+#[derive(Debug, Default)]
+pub struct Metrics {
+    inner: Mutex<Inner>,
+}
+
+impl Metrics {
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        let mut inner = self.inner.lock().expect("Poisonned Mutex");
+        *inner
+            .requests
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default() += 1;
+        inner
+            .latencies
+            .entry((method.to_string(), route.to_string()))
+            .or_default()
+            .observe(duration);
+    }
+
+    /// Call once the computation (or drill through) which used `ctx` has finished
+    pub fn observe_compute<T>(&self, ctx: &ExecutionContext, result: &UltiResult<T>) {
+        self.observe_computations(ctx, std::slice::from_ref(result))
+    }
+
+    /// Same as [Metrics::observe_compute], for requests computed together (eg a batch)
+    pub fn observe_computations<T>(&self, ctx: &ExecutionContext, results: &[UltiResult<T>]) {
+        let timings = ctx.timings();
+        let (hits, misses) = ctx.cache_lookups();
+
+        let mut inner = self.inner.lock().expect("Poisonned Mutex");
+        inner.computations += results.len() as u64;
+        inner.compute_errors += results.iter().filter(|r| r.is_err()).count() as u64;
+        inner
+            .compute_latency
+            .observe(ctx.elapsed().unwrap_or_default());
+        for (stage, d) in timings {
+            inner.stages.entry(stage).or_default().observe(d);
+        }
+        inner.cache_hits += hits;
+        inner.cache_misses += misses;
+    }
+
+    /// Prometheus text exposition format
+    pub fn render(&self, jobs_in_flight: usize) -> String {
+        let inner = self.inner.lock().expect("Poisonned Mutex");
+        let mut out = String::new();
+
+        out.push_str("# TYPE ultibi_http_requests_total counter\n");
+        for ((method, route, status), n) in &inner.requests {
+            let _ = writeln!(
+                out,
+                "ultibi_http_requests_total{{method=\"{method}\",route=\"{route}\",status=\"{status}\"}} {n}"
+            );
+        }
+
+        out.push_str("# TYPE ultibi_http_request_errors_total counter\n");
+        let mut errors: BTreeMap<(&String, &String), u64> = BTreeMap::new();
+        for ((method, route, status), n) in &inner.requests {
+            if *status >= 400 {
+                *errors.entry((method, route)).or_default() += n;
+            }
+        }
+        for ((method, route), n) in errors {
+            let _ = writeln!(
+                out,
+                "ultibi_http_request_errors_total{{method=\"{method}\",route=\"{route}\"}} {n}"
+            );
+        }
+
+        out.push_str("# TYPE ultibi_http_request_duration_seconds histogram\n");
+        for ((method, route), h) in &inner.latencies {
+            h.render(
+                &mut out,
+                "ultibi_http_request_duration_seconds",
+                &format!("method=\"{method}\",route=\"{route}\""),
+            );
+        }
+
+        out.push_str("# TYPE ultibi_computations_total counter\n");
+        let _ = writeln!(out, "ultibi_computations_total {}", inner.computations);
+        out.push_str("# TYPE ultibi_compute_errors_total counter\n");
+        let _ = writeln!(out, "ultibi_compute_errors_total {}", inner.compute_errors);
+
+        out.push_str("# TYPE ultibi_compute_duration_seconds histogram\n");
+        inner
+            .compute_latency
+            .render(&mut out, "ultibi_compute_duration_seconds", "");
+
+        out.push_str("# TYPE ultibi_compute_stage_duration_seconds histogram\n");
+        for (stage, h) in &inner.stages {
+            h.render(
+                &mut out,
+                "ultibi_compute_stage_duration_seconds",
+                &format!("stage=\"{stage}\""),
+            );
+        }
+
+        out.push_str("# TYPE ultibi_cache_hits_total counter\n");
+        let _ = writeln!(out, "ultibi_cache_hits_total {}", inner.cache_hits);
+        out.push_str("# TYPE ultibi_cache_misses_total counter\n");
+        let _ = writeln!(out, "ultibi_cache_misses_total {}", inner.cache_misses);
+        out.push_str("# TYPE ultibi_cache_hit_ratio gauge\n");
+        let lookups = inner.cache_hits + inner.cache_misses;
+        let ratio = match lookups {
+            0 => 0.0,
+            _ => inner.cache_hits as f64 / lookups as f64,
+        };
+        let _ = writeln!(out, "ultibi_cache_hit_ratio {ratio}");
+
+        out.push_str("# TYPE ultibi_jobs_in_flight gauge\n");
+        let _ = writeln!(out, "ultibi_jobs_in_flight {jobs_in_flight}");
+
+        out
+    }
+}
+
+/// Middleware counting and timing requests into the [Metrics] registered as `app_data`.
+/// Does nothing if there are none.
+/// Requests are labelled by their route pattern (eg `/api/FRTB/jobs/{id}`), not the actual path
+pub struct RequestMetrics;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestMetricsMiddleware { service }))
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let metrics = req.app_data::<Data<Metrics>>().cloned();
+        let method = req.method().to_string();
+        // Unmatched paths are grouped together to keep the number of series bounded
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_string());
+        let started = Instant::now();
+
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            let res = fut.await;
+            if let Some(metrics) = metrics {
+                let status = match &res {
+                    Ok(r) => r.status().as_u16(),
+                    Err(e) => e.as_response_error().status_code().as_u16(),
+                };
+                metrics.observe_request(&method, &route, status, started.elapsed());
+            }
+            res
+        })
+    }
+}
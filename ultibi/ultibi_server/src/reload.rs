@@ -0,0 +1,254 @@
+//! Hot reload of the data of the DataSet, without restarting the server
+//!
+//! A new DataSet is built (and prepared) in the background, validated and only then swapped
+//! in place of the current one. Meanwhile requests keep being served from the current data.
+//! Reloads are triggered via `POST /api/admin/reload` or by [Reloader::watch]ing input files.
+//! If a reload fails, the current data is kept and the failure is reported in the [LoadStatus].
+//!
+//! The server is ready (see `GET /api/ready`) once some data has been loaded successfully.
+//! Use [Reloader::deferred] to start serving before the initial data is loaded.
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use ultibi_core::{
+    errors::{UltiResult, UltimaErr},
+    DataSet,
+};
+use utoipa::ToSchema;
+
+use crate::audit::AuditLog;
+
+/// How often watched files are checked for changes
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+type Swap = Box<dyn Fn() -> UltiResult<()> + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum LoadStatus {
+    /// No reload yet. Serving the data loaded on start up, if any (see [Reloader::deferred])
+    Initial,
+    /// Seconds since UNIX epoch
+    Loading {
+        since: u64,
+    },
+    Loaded {
+        at: u64,
+        data_version: String,
+    },
+    /// The previous data is still being served
+    Failed {
+        at: u64,
+        error: String,
+    },
+}
+
+impl LoadStatus {
+    pub fn state(&self) -> &'static str {
+        match self {
+            LoadStatus::Initial => "initial",
+            LoadStatus::Loading { .. } => "loading",
+            LoadStatus::Loaded { .. } => "loaded",
+            LoadStatus::Failed { .. } => "failed",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReloadError {
+    /// Another reload is in progress
+    InProgress,
+    Failed(String),
+}
+
+pub struct Reloader {
+    swap: Swap,
+    status: RwLock<LoadStatus>,
+    loading: AtomicBool,
+    reloads: AtomicU64,
+    /// Seconds since UNIX epoch of the last successful load. 0 if none yet
+    loaded_at: AtomicU64,
+    audit: RwLock<Option<Arc<AuditLog>>>,
+}
+
+impl Reloader {
+    /// `load` builds and prepares a new DataSet. It may panic (eg on a bad config),
+    /// which is reported as a failed reload
+    ///
+    /// `ds` must already be loaded and prepared
+    pub fn new<DS, F>(ds: Arc<RwLock<DS>>, load: F) -> Self
+    where
+        DS: DataSet + 'static,
+        F: Fn() -> UltiResult<DS> + Send + Sync + 'static,
+    {
+        let res = Self::deferred(ds, load);
+        res.loaded_at.store(now(), Ordering::Relaxed);
+        res
+    }
+
+    /// Same as [Reloader::new], but `ds` is only a placeholder (eg empty) until the first
+    /// successful [Reloader::reload]. Meanwhile the server is not ready
+    pub fn deferred<DS, F>(ds: Arc<RwLock<DS>>, load: F) -> Self
+    where
+        DS: DataSet + 'static,
+        F: Fn() -> UltiResult<DS> + Send + Sync + 'static,
+    {
+        let swap = move || {
+            let new = panic::catch_unwind(AssertUnwindSafe(&load))
+                .map_err(|p| UltimaErr::Other(panic_message(p)))??;
+            new.validate_frame(None, 0)?;
+            // Readers only wait for the swap. Old data is dropped once the lock is released
+            let _old = std::mem::replace(&mut *ds.write().expect("Poisonned RwLock"), new);
+            Ok(())
+        };
+        Self {
+            swap: Box::new(swap),
+            status: RwLock::new(LoadStatus::Initial),
+            loading: AtomicBool::new(false),
+            reloads: AtomicU64::new(0),
+            loaded_at: AtomicU64::new(0),
+            audit: RwLock::new(None),
+        }
+    }
+
+    /// Audit entries record the version of the data, which is bumped on each reload
+    pub fn set_audit(&self, audit: Arc<AuditLog>) {
+        *self.audit.write().expect("Poisonned RwLock") = Some(audit);
+    }
+
+    pub fn status(&self) -> LoadStatus {
+        self.status.read().expect("Poisonned RwLock").clone()
+    }
+
+    /// Some data has been validated and prepared. A failed reload keeps the previous data,
+    /// so it doesn't affect readiness
+    pub fn is_ready(&self) -> bool {
+        self.loaded_at().is_some()
+    }
+
+    /// Seconds since UNIX epoch of the last successful load
+    pub fn loaded_at(&self) -> Option<u64> {
+        match self.loaded_at.load(Ordering::Relaxed) {
+            0 => None,
+            at => Some(at),
+        }
+    }
+
+    /// Builds, validates and swaps in the new data. Blocks until done
+    pub fn reload(&self) -> Result<(), ReloadError> {
+        self.begin()?;
+        self.run()
+    }
+
+    /// Same as [Reloader::reload], but returns as soon as the reload has started.
+    /// The outcome is reported in the [LoadStatus]
+    pub fn spawn_reload(self: &Arc<Self>) -> Result<LoadStatus, ReloadError> {
+        self.begin()?;
+        let reloader = self.clone();
+        thread::spawn(move || reloader.run());
+        Ok(self.status())
+    }
+
+    fn begin(&self) -> Result<(), ReloadError> {
+        if self
+            .loading
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return Err(ReloadError::InProgress);
+        }
+        self.set_status(LoadStatus::Loading { since: now() });
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ReloadError> {
+        let res = (self.swap)();
+
+        let status = match &res {
+            Ok(()) => {
+                let n = self.reloads.fetch_add(1, Ordering::Relaxed) + 1;
+                let data_version = format!("reload-{n}");
+                if let Some(audit) = &*self.audit.read().expect("Poisonned RwLock") {
+                    audit.set_data_version(data_version.clone());
+                }
+                tracing::info!("Reloaded data, now {data_version}");
+                let at = now();
+                self.loaded_at.store(at, Ordering::Relaxed);
+                LoadStatus::Loaded { at, data_version }
+            }
+            Err(e) => {
+                tracing::error!("Failed to reload data: {e}");
+                LoadStatus::Failed {
+                    at: now(),
+                    error: e.to_string(),
+                }
+            }
+        };
+        self.set_status(status);
+        self.loading.store(false, Ordering::Release);
+
+        res.map_err(|e| ReloadError::Failed(e.to_string()))
+    }
+
+    /// Reloads whenever any of `files` changes. A change is picked up once the files
+    /// have stopped changing for `interval`, so that half written files are not loaded
+    pub fn watch(
+        self: &Arc<Self>,
+        files: Vec<PathBuf>,
+        interval: Duration,
+    ) -> thread::JoinHandle<()> {
+        let reloader = self.clone();
+        thread::spawn(move || {
+            let modified = || {
+                files
+                    .iter()
+                    .map(|f| f.metadata().and_then(|m| m.modified()).ok())
+                    .collect::<Vec<_>>()
+            };
+            let mut loaded = modified();
+            let mut previous = loaded.clone();
+            loop {
+                thread::sleep(interval);
+                let current = modified();
+                if current != loaded && current == previous {
+                    tracing::info!("Input files changed, reloading");
+                    // Failures are reported in the status
+                    let _ = reloader.reload();
+                    loaded = current.clone();
+                }
+                previous = current;
+            }
+        })
+    }
+
+    fn set_status(&self, status: LoadStatus) {
+        *self.status.write().expect("Poisonned RwLock") = status;
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn panic_message(p: Box<dyn Any + Send>) -> String {
+    let msg = p
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| p.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown reason".to_string());
+    format!("Loading panicked: {msg}")
+}
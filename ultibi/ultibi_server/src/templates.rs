@@ -0,0 +1,291 @@
+//! Saved requests (aka templates), named by [AggregationRequest::name]
+//!
+//! Templates are owned by the user who saved them and are only visible to them, unless shared.
+//! Names are unique among the shared templates and among the templates of each user, so that
+//! users can name their private templates as they like. A user's own template comes first.
+//! Templates passed on start up (eg via `--requests`) are shared, read only and not persisted.
+//! The rest are persisted to `$TEMPLATES_STORE`, if set. Otherwise they are kept in memory only.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::{Display, Formatter},
+    fs,
+    path::PathBuf,
+    sync::RwLock,
+};
+
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde::{Deserialize, Serialize};
+use ultibi_core::AggregationRequest;
+use utoipa::ToSchema;
+
+use crate::auth::AuthenticatedUser;
+
+/// Owner of the templates passed on start up
+pub const SYSTEM_OWNER: &str = "system";
+
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound(String),
+    Conflict(String),
+    Forbidden(String),
+    Invalid(String),
+    Io(String),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound(name) => write!(f, "No template {name}"),
+            TemplateError::Conflict(name) => write!(f, "Template {name} already exists"),
+            TemplateError::Forbidden(name) => write!(f, "You can't modify template {name}"),
+            TemplateError::Invalid(reason) => write!(f, "Invalid template: {reason}"),
+            TemplateError::Io(reason) => write!(f, "Failed to save templates: {reason}"),
+        }
+    }
+}
+
+impl ResponseError for TemplateError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            TemplateError::NotFound(_) => StatusCode::NOT_FOUND,
+            TemplateError::Conflict(_) => StatusCode::CONFLICT,
+            TemplateError::Forbidden(_) => StatusCode::FORBIDDEN,
+            TemplateError::Invalid(_) => StatusCode::BAD_REQUEST,
+            TemplateError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).body(self.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StoredTemplate {
+    pub owner: String,
+    /// Visible to everyone if true
+    #[serde(default)]
+    pub shared: bool,
+    pub request: AggregationRequest,
+}
+
+impl StoredTemplate {
+    fn visible_to(&self, user: &AuthenticatedUser) -> bool {
+        self.shared || self.owner == user.name || user.entitlement.admin
+    }
+
+    /// Always set, see [template_name]
+    fn name(&self) -> &str {
+        self.request.name.as_deref().unwrap_or_default()
+    }
+}
+
+pub struct TemplateStore {
+    /// If None, templates are not persisted
+    path: Option<PathBuf>,
+    /// Read only
+    seeded: BTreeMap<String, StoredTemplate>,
+    templates: RwLock<Vec<StoredTemplate>>,
+}
+
+impl TemplateStore {
+    /// Loads previously saved templates from `path`, if it exists.
+    /// `seed` templates without a name are named by their position
+    pub fn new(
+        path: Option<PathBuf>,
+        seed: Vec<AggregationRequest>,
+    ) -> Result<Self, TemplateError> {
+        let seeded = seed
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut request)| {
+                let name = request
+                    .name
+                    .get_or_insert_with(|| format!("Template {}", i + 1))
+                    .clone();
+                let template = StoredTemplate {
+                    owner: SYSTEM_OWNER.to_string(),
+                    shared: true,
+                    request,
+                };
+                (name, template)
+            })
+            .collect();
+
+        let templates = match &path {
+            Some(p) if p.exists() => {
+                let file = fs::read(p).map_err(|e| TemplateError::Io(e.to_string()))?;
+                serde_json::from_slice(&file).map_err(|e| TemplateError::Invalid(e.to_string()))?
+            }
+            _ => vec![],
+        };
+
+        Ok(Self {
+            path,
+            seeded,
+            templates: RwLock::new(templates),
+        })
+    }
+
+    /// Reads `TEMPLATES_STORE`
+    pub fn from_env(seed: Vec<AggregationRequest>) -> Result<Self, TemplateError> {
+        Self::new(env::var("TEMPLATES_STORE").ok().map(PathBuf::from), seed)
+    }
+
+    /// Templates the user can see: their own, shared and start up ones
+    pub fn visible(&self, user: &AuthenticatedUser) -> Vec<StoredTemplate> {
+        let templates = self.templates.read().expect("Poisonned RwLock");
+        self.seeded
+            .values()
+            .chain(templates.iter())
+            .filter(|t| t.visible_to(user))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(
+        &self,
+        name: &str,
+        user: &AuthenticatedUser,
+    ) -> Result<StoredTemplate, TemplateError> {
+        let templates = self.templates.read().expect("Poisonned RwLock");
+        self.seeded
+            .get(name)
+            .or_else(|| find(&templates, name, user).map(|i| &templates[i]))
+            .cloned()
+            .ok_or_else(|| TemplateError::NotFound(name.to_string()))
+    }
+
+    pub fn create(
+        &self,
+        mut request: AggregationRequest,
+        shared: bool,
+        user: &AuthenticatedUser,
+    ) -> Result<StoredTemplate, TemplateError> {
+        let name = template_name(&mut request)?;
+        let mut templates = self.templates.write().expect("Poisonned RwLock");
+        if self.taken(&templates, &name, &user.name, None) {
+            return Err(TemplateError::Conflict(name));
+        }
+
+        let template = StoredTemplate {
+            owner: user.name.clone(),
+            shared,
+            request,
+        };
+        templates.push(template.clone());
+        self.persist(&templates)?;
+        Ok(template)
+    }
+
+    /// Replaces the request of the template `name`.
+    /// If the name of the new request is different, the template gets renamed
+    pub fn update(
+        &self,
+        name: &str,
+        mut request: AggregationRequest,
+        shared: Option<bool>,
+        user: &AuthenticatedUser,
+    ) -> Result<StoredTemplate, TemplateError> {
+        let new_name = template_name(&mut request)?;
+        let mut templates = self.templates.write().expect("Poisonned RwLock");
+        let i = self.modifiable(&templates, name, user)?;
+        let existing = &templates[i];
+        let shared = shared.unwrap_or(existing.shared);
+
+        let becomes_shared = shared && !existing.shared;
+        if (new_name != name || becomes_shared)
+            && self.taken(&templates, &new_name, &existing.owner, Some(i))
+        {
+            return Err(TemplateError::Conflict(new_name));
+        }
+
+        let template = StoredTemplate {
+            owner: existing.owner.clone(),
+            shared,
+            request,
+        };
+        templates[i] = template.clone();
+        self.persist(&templates)?;
+        Ok(template)
+    }
+
+    pub fn delete(&self, name: &str, user: &AuthenticatedUser) -> Result<(), TemplateError> {
+        let mut templates = self.templates.write().expect("Poisonned RwLock");
+        let i = self.modifiable(&templates, name, user)?;
+        templates.remove(i);
+        self.persist(&templates)
+    }
+
+    /// Only owners and admins can modify a template. Start up templates are read only
+    fn modifiable(
+        &self,
+        templates: &[StoredTemplate],
+        name: &str,
+        user: &AuthenticatedUser,
+    ) -> Result<usize, TemplateError> {
+        if self.seeded.contains_key(name) {
+            return Err(TemplateError::Forbidden(name.to_string()));
+        }
+        match find(templates, name, user) {
+            Some(i) if templates[i].owner == user.name || user.entitlement.admin => Ok(i),
+            Some(_) => Err(TemplateError::Forbidden(name.to_string())),
+            None => Err(TemplateError::NotFound(name.to_string())),
+        }
+    }
+
+    /// If `name` is used by a shared template or by another template of `owner`.
+    /// Private templates of other users don't count, not to reveal them
+    fn taken(
+        &self,
+        templates: &[StoredTemplate],
+        name: &str,
+        owner: &str,
+        except: Option<usize>,
+    ) -> bool {
+        self.seeded.contains_key(name)
+            || templates.iter().enumerate().any(|(i, t)| {
+                Some(i) != except && t.name() == name && (t.shared || t.owner == owner)
+            })
+    }
+
+    /// Writes to a temporary file first, so that a crash doesn't corrupt the store
+    fn persist(&self, templates: &[StoredTemplate]) -> Result<(), TemplateError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let io = |e: std::io::Error| TemplateError::Io(e.to_string());
+        let body =
+            serde_json::to_vec_pretty(templates).map_err(|e| TemplateError::Io(e.to_string()))?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, body).map_err(io)?;
+        fs::rename(&tmp, path).map_err(io)
+    }
+}
+
+/// Position of the template `name` the user sees: their own, else a shared one,
+/// else (for admins) anyone's
+fn find(templates: &[StoredTemplate], name: &str, user: &AuthenticatedUser) -> Option<usize> {
+    let position = |f: fn(&StoredTemplate, &AuthenticatedUser) -> bool| {
+        templates
+            .iter()
+            .position(|t| t.name() == name && f(t, user))
+    };
+    position(|t, user| t.owner == user.name)
+        .or_else(|| position(|t, _| t.shared))
+        .or_else(|| position(StoredTemplate::visible_to))
+}
+
+/// Trims the name of the request
+fn template_name(request: &mut AggregationRequest) -> Result<String, TemplateError> {
+    match request.name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => {
+            let name = name.to_string();
+            request.name = Some(name.clone());
+            Ok(name)
+        }
+        _ => Err(TemplateError::Invalid("name is required".to_string())),
+    }
+}
@@ -0,0 +1,177 @@
+use std::{env, fs, time::Duration};
+
+use ultibi_core::{errors::UltimaErr, polars::df, ComputeRequest};
+use ultibi_server::audit::AuditLog;
+
+fn request() -> ComputeRequest {
+    let req = r#"{"measures": [["Balance", "sum"]], "groupby": ["State"], "filters": []}"#;
+    serde_json::from_str::<ComputeRequest>(req).expect("Could not parse request")
+}
+
+#[test]
+fn record_list_and_reopen() {
+    let path = env::temp_dir().join(format!("ultibi_audit_{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let audit = AuditLog::new(&path, "v1".to_string());
+    let ok = df!("State" => ["NY", "Washington"], "Balance_sum" => [25.0, 20.0]).unwrap();
+    let first = audit.record(
+        "alice",
+        "execute",
+        request(),
+        Duration::from_millis(5),
+        &Ok(ok),
+    );
+    audit.set_data_version("v2".to_string());
+    let second = audit.record(
+        "bob",
+        "job",
+        request(),
+        Duration::from_millis(7),
+        &Err(UltimaErr::Other("boom".to_string())),
+    );
+
+    let all = audit.list(None, 0, 10);
+    assert_eq!(all.len(), 2);
+    // Most recent first
+    assert_eq!(all[0].id, second);
+    assert_eq!(all[0].data_version, "v2");
+    assert_eq!(all[0].error.as_deref(), Some("boom"));
+
+    let alices = audit.list(Some("alice"), 0, 10);
+    assert_eq!(alices.len(), 1);
+    assert_eq!(alices[0].shape, Some((2, 2)));
+    assert_eq!(audit.get(first).unwrap().request, Some(request()));
+
+    // Numbering continues after a restart
+    let reopened = AuditLog::new(&path, "v3".to_string());
+    let third = reopened.record(
+        "alice",
+        "replay of 1",
+        request(),
+        Duration::ZERO,
+        &Err(UltimaErr::Other("boom".to_string())),
+    );
+    assert_eq!(third, second + 1);
+
+    // Entries read at start up are indexed together with the new ones
+    let alices = reopened.list(Some("alice"), 0, 10);
+    assert_eq!(
+        alices.iter().map(|e| e.id).collect::<Vec<_>>(),
+        [third, first]
+    );
+    assert_eq!(reopened.list(None, 1, 1)[0].id, second);
+    assert_eq!(reopened.get(first).unwrap().shape, Some((2, 2)));
+    assert!(reopened.get(third + 1).is_none());
+    assert!(reopened.list(Some("carol"), 0, 10).is_empty());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn drill_through_is_recorded() {
+    use ultibi_core::{
+        datasource::DataSource, new::NewSourcedDataSet, DataSetBase, DrillThroughRequest,
+        ExecutionContext,
+    };
+    use ultibi_server::audit::AuditDetail;
+
+    let path = env::temp_dir().join(format!("ultibi_audit_drill_{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let audit = AuditLog::new(&path, "v1".to_string());
+
+    let data = DataSetBase::from_vec(
+        DataSource::InMemory(
+            df!("State" => ["NY", "NY", "Texas"], "Balance" => [1., 2., 3.]).unwrap(),
+        ),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    );
+    let req = r#"{"request": {"measures": [["Balance", "sum"]], "groupby": ["State"]},
+        "cell": {"State": "NY"}, "limit": 1}"#;
+    let req = serde_json::from_str::<DrillThroughRequest>(req).unwrap();
+    let page = audit
+        .drill_through(&data, req, &ExecutionContext::new(), "alice")
+        .unwrap();
+    assert_eq!(page.total, 2);
+
+    let entry = &audit.list(Some("alice"), 0, 10)[0];
+    assert_eq!(entry.source, "drillthrough");
+    assert_eq!(entry.shape, Some((1, 2)));
+    assert!(matches!(
+        &entry.detail,
+        Some(AuditDetail::DrillThrough { cell, limit: 1, .. }) if cell["State"].as_deref() == Some("NY")
+    ));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn data_changes_are_recorded() {
+    use ultibi_core::{
+        datasource::DataSource, filters::FilterE, new::NewSourcedDataSet, DataSetBase,
+    };
+    use ultibi_server::audit::AuditDetail;
+
+    let path = env::temp_dir().join(format!("ultibi_audit_change_{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let audit = AuditLog::new(&path, "v1".to_string());
+
+    let mut data = DataSetBase::from_vec(
+        DataSource::InMemory(df!("State" => ["NY", "Texas"], "Balance" => [1., 2.]).unwrap()),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    );
+    let new_rows = df!("State" => ["NY"], "Balance" => [3.]).unwrap();
+    audit.append_frame(&mut data, new_rows, "admin").unwrap();
+    let filters = vec![vec![FilterE::Eq {
+        field: "State".to_string(),
+        value: Some("NY".to_string()),
+    }]];
+    audit
+        .delete_where(&mut data, filters.clone(), "admin")
+        .unwrap();
+    assert!(audit.delete_where(&mut data, vec![], "admin").is_err());
+
+    let entries = audit.list(Some("admin"), 0, 10);
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().all(|e| e.request.is_none()));
+    assert!(entries[0].error.is_some());
+    assert_eq!(entries[1].source, "delete");
+    assert_eq!(
+        entries[1].detail,
+        Some(AuditDetail::Delete {
+            filters,
+            rows: Some(2)
+        })
+    );
+    assert_eq!(entries[2].source, "append");
+    assert_eq!(
+        entries[2].detail,
+        Some(AuditDetail::Append { rows: Some(1) })
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn in_memory() {
+    let audit = AuditLog::in_memory("v1".to_string());
+    let ok = df!("State" => ["NY"], "Balance_sum" => [25.0]).unwrap();
+    let first = audit.record("alice", "execute", request(), Duration::ZERO, &Ok(ok));
+    let second = audit.record(
+        "bob",
+        "execute",
+        request(),
+        Duration::ZERO,
+        &Err(UltimaErr::Other("boom".to_string())),
+    );
+
+    assert_eq!(audit.list(None, 0, 10).len(), 2);
+    assert_eq!(audit.list(Some("bob"), 0, 10)[0].id, second);
+    assert_eq!(audit.get(first).unwrap().shape, Some((1, 2)));
+}
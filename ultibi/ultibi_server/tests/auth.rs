@@ -0,0 +1,206 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use jsonwebtoken::{encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde_json::json;
+use ultibi_core::ComputeRequest;
+use ultibi_server::auth::{parse_basic_file, AuthConfig, Entitlement, Entitlements, Security};
+
+fn request(overrides: bool) -> ComputeRequest {
+    let overrides = if overrides {
+        r#"[{"field": "Balance", "value": "1", "filters": []}]"#
+    } else {
+        "[]"
+    };
+    let req = format!(
+        r#"{{"measures": [["Balance", "sum"]], "groupby": ["State"],
+        "filters": [[{{"op": "Eq", "field": "Sex", "value": "female"}}]], "overrides": {overrides}}}"#
+    );
+    serde_json::from_str::<ComputeRequest>(&req).expect("Could not parse request")
+}
+
+fn ny_only() -> Entitlement {
+    Entitlement {
+        filters: serde_json::from_str(r#"[[{"op": "Eq", "field": "State", "value": "NY"}]]"#)
+            .unwrap(),
+        can_override: false,
+        can_add_rows: false,
+        admin: false,
+    }
+}
+
+fn basic_security() -> Security {
+    Security::new(
+        AuthConfig::Basic(parse_basic_file("# test users\nalice:secret\nbob:pass\n")),
+        Some(Entitlements {
+            users: HashMap::from([("alice".to_string(), ny_only())]),
+            default: None,
+        }),
+    )
+}
+
+#[test]
+fn restrictions_are_anded() {
+    let res = ny_only().restrict(request(false)).unwrap();
+    match res {
+        ComputeRequest::Aggregation(ar) => assert_eq!(ar.filters.len(), 2),
+        _ => panic!("Expected aggregation request"),
+    }
+}
+
+#[test]
+fn overrides_forbidden() {
+    assert!(ny_only().restrict(request(true)).is_err());
+    assert!(Entitlement::unrestricted().restrict(request(true)).is_ok());
+}
+
+#[test]
+fn basic_auth() {
+    let security = basic_security();
+
+    // alice:secret
+    let user = security
+        .authenticate(Some("Basic YWxpY2U6c2VjcmV0"))
+        .expect("alice should be authenticated");
+    assert_eq!(user.name, "alice");
+    assert_eq!(user.entitlement, ny_only());
+
+    // alice:wrong
+    assert!(security
+        .authenticate(Some("Basic YWxpY2U6d3Jvbmc="))
+        .is_err());
+    assert!(security.authenticate(None).is_err());
+    // bob:pass is valid, but bob has no entitlements
+    assert!(security.authenticate(Some("Basic Ym9iOnBhc3M=")).is_err());
+}
+
+#[test]
+fn static_token() {
+    let security = Security::new(
+        AuthConfig::StaticToken {
+            token: "xyz".to_string(),
+            user: "ultima".to_string(),
+        },
+        None,
+    );
+
+    let user = security.authenticate(Some("Bearer xyz")).unwrap();
+    assert_eq!(user.name, "ultima");
+    assert_eq!(user.entitlement, Entitlement::unrestricted());
+    assert!(security.authenticate(Some("Bearer abc")).is_err());
+    // Prefixes and extensions of the token
+    assert!(security.authenticate(Some("Bearer xy")).is_err());
+    assert!(security.authenticate(Some("Bearer xyzz")).is_err());
+}
+
+#[test]
+fn no_auth() {
+    let user = Security::default().authenticate(None).unwrap();
+    assert_eq!(user.entitlement, Entitlement::unrestricted());
+}
+
+/// Claims of a token of `sub`, valid for an hour
+fn claims(sub: &str, aud: Option<&str>) -> serde_json::Value {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut claims = json!({"sub": sub, "exp": now + 3600});
+    if let Some(aud) = aud {
+        claims["aud"] = json!(aud);
+    }
+    claims
+}
+
+fn bearer(token: String) -> String {
+    format!("Bearer {token}")
+}
+
+#[test]
+fn jwt_hs256() {
+    let security = Security::new(
+        AuthConfig::Jwt {
+            key: DecodingKey::from_secret(b"secret"),
+            validation: Validation::new(Algorithm::HS256),
+        },
+        None,
+    );
+    let sign = |claims: &serde_json::Value, secret: &[u8]| {
+        encode(
+            &Header::new(Algorithm::HS256),
+            claims,
+            &EncodingKey::from_secret(secret),
+        )
+        .unwrap()
+    };
+
+    let token = sign(&claims("alice", None), b"secret");
+    let user = security.authenticate(Some(&bearer(token))).unwrap();
+    assert_eq!(user.name, "alice");
+
+    let forged = sign(&claims("alice", None), b"guessed");
+    assert!(security.authenticate(Some(&bearer(forged))).is_err());
+
+    let mut expired = claims("alice", None);
+    expired["exp"] = json!(1_000_000);
+    let expired = sign(&expired, b"secret");
+    assert!(security.authenticate(Some(&bearer(expired))).is_err());
+
+    assert!(security.authenticate(Some("Bearer not.a.jwt")).is_err());
+    assert!(security.authenticate(None).is_err());
+}
+
+#[test]
+fn jwt_rs256_with_audience() {
+    // Test only key pair
+    let private_key = include_bytes!("data/jwt_private.pem");
+    let public_key = include_bytes!("data/jwt_public.pem");
+
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_audience(&["ultibi"]);
+    let security = Security::new(
+        AuthConfig::Jwt {
+            key: DecodingKey::from_rsa_pem(public_key).unwrap(),
+            validation,
+        },
+        Some(Entitlements {
+            users: HashMap::from([("alice".to_string(), ny_only())]),
+            default: None,
+        }),
+    );
+    let sign = |claims: &serde_json::Value| {
+        encode(
+            &Header::new(Algorithm::RS256),
+            claims,
+            &EncodingKey::from_rsa_pem(private_key).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let token = sign(&claims("alice", Some("ultibi")));
+    let user = security.authenticate(Some(&bearer(token))).unwrap();
+    assert_eq!(user.name, "alice");
+    assert_eq!(user.entitlement, ny_only());
+
+    let other_audience = sign(&claims("alice", Some("another_app")));
+    assert!(security
+        .authenticate(Some(&bearer(other_audience)))
+        .is_err());
+    let no_audience = sign(&claims("alice", None));
+    assert!(security.authenticate(Some(&bearer(no_audience))).is_err());
+
+    // HS256 signed with the public key must not pass as RS256
+    let confused = encode(
+        &Header::new(Algorithm::HS256),
+        &claims("alice", Some("ultibi")),
+        &EncodingKey::from_secret(public_key),
+    )
+    .unwrap();
+    assert!(security.authenticate(Some(&bearer(confused))).is_err());
+
+    // Valid token, but bob has no entitlements
+    let bob = sign(&claims("bob", Some("ultibi")));
+    assert!(security.authenticate(Some(&bearer(bob))).is_err());
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use actix_web::{body::to_bytes, http::StatusCode, ResponseError};
+use serde_json::{json, Value};
+use ultibi_core::errors::UltimaErr;
+use ultibi_server::errors::ApiError;
+
+async fn body(err: &ApiError) -> Value {
+    let bytes = to_bytes(err.error_response().into_body()).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[actix_web::test]
+async fn unknown_measure_is_bad_request() {
+    let err = ApiError::from(UltimaErr::UnknownMeasure {
+        measure: "Balance".to_string(),
+        available: vec!["Notional".to_string()],
+    });
+
+    assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    let body = body(&err).await;
+    assert_eq!(body["code"], json!("unknown_measure"));
+    assert_eq!(body["field"], json!("Balance"));
+}
+
+#[actix_web::test]
+async fn timeout_is_gateway_timeout() {
+    let err = ApiError::from(UltimaErr::Timeout(Duration::from_secs(5)));
+
+    assert_eq!(err.status_code(), StatusCode::GATEWAY_TIMEOUT);
+    let body = body(&err).await;
+    assert_eq!(body["code"], json!("timeout"));
+    assert!(body.get("field").is_none());
+}
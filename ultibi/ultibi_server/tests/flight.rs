@@ -0,0 +1,104 @@
+#![cfg(feature = "flight")]
+
+use std::sync::{Arc, RwLock};
+
+use arrow_array::{Float64Array, RecordBatch};
+use arrow_flight::{FlightClient, FlightDescriptor, Ticket};
+use futures::TryStreamExt;
+use tokio::net::TcpListener;
+use tonic::transport::Endpoint;
+use ultibi_core::{
+    datasource::DataSource,
+    new::NewSourcedDataSet,
+    polars::df,
+    prelude::{DataSet, DataSetBase},
+    Measure,
+};
+use ultibi_server::flight::{serve_with_listener, UltibiFlightService, DATASET_METADATA_KEY};
+
+async fn spawn_flight() -> FlightClient {
+    let df = df!(
+        "State" => ["NY", "NY", "Washington"],
+        "Balance" => [10.0, 15.0, 20.0]
+    )
+    .unwrap();
+    let ds: Arc<RwLock<dyn DataSet>> = Arc::new(RwLock::new(DataSetBase::from_vec(
+        DataSource::InMemory(df),
+        Vec::<Measure>::new(),
+        true,
+        vec![],
+        Default::default(),
+    )));
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind random port");
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(serve_with_listener(listener, UltibiFlightService::new(ds)));
+
+    let channel = Endpoint::from_shared(format!("http://127.0.0.1:{port}"))
+        .unwrap()
+        .connect()
+        .await
+        .expect("Failed to connect");
+    FlightClient::new(channel)
+}
+
+#[tokio::test]
+async fn do_get_streams_result() {
+    let mut client = spawn_flight().await;
+
+    let req = r#"{"measures": [["Balance", "sum"]], "groupby": ["State"], "filters": []}"#;
+    let batches: Vec<RecordBatch> = client
+        .do_get(Ticket::new(req))
+        .await
+        .expect("DoGet failed")
+        .try_collect()
+        .await
+        .expect("Failed to read stream");
+
+    let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(rows, 2);
+
+    let balance = batches[0]
+        .column_by_name("Balance_sum")
+        .expect("Balance_sum missing")
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert_eq!(balance.value(0), 25.0);
+}
+
+#[tokio::test]
+async fn do_get_invalid_ticket() {
+    let mut client = spawn_flight().await;
+    assert!(client.do_get(Ticket::new("not a request")).await.is_err());
+}
+
+#[tokio::test]
+async fn schema_exposes_fields_and_measures() {
+    let mut client = spawn_flight().await;
+
+    let schema = client
+        .get_schema(FlightDescriptor::new_path(vec!["dataset".into()]))
+        .await
+        .expect("GetSchema failed");
+    assert!(schema.field_with_name("State").is_ok());
+
+    let description: serde_json::Value =
+        serde_json::from_str(&schema.metadata()[DATASET_METADATA_KEY]).unwrap();
+    assert_eq!(description["fields"], serde_json::json!(["State"]));
+    assert!(description["measures"]
+        .as_object()
+        .unwrap()
+        .contains_key("Balance"));
+
+    let flights: Vec<_> = client
+        .list_flights("")
+        .await
+        .expect("ListFlights failed")
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(flights.len(), 1);
+}
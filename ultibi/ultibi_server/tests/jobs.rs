@@ -0,0 +1,149 @@
+//! Submit, poll, fetch and cancel jobs over HTTP
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use actix_web::{
+    http::StatusCode,
+    test,
+    web::{Data, ServiceConfig},
+    App,
+};
+use serde_json::{json, Value};
+use ultibi_core::{
+    datasource::DataSource, new::NewSourcedDataSet, polars::df, DataSet, DataSetBase,
+};
+use ultibi_server::{api::routers, audit::AuditLog, jobs::JobRegistry, metrics::Metrics};
+
+/// What the job handlers take from the app
+fn state() -> impl FnOnce(&mut ServiceConfig) {
+    let data = DataSetBase::from_vec(
+        DataSource::InMemory(
+            df!("State" => ["NY", "NY", "Texas"], "Balance" => [1., 2., 3.]).unwrap(),
+        ),
+        vec![],
+        true,
+        vec![],
+        Default::default(),
+    );
+    let ds: Arc<RwLock<dyn DataSet>> = Arc::new(RwLock::new(data));
+    let audit = AuditLog::in_memory("v1".to_string());
+
+    move |config: &mut ServiceConfig| {
+        config
+            .app_data(Data::from(ds))
+            .app_data(Data::new(JobRegistry::default()))
+            .app_data(Data::new(audit))
+            .app_data(Data::new(Metrics::default()));
+    }
+}
+
+fn request() -> Value {
+    json!({"measures": [["Balance", "sum"]], "groupby": ["State"], "filters": []})
+}
+
+#[actix_web::test]
+async fn submit_poll_and_fetch() {
+    let app = test::init_service(
+        App::new()
+            .configure(state())
+            .configure(routers::configure()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/FRTB/jobs")
+        .set_json(request())
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+    let location = res
+        .headers()
+        .get("Location")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    let info: Value = test::read_body_json(res).await;
+    assert_eq!(location, format!("/api/FRTB/jobs/{}", info["id"]));
+
+    // The job doesn't run until the (single threaded) test runtime is yielded to
+    let req = test::TestRequest::get()
+        .uri(&format!("{location}/result"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+
+    let mut status = Value::Null;
+    for _ in 0..100 {
+        let req = test::TestRequest::get().uri(&location).to_request();
+        let info: Value = test::call_and_read_body_json(&app, req).await;
+        status = info["status"].clone();
+        if status != "Running" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(status, "Finished");
+
+    let req = test::TestRequest::get()
+        .uri(&format!("{location}/result"))
+        .to_request();
+    let res: Value = test::call_and_read_body_json(&app, req).await;
+    let columns = res["columns"].as_array().unwrap();
+    assert!(columns.iter().any(|c| c["name"] == "Balance_sum"));
+
+    // Finished jobs stay finished
+    let req = test::TestRequest::delete().uri(&location).to_request();
+    let info: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(info["status"], "Finished");
+}
+
+#[actix_web::test]
+async fn cancel() {
+    let app = test::init_service(
+        App::new()
+            .configure(state())
+            .configure(routers::configure()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/FRTB/jobs")
+        .set_json(request())
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let location = res
+        .headers()
+        .get("Location")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    // Cancelled before it was run, see above
+    let req = test::TestRequest::delete().uri(&location).to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let info: Value = test::read_body_json(res).await;
+    assert_eq!(info["status"], "Cancelled");
+
+    let req = test::TestRequest::get()
+        .uri(&format!("{location}/result"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::EXPECTATION_FAILED);
+
+    for uri in ["/api/FRTB/jobs/999", "/api/FRTB/jobs/999/result"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND, "{uri}");
+    }
+    let req = test::TestRequest::delete()
+        .uri("/api/FRTB/jobs/999")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use ultibi_core::{errors::UltimaErr, polars::df, ExecutionContext};
+use ultibi_server::metrics::Metrics;
+
+#[test]
+fn render_prometheus() {
+    let metrics = Metrics::default();
+    metrics.observe_request("POST", "/api/FRTB", 200, Duration::from_millis(20));
+    metrics.observe_request("POST", "/api/FRTB", 417, Duration::from_millis(3));
+
+    let ctx = ExecutionContext::new();
+    ctx.start_timer();
+    ctx.checkpoint("filter").unwrap();
+    ctx.checkpoint("groupby").unwrap();
+    ctx.record_cache_lookup(true);
+    ctx.record_cache_lookup(false);
+    let ok = df!("Balance_sum" => [1.0]).unwrap();
+    metrics.observe_compute(&ctx, &Ok(ok));
+    metrics.observe_compute(
+        &ExecutionContext::new(),
+        &Err(UltimaErr::Other("boom".to_string())),
+    );
+
+    let text = metrics.render(3);
+    for line in [
+        r#"ultibi_http_requests_total{method="POST",route="/api/FRTB",status="200"} 1"#,
+        r#"ultibi_http_request_errors_total{method="POST",route="/api/FRTB"} 1"#,
+        r#"ultibi_http_request_duration_seconds_bucket{method="POST",route="/api/FRTB",le="0.025"} 2"#,
+        r#"ultibi_http_request_duration_seconds_count{method="POST",route="/api/FRTB"} 2"#,
+        "ultibi_computations_total 2",
+        "ultibi_compute_errors_total 1",
+        r#"ultibi_compute_stage_duration_seconds_count{stage="groupby"} 1"#,
+        "ultibi_cache_hit_ratio 0.5",
+        "ultibi_jobs_in_flight 3",
+    ] {
+        assert!(text.lines().any(|l| l == line), "{line} not in\n{text}");
+    }
+}
@@ -0,0 +1,159 @@
+use actix_web::{http::StatusCode, test, web, App, HttpResponse};
+use serde_json::Value;
+use ultibi_server::api::{open_api::ApiDoc, routers};
+use utoipa::OpenApi;
+
+fn spec() -> Value {
+    serde_json::to_value(ApiDoc::openapi()).expect("Spec is serialisable")
+}
+
+/// Every path of the spec is routed with the documented method. Unknown paths hit
+/// the default service (418) and known paths with a wrong method get 405
+#[actix_web::test]
+async fn paths_match_routes() {
+    let app = test::init_service(
+        App::new()
+            .configure(routers::configure())
+            .default_service(web::to(|| async { HttpResponse::ImATeapot() })),
+    )
+    .await;
+
+    let spec = spec();
+    let paths = spec["paths"].as_object().expect("Spec has paths");
+    assert!(paths.contains_key("/api/FRTB"));
+
+    for (path, item) in paths {
+        // Any value will do for path parameters, since we only check routing
+        let uri = path
+            .split('/')
+            .map(|s| match s.starts_with('{') {
+                true => "1",
+                false => s,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        for method in item.as_object().expect("Path item is an object").keys() {
+            let req = test::TestRequest::default()
+                .method(method.to_uppercase().parse().expect("Valid method"))
+                .uri(&uri)
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_ne!(res.status(), StatusCode::IM_A_TEAPOT, "{method} {path}");
+            assert_ne!(
+                res.status(),
+                StatusCode::METHOD_NOT_ALLOWED,
+                "{method} {path}"
+            );
+        }
+    }
+}
+
+/// (method, path) of every route of [routers::configure], since actix can't list its
+/// routes. Add new routes here as well
+const ROUTES: &[(&str, &str)] = &[
+    ("get", "/api/aggtypes"),
+    ("post", "/api/describe"),
+    ("get", "/api/health_check"),
+    ("get", "/api/live"),
+    ("get", "/api/ready"),
+    ("get", "/api/status"),
+    ("get", "/api/metrics"),
+    ("get", "/api/admin/reload"),
+    ("post", "/api/admin/reload"),
+    ("post", "/api/admin/data/append"),
+    ("post", "/api/admin/data/delete"),
+    ("get", "/api/FRTB"),
+    ("post", "/api/FRTB"),
+    ("post", "/api/FRTB/batch"),
+    ("post", "/api/FRTB/drillthrough"),
+    ("get", "/api/FRTB/columns/{column_name}"),
+    ("get", "/api/FRTB/templates"),
+    ("get", "/api/FRTB/templates/{name}"),
+    ("post", "/api/FRTB/templates"),
+    ("put", "/api/FRTB/templates/{name}"),
+    ("delete", "/api/FRTB/templates/{name}"),
+    ("post", "/api/FRTB/templates/{name}/execute"),
+    ("get", "/api/FRTB/overrides"),
+    ("post", "/api/FRTB/jobs"),
+    ("get", "/api/FRTB/jobs/{id}"),
+    ("get", "/api/FRTB/jobs/{id}/result"),
+    ("delete", "/api/FRTB/jobs/{id}"),
+    ("get", "/api/FRTB/audit"),
+    ("get", "/api/FRTB/audit/{id}"),
+    ("post", "/api/FRTB/audit/{id}/replay"),
+];
+
+/// Every route is documented with its method
+#[test]
+fn routes_are_documented() {
+    let spec = spec();
+    for (method, path) in ROUTES {
+        assert!(
+            spec["paths"][path].get(method).is_some(),
+            "{method} {path} is routed, but not documented"
+        );
+    }
+}
+
+/// Clients are generated from the spec, so every referenced schema must be defined
+#[test]
+fn schemas_are_defined() {
+    fn refs(v: &Value, acc: &mut Vec<String>) {
+        match v {
+            Value::Object(map) => {
+                if let Some(Value::String(r)) = map.get("$ref") {
+                    acc.push(r.clone());
+                }
+                map.values().for_each(|v| refs(v, acc));
+            }
+            Value::Array(items) => items.iter().for_each(|v| refs(v, acc)),
+            _ => (),
+        }
+    }
+
+    let spec = spec();
+    let schemas = spec["components"]["schemas"]
+        .as_object()
+        .expect("Spec has schemas");
+    for name in [
+        "ComputeRequest",
+        "AggregationRequest",
+        "FilterE",
+        "DataFrame",
+    ] {
+        assert!(schemas.contains_key(name), "{name} is not documented");
+    }
+
+    let mut all = vec![];
+    refs(&spec, &mut all);
+    for r in all {
+        let name = r
+            .strip_prefix("#/components/schemas/")
+            .unwrap_or_else(|| panic!("Unexpected reference {r}"));
+        assert!(
+            schemas.contains_key(name),
+            "{name} is referenced, but not defined"
+        );
+    }
+}
+
+#[test]
+fn unauthenticated_probes() {
+    let spec = spec();
+    for path in [
+        "/api/health_check",
+        "/api/live",
+        "/api/ready",
+        "/api/metrics",
+    ] {
+        let op = &spec["paths"][path]["get"];
+        assert!(
+            op["responses"].get("401").is_none(),
+            "{path} should not require authentication"
+        );
+    }
+    assert!(spec["paths"]["/api/FRTB"]["post"]["responses"]
+        .get("401")
+        .is_some());
+}
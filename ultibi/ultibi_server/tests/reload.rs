@@ -0,0 +1,80 @@
+use std::sync::{Arc, RwLock};
+
+use ultibi_core::{datasource::DataSource, polars::df, DataSet, DataSetBase};
+use ultibi_server::reload::{LoadStatus, ReloadError, Reloader};
+
+fn dataset(balance: f64) -> DataSetBase {
+    DataSetBase {
+        source: DataSource::InMemory(df!("State" => ["NY"], "Balance" => [balance]).unwrap()),
+        ..Default::default()
+    }
+}
+
+fn balance(ds: &RwLock<DataSetBase>) -> Vec<Option<f64>> {
+    ds.read()
+        .unwrap()
+        .get_column("Balance")
+        .unwrap()
+        .f64()
+        .unwrap()
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn reload_swaps_data() {
+    let ds = Arc::new(RwLock::new(dataset(1.)));
+    let reloader = Reloader::new(ds.clone(), || Ok(dataset(2.)));
+    assert_eq!(reloader.status(), LoadStatus::Initial);
+
+    reloader.reload().unwrap();
+
+    assert_eq!(balance(&ds), vec![Some(2.)]);
+    assert!(matches!(
+        reloader.status(),
+        LoadStatus::Loaded { data_version, .. } if data_version == "reload-1"
+    ));
+}
+
+#[test]
+fn failed_reload_keeps_data() {
+    let ds = Arc::new(RwLock::new(dataset(1.)));
+    let reloader = Reloader::new(ds.clone(), || -> ultibi_core::errors::UltiResult<_> {
+        panic!("File path doesn't exist")
+    });
+
+    assert!(matches!(reloader.reload(), Err(ReloadError::Failed(_))));
+
+    assert_eq!(balance(&ds), vec![Some(1.)]);
+    assert!(matches!(
+        reloader.status(),
+        LoadStatus::Failed { error, .. } if error.contains("File path doesn't exist")
+    ));
+}
+
+#[test]
+fn deferred_ready_after_first_load() {
+    let ds = Arc::new(RwLock::new(DataSetBase::default()));
+    let reloader = Reloader::deferred(ds.clone(), || Ok(dataset(2.)));
+    assert!(!reloader.is_ready());
+    assert_eq!(reloader.loaded_at(), None);
+
+    reloader.reload().unwrap();
+
+    assert!(reloader.is_ready());
+    assert!(reloader.loaded_at().is_some());
+    assert_eq!(balance(&ds), vec![Some(2.)]);
+}
+
+#[test]
+fn failed_reload_keeps_readiness() {
+    let ds = Arc::new(RwLock::new(dataset(1.)));
+    let reloader = Reloader::new(ds, || -> ultibi_core::errors::UltiResult<_> {
+        panic!("File path doesn't exist")
+    });
+    assert!(reloader.is_ready());
+
+    assert!(reloader.reload().is_err());
+
+    assert!(reloader.is_ready());
+}
@@ -0,0 +1,77 @@
+use std::{env, fs};
+
+use ultibi_core::AggregationRequest;
+use ultibi_server::{
+    auth::{AuthenticatedUser, Entitlement},
+    templates::{TemplateError, TemplateStore},
+};
+
+fn request(name: &str) -> AggregationRequest {
+    let req = format!(
+        r#"{{"name": "{name}", "measures": [["Balance", "sum"]], "groupby": ["State"], "filters": []}}"#
+    );
+    serde_json::from_str::<AggregationRequest>(&req).expect("Could not parse request")
+}
+
+fn user(name: &str) -> AuthenticatedUser {
+    AuthenticatedUser {
+        name: name.to_string(),
+        entitlement: Entitlement::default(),
+    }
+}
+
+#[test]
+fn crud_with_ownership() {
+    let path = env::temp_dir().join(format!("ultibi_templates_{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let store = TemplateStore::new(Some(path.clone()), vec![request("Start up")]).unwrap();
+    let (alice, bob) = (user("alice"), user("bob"));
+
+    store.create(request("Mine"), false, &alice).unwrap();
+    store.create(request("Ours"), true, &alice).unwrap();
+    // Private templates of others don't clash, nor are they revealed
+    store.create(request("Mine"), false, &bob).unwrap();
+    assert!(matches!(
+        store.create(request("Ours"), false, &bob),
+        Err(TemplateError::Conflict(_))
+    ));
+    assert!(matches!(
+        store.create(request("Mine"), false, &alice),
+        Err(TemplateError::Conflict(_))
+    ));
+    assert!(matches!(
+        store.create(request("Start up"), false, &bob),
+        Err(TemplateError::Conflict(_))
+    ));
+
+    assert_eq!(store.visible(&alice).len(), 3);
+    // Bob sees start up, shared and his own templates
+    assert_eq!(store.visible(&bob).len(), 3);
+    assert_eq!(store.get("Mine", &bob).unwrap().owner, "bob");
+    assert_eq!(store.get("Mine", &alice).unwrap().owner, "alice");
+
+    // Only owners can modify
+    assert!(matches!(
+        store.delete("Ours", &bob),
+        Err(TemplateError::Forbidden(_))
+    ));
+    assert!(matches!(
+        store.delete("Start up", &alice),
+        Err(TemplateError::Forbidden(_))
+    ));
+
+    // Rename
+    store
+        .update("Mine", request("Renamed"), Some(true), &alice)
+        .unwrap();
+    assert_eq!(store.get("Renamed", &bob).unwrap().owner, "alice");
+
+    // Persisted, apart from start up templates
+    let reopened = TemplateStore::new(Some(path.clone()), vec![]).unwrap();
+    assert_eq!(reopened.visible(&alice).len(), 2);
+    reopened.delete("Ours", &alice).unwrap();
+    assert_eq!(reopened.visible(&bob).len(), 2);
+
+    fs::remove_file(&path).unwrap();
+}